@@ -16,10 +16,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let signing_key = SigningKey::from_bytes(&private_key.0);
 
     // Creates a new environment
-    let configs = EnvConfigs {
-        rpc_url: "https://soroban-testnet.stellar.org".to_string(),
-        network_passphrase: "Test SDF Network ; September 2015".to_string(),
-    };
+    let configs = EnvConfigs::new(
+        "https://soroban-testnet.stellar.org",
+        "Test SDF Network ; September 2015",
+    );
     let env = Env::new(configs)?;
 
     // Initializes a new account
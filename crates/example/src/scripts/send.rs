@@ -1,9 +1,8 @@
 use dotenv::dotenv;
 use ed25519_dalek::SigningKey;
 use soroban_rs::{
-    macros::soroban,
-    xdr::{ScAddress, ScVal},
-    Account, ClientContractConfigs, ContractId, Env, EnvConfigs, Guard, Signer,
+    macros::soroban, xdr::ScAddress, Account, ClientContractConfigs, ContractId, Env, EnvConfigs,
+    Guard, Signer,
 };
 use std::{env, error::Error};
 use stellar_strkey::ed25519::PrivateKey;
@@ -42,10 +41,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let signing_key = SigningKey::from_bytes(&private_key.0);
 
     // Creates a new environment
-    let configs = EnvConfigs {
-        rpc_url: "https://soroban-testnet.stellar.org".to_string(),
-        network_passphrase: "Test SDF Network ; September 2015".to_string(),
-    };
+    let configs = EnvConfigs::new(
+        "https://soroban-testnet.stellar.org",
+        "Test SDF Network ; September 2015",
+    );
     let env = Env::new(configs)?;
 
     // Initializes a new account
@@ -61,14 +60,14 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let client_configs = ClientContractConfigs {
         env: env.clone(),
         contract_id,
-        source_account: account.clone(),
+        source_account: Some(account.clone()),
     };
     let mut token_client = TokenClient::new(&client_configs);
 
-    let alice = ScVal::Address(ScAddress::Account(account.account_id()));
-    let bob = ScVal::Address(ScAddress::Account(account.account_id()));
+    let alice = ScAddress::Account(account.account_id());
+    let bob = ScAddress::Account(account.account_id());
     let res = token_client.send(alice, bob).await?;
 
-    println!("Invocation result: {:?}", res.get_return_value());
+    println!("Invocation result: {:?}", res);
     Ok(())
 }
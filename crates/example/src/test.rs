@@ -1,7 +1,24 @@
 #![cfg(test)]
+extern crate std;
 
 use super::*;
-use soroban_sdk::{testutils::Address as AddressTrait, vec, Address, Env};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as AddressTrait, AuthorizedFunction},
+    vec, Address, BytesN, Env, String, Symbol,
+};
+
+// Contract requiring authorization, used to test the `mock_all_auths` flag.
+#[contract]
+struct Guarded;
+
+#[contractimpl]
+impl Guarded {
+    pub fn guarded(admin: Address) -> Address {
+        admin.require_auth();
+        admin
+    }
+}
 
 // Default test implementation.
 #[test]
@@ -26,3 +43,123 @@ fn test_injected_args(env: Env, alice: Address, bob: Address) {
     let words = client.send(&alice, &bob);
     assert_eq!(words, vec![&env, alice.to_string(), bob.to_string(),]);
 }
+
+// Test implementation taking the env by reference.
+#[soroban_test_helpers::test]
+fn test_env_by_reference(env: &Env, alice: Address, bob: Address) {
+    let contract_id = env.register(Token, (19_u32,));
+    let client = TokenClient::new(env, &contract_id);
+
+    let words = client.send(&alice, &bob);
+    assert_eq!(words, vec![env, alice.to_string(), bob.to_string(),]);
+}
+
+// Test implementation generating non-`Address` arguments.
+#[soroban_test_helpers::test]
+fn test_injected_bytes_and_symbols(
+    env: Env,
+    alice: Address,
+    salt: BytesN<32>,
+    other_salt: BytesN<32>,
+    topic: Symbol,
+    #[generate_with = String::from_str(&env, "memo")] memo: String,
+) {
+    assert_eq!(salt, BytesN::from_array(&env, &[2; 32]));
+    assert_ne!(salt, other_salt);
+    assert_eq!(topic, Symbol::new(&env, "topic"));
+    assert_eq!(memo, String::from_str(&env, "memo"));
+
+    let contract_id = env.register(Token, (19_u32,));
+    let client = TokenClient::new(&env, &contract_id);
+    assert_eq!(client.send(&alice, &alice).len(), 2);
+}
+
+// Test implementation with a configured ledger.
+#[soroban_test_helpers::test(ledger_timestamp = 12345, protocol = 23, sequence_number = 100)]
+fn test_configured_ledger(env: Env, alice: Address) {
+    assert_eq!(env.ledger().timestamp(), 12345);
+    assert_eq!(env.ledger().protocol_version(), 23);
+    assert_eq!(env.ledger().sequence(), 100);
+
+    let contract_id = env.register(Token, (19_u32,));
+    let client = TokenClient::new(&env, &contract_id);
+    assert_eq!(client.send(&alice, &alice).len(), 2);
+}
+
+// Test implementation returning a `Result`.
+#[soroban_test_helpers::test]
+fn test_result_return(env: Env, alice: Address, bob: Address) -> Result<(), std::string::String> {
+    let contract_id = env.register(Token, (19_u32,));
+    let client = TokenClient::new(&env, &contract_id);
+
+    let words = client
+        .try_send(&alice, &bob)
+        .map_err(|err| std::format!("{:?}", err))?
+        .map_err(|err| std::format!("{:?}", err))?;
+    assert_eq!(words, vec![&env, alice.to_string(), bob.to_string(),]);
+    Ok(())
+}
+
+// Test implementation with a registered contract and an injected client.
+#[soroban_test_helpers::test(register = Token, constructor_args = (19_u32,))]
+fn test_injected_client(env: Env, client: TokenClient, alice: Address, bob: Address) {
+    let words = client.send(&alice, &bob);
+    assert_eq!(words, vec![&env, alice.to_string(), bob.to_string(),]);
+}
+
+// Test implementation with mocked authorizations and a registered contract without constructor.
+#[soroban_test_helpers::test(mock_all_auths, register = Guarded)]
+fn test_injected_client_without_constructor(env: Env, client: GuardedClient, admin: Address) {
+    assert_eq!(client.guarded(&admin), admin);
+    assert_eq!(env.auths().len(), 1);
+}
+
+// Test implementation with mocked authorizations.
+#[soroban_test_helpers::test(mock_all_auths)]
+fn test_mock_all_auths(env: Env, admin: Address) {
+    let contract_id = env.register(Guarded, ());
+    let client = GuardedClient::new(&env, &contract_id);
+
+    assert_eq!(client.guarded(&admin), admin);
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, admin);
+    assert!(matches!(
+        auths[0].1.function,
+        AuthorizedFunction::Contract(_)
+    ));
+}
+
+// Addresses derived from the seed `42` by the test macro.
+const SEEDED_ADDRESSES: [&str; 2] = [
+    "CAUO7YZTWJTPCA2HKJTVOEYPT5JFQHHB74HEVY4UBG6FQWREJAR7EEPU",
+    "CBP5GDJPZPXXLY3FIXJ3JCYFZF2NRGFCGG4QNQEP2ODIRXIFCI5R5P4U",
+];
+
+// Test implementation with seeded addresses.
+#[soroban_test_helpers::test(seed = 42)]
+fn test_seeded_addresses(env: Env, alice: Address, bob: Address) {
+    assert_eq!(alice, Address::from_str(&env, SEEDED_ADDRESSES[0]));
+    assert_eq!(bob, Address::from_str(&env, SEEDED_ADDRESSES[1]));
+}
+
+// Same seed in another test, with other arguments in between, yields the same addresses.
+#[soroban_test_helpers::test(seed = 42, register = Token, constructor_args = (19_u32,))]
+fn test_seeded_addresses_are_reproducible(
+    env: Env,
+    client: TokenClient,
+    alice: Address,
+    salt: BytesN<32>,
+    bob: Address,
+) {
+    assert_eq!(alice, Address::from_str(&env, SEEDED_ADDRESSES[0]));
+    assert_eq!(bob, Address::from_str(&env, SEEDED_ADDRESSES[1]));
+    assert_eq!(salt, BytesN::from_array(&env, &[3; 32]));
+    assert_eq!(client.send(&alice, &bob).len(), 2);
+}
+
+// A different seed yields different addresses.
+#[soroban_test_helpers::test(seed = 7)]
+fn test_seeded_addresses_differ_by_seed(env: Env, alice: Address) {
+    assert_ne!(alice, Address::from_str(&env, SEEDED_ADDRESSES[0]));
+}
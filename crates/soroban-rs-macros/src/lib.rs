@@ -13,7 +13,7 @@
 //!
 //! ```rust,ignore
 //! use soroban_rs_macros::soroban;
-//! use soroban_rs::{xdr::ScVal, ClientContractConfigs};
+//! use soroban_rs::{xdr::ScAddress, ClientContractConfigs};
 //!
 //! soroban!(r#"
 //!     pub struct Token;
@@ -30,13 +30,18 @@
 //!     let client_configs = ClientContractConfigs::new(/* ... */);
 //!     let mut token_client = TokenClient::new(&client_configs);
 //!     
-//!     // Call the contract method with ScVal parameters
-//!     let result = token_client.transfer(from_scval, to_scval, amount_scval).await;
+//!     // Call the contract method with typed parameters
+//!     let result = token_client.transfer(from_address, to_address, 1_000u128).await;
 //! }
 //! ```
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, File, FnArg, Item, ReturnType};
+use syn::{
+    parse_macro_input, File, FnArg, GenericArgument, Ident, ImplItemFn, Item, LitStr,
+    PathArguments, ReturnType, Type,
+};
+
+mod spec;
 
 /// A procedural macro for generating Soroban contract client code.
 ///
@@ -49,8 +54,14 @@ use syn::{parse_macro_input, File, FnArg, Item, ReturnType};
 /// 2. Extracts the contract's public methods
 /// 3. Generates a client struct with matching methods that:
 ///    - Skip the first parameter (env)
-///    - Convert all other parameters to use `ScVal` types
-///    - Return `Result<GetTransactionResponse, SorobanHelperError>`
+///    - Keep the type of all other parameters, mapping Soroban SDK types to client
+///      types implementing `IntoScVal` (e.g. `Address` to `ScAddress`, `Symbol` to
+///      `SymbolVal`); types without a counterpart are taken as `ScVal`
+///    - Return the contract's return value decoded with `FromScVal`, e.g.
+///      `Result<u32, SorobanHelperError>` for a method returning `u32`; methods
+///      without a return value return `Result<SorobanTransactionResponse, SorobanHelperError>`
+/// 4. Methods marked with `#[view]` only simulate the invocation through
+///    `invoke_read_only`, so queries don't submit a transaction nor pay fees
 ///
 /// # Parameters
 ///
@@ -62,6 +73,9 @@ use syn::{parse_macro_input, File, FnArg, Item, ReturnType};
 /// - A `TokenClient` struct with client configuration
 /// - Methods matching the contract's public interface but with modified signatures
 /// - A `new` method to instantiate the client
+/// - A `deploy` method deploying the contract, taking the `__constructor` parameters
+/// - A `TokenClientApi` async trait with the same methods, implemented by `TokenClient`,
+///   to substitute the client with a mock in tests
 ///
 /// # Example
 ///
@@ -73,6 +87,11 @@ use syn::{parse_macro_input, File, FnArg, Item, ReturnType};
 ///         pub fn increment(env: &Env, amount: u32) -> u32 {
 ///             // Contract implementation...
 ///         }
+///
+///         #[view]
+///         pub fn get(env: &Env) -> u32 {
+///             // Contract implementation...
+///         }
 ///     }
 /// "#);
 /// // or
@@ -80,22 +99,37 @@ use syn::{parse_macro_input, File, FnArg, Item, ReturnType};
 ///
 /// // Use the generated client:
 /// let mut counter_client = CounterClient::new(&client_configs);
-/// let result = counter_client.increment(amount_scval).await?;
+/// let count: u32 = counter_client.increment(5).await?;
+/// // Simulated only, no transaction is submitted
+/// let count: u32 = counter_client.get().await?;
 /// ```
 #[proc_macro]
 pub fn soroban(input: TokenStream) -> TokenStream {
-    let lit = parse_macro_input!(input as syn::LitStr);
+    let lit = parse_macro_input!(input as LitStr);
+    expand(&lit)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates the client of the contract given by `lit`, either its code or the path to it.
+///
+/// # Errors
+///
+/// Returns an error pointing at `lit` if the file can't be read, the code can't be
+/// parsed or it doesn't declare the contract struct
+fn expand(lit: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
     let value = lit.value();
 
     let code = if value.ends_with(".rs") {
-        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
-        let full_path = std::path::Path::new(&manifest_dir).join(&value);
-        std::fs::read_to_string(&full_path)
-            .unwrap_or_else(|_| panic!("Failed to read file at {}", full_path.display()))
+        String::from_utf8(read_file(lit)?).map_err(|e| {
+            syn::Error::new_spanned(lit, format!("Invalid UTF-8 in {}: {}", value, e))
+        })?
     } else {
         value
     };
-    let file_ast: File = syn::parse_str(&code).expect("Failed to parse input");
+    let file_ast: File = syn::parse_str(&code).map_err(|e| {
+        syn::Error::new_spanned(lit, format!("Failed to parse contract code: {}", e))
+    })?;
 
     let mut struct_name = None;
     let mut methods = Vec::new();
@@ -115,9 +149,20 @@ pub fn soroban(input: TokenStream) -> TokenStream {
         }
     }
 
-    let struct_ident = struct_name.expect("No struct found");
+    let struct_ident = struct_name.ok_or_else(|| {
+        syn::Error::new_spanned(
+            lit,
+            "No contract struct found, expected a declaration like `pub struct Token;`",
+        )
+    })?;
     let client_struct_ident = format_ident!("{}Client", struct_ident);
 
+    // The constructor parameters become the arguments of the generated deploy
+    let constructor = methods
+        .iter()
+        .find(|method| method.sig.ident == "__constructor")
+        .map(method_params);
+
     // Transform each method according to your requirement
     let transformed_methods = methods.iter().filter_map(|method| {
         let method_name = &method.sig.ident;
@@ -128,67 +173,426 @@ pub fn soroban(input: TokenStream) -> TokenStream {
             return None;
         }
 
-        // Transform inputs: Skip first arg (env), transform rest
-        let transformed_inputs: Vec<_> = method
-            .sig
-            .inputs
-            .iter()
-            .skip(1)
-            .map(|arg| match arg {
-                FnArg::Typed(pat_type) => {
-                    let pat = &pat_type.pat;
-                    quote! { #pat : soroban_rs::xdr::ScVal }
-                }
-                FnArg::Receiver(r) => quote! { #r },
-            })
-            .collect();
-
-        // Also create a list of just the parameter names for the invoke call
-        let param_names = method
-            .sig
-            .inputs
-            .iter()
-            .skip(1)
-            .map(|arg| match arg {
-                FnArg::Typed(pat_type) => {
-                    let pat = &pat_type.pat;
-                    quote! { #pat }
-                }
-                FnArg::Receiver(r) => quote! { #r },
-            })
-            .collect::<Vec<_>>();
-
-        // Transform return type to ScVal
-        let transformed_output = match &method.sig.output {
-            ReturnType::Default => quote! {},
-            ReturnType::Type(_, _) => {
-                quote! { -> Result<soroban_rs::SorobanTransactionResponse, soroban_rs::SorobanHelperError>  }
-            }
+        let params = method_params(method);
+
+        // Decode the return value to the declared type, unit methods return the response
+        let output = match &method.sig.output {
+            ReturnType::Type(_, ty) if !is_unit(ty) => Some(return_type(ty)),
+            _ => None,
         };
 
-        Some(quote! {
-            pub async fn #method_name(&mut self, #(#transformed_inputs),*) #transformed_output {
-                // internally calls invoke API.
-                self.contract.invoke(stringify!(#method_name), vec![#(#param_names),*]).await
+        Some(client_method(
+            method_name,
+            "",
+            &params,
+            output,
+            is_view(method),
+        ))
+    });
+
+    Ok(client_struct(
+        &client_struct_ident,
+        constructor,
+        transformed_methods,
+    ))
+}
+
+/// Reads the file at the path given by `lit`, relative to the crate root.
+///
+/// # Errors
+///
+/// Returns an error pointing at `lit` if the file can't be read
+fn read_file(lit: &LitStr) -> syn::Result<Vec<u8>> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = std::path::Path::new(&manifest_dir).join(lit.value());
+    std::fs::read(&full_path).map_err(|e| {
+        syn::Error::new_spanned(
+            lit,
+            format!("Failed to read file at {}: {}", full_path.display(), e),
+        )
+    })
+}
+
+/// Returns the parameter names of a contract method along with their client types.
+///
+/// The first parameter, the contract env, is skipped.
+fn method_params(method: &ImplItemFn) -> Vec<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+    method
+        .sig
+        .inputs
+        .iter()
+        .skip(1)
+        .filter_map(|arg| match arg {
+            FnArg::Typed(pat_type) => {
+                let pat = &pat_type.pat;
+                Some((quote! { #pat }, client_type(&pat_type.ty)))
             }
+            FnArg::Receiver(_) => None,
         })
+        .collect()
+}
+
+/// A procedural macro for generating Soroban contract client code from a contract spec.
+///
+/// Unlike `soroban!`, the client is generated from the interface embedded in the
+/// contract WASM (its `contractspecv0` section), so it matches the deployed
+/// contract exactly. The spec can also be given as a file holding the stream of
+/// XDR encoded `ScSpecEntry`.
+///
+/// # Parameters
+///
+/// * `input`: The contract name, followed by the path to the contract WASM or spec,
+///   relative to the crate root
+///
+/// # Generated Code
+///
+/// For a contract named `Token`, the macro generates:
+/// - A `TokenClient` struct with the same constructors as the `soroban!` clients
+/// - A typed method per contract function, returning the decoded return value
+///   (or the transaction response for functions without a return value). Functions
+///   returning `Result<T, E>` return `Result<T, ContractError>`, holding the contract
+///   error if the function failed
+/// - A type per user defined struct, enum and union of the contract, implementing
+///   `IntoScVal` and `FromScVal`
+///
+/// # Example
+///
+/// ```rust,ignore
+/// soroban_spec!(Token, "path/to/token.wasm");
+///
+/// // Use the generated client:
+/// let mut token_client = TokenClient::new(&client_configs);
+/// let allowance: Allowance = token_client.allowance(from, spender).await?;
+/// ```
+#[proc_macro]
+pub fn soroban_spec(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as spec::SpecInput);
+    spec::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A generated client method, split in parts to emit both the inherent method
+/// and its declaration in the client trait.
+struct ClientMethod {
+    /// Documentation attribute, if any
+    doc: Option<proc_macro2::TokenStream>,
+    /// Method name
+    name: Ident,
+    /// Parameter names, excluding the receiver
+    args: Vec<proc_macro2::TokenStream>,
+    /// Signature, from `async fn` to the return type
+    signature: proc_macro2::TokenStream,
+    /// Method body, including the braces
+    body: proc_macro2::TokenStream,
+}
+
+/// Generates a client method invoking the contract function `method_name`.
+///
+/// # Parameters
+///
+/// * `method_name` - The name of the contract function
+/// * `doc` - The documentation of the contract function, if any
+/// * `params` - The parameter names along with their client types
+/// * `output` - The type the return value is decoded to, `None` to return the
+///   transaction response instead
+/// * `view` - Whether to simulate the invocation instead of submitting a transaction
+fn client_method(
+    method_name: &Ident,
+    doc: &str,
+    params: &[(proc_macro2::TokenStream, proc_macro2::TokenStream)],
+    output: Option<proc_macro2::TokenStream>,
+    view: bool,
+) -> ClientMethod {
+    let doc = (!doc.is_empty()).then(|| quote! { #[doc = #doc] });
+    let args: Vec<_> = params.iter().map(|(name, _)| name.clone()).collect();
+    let inputs = params.iter().map(|(name, ty)| quote! { #name: #ty });
+    let param_vals = params
+        .iter()
+        .map(|(name, _)| quote! { soroban_rs::IntoScVal::try_into_val(&#name)? });
+
+    // View methods simulate the invocation instead of submitting a transaction
+    let (signature, body) = if view {
+        let (ret_ty, decode) = match output {
+            Some(ret_ty) => (
+                ret_ty.clone(),
+                quote! { <#ret_ty as soroban_rs::FromScVal>::try_from_val(&val) },
+            ),
+            None => (quote! { () }, quote! { Ok(()) }),
+        };
+        (
+            quote! {
+                async fn #method_name(
+                    &self,
+                    #(#inputs),*
+                ) -> Result<#ret_ty, soroban_rs::SorobanHelperError>
+            },
+            quote! {{
+                // internally calls invoke_read_only API.
+                let val = self
+                    .contract
+                    .invoke_read_only(stringify!(#method_name), vec![#(#param_vals),*])
+                    .await?;
+                #decode
+            }},
+        )
+    } else {
+        match output {
+            Some(ret_ty) => (
+                quote! {
+                    async fn #method_name(
+                        &mut self,
+                        #(#inputs),*
+                    ) -> Result<#ret_ty, soroban_rs::SorobanHelperError>
+                },
+                quote! {{
+                    // internally calls invoke API.
+                    self.contract
+                        .invoke(stringify!(#method_name), vec![#(#param_vals),*])
+                        .await?
+                        .get_return_value_as::<#ret_ty>()
+                }},
+            ),
+            None => (
+                quote! {
+                    async fn #method_name(
+                        &mut self,
+                        #(#inputs),*
+                    ) -> Result<soroban_rs::SorobanTransactionResponse, soroban_rs::SorobanHelperError>
+                },
+                quote! {{
+                    // internally calls invoke API.
+                    self.contract.invoke(stringify!(#method_name), vec![#(#param_vals),*]).await
+                }},
+            ),
+        }
+    };
+
+    ClientMethod {
+        doc,
+        name: method_name.clone(),
+        args,
+        signature,
+        body,
+    }
+}
+
+/// Generates the client struct `client_struct_ident` along with its constructors and `methods`.
+///
+/// The methods are also declared by the `{client_struct_ident}Api` trait,
+/// implemented by the client, so that callers can substitute the client with a mock.
+///
+/// # Parameters
+///
+/// * `client_struct_ident` - The name of the client struct
+/// * `constructor` - The parameters of the contract constructor, if any, taken by `deploy`
+/// * `methods` - The client methods of the contract functions
+fn client_struct(
+    client_struct_ident: &Ident,
+    constructor: Option<Vec<(proc_macro2::TokenStream, proc_macro2::TokenStream)>>,
+    methods: impl Iterator<Item = ClientMethod>,
+) -> proc_macro2::TokenStream {
+    let methods: Vec<ClientMethod> = methods.collect();
+    let trait_ident = format_ident!("{}Api", client_struct_ident);
+
+    let inherent_methods = methods.iter().map(|method| {
+        let ClientMethod {
+            doc,
+            signature,
+            body,
+            ..
+        } = method;
+        quote! { #doc pub #signature #body }
+    });
+    let trait_methods = methods.iter().map(|method| {
+        let ClientMethod { doc, signature, .. } = method;
+        quote! { #doc #signature; }
+    });
+    let trait_impl_methods = methods.iter().map(|method| {
+        let ClientMethod {
+            name,
+            args,
+            signature,
+            ..
+        } = method;
+        quote! {
+            #signature {
+                #client_struct_ident::#name(self, #(#args),*).await
+            }
+        }
     });
 
-    let expanded = quote! {
+    let (constructor_inputs, constructor_args) = match &constructor {
+        Some(params) => {
+            let inputs = params.iter().map(|(name, ty)| quote! { #name: #ty });
+            let vals = params
+                .iter()
+                .map(|(name, _)| quote! { soroban_rs::IntoScVal::try_into_val(&#name)? });
+            (quote! { #(, #inputs)* }, quote! { Some(vec![#(#vals),*]) })
+        }
+        None => (quote! {}, quote! { None }),
+    };
+
+    quote! {
         pub struct #client_struct_ident {
             client_configs: soroban_rs::ClientContractConfigs,
             contract: soroban_rs::Contract,
         }
 
         impl #client_struct_ident {
-            #(#transformed_methods)*
+            #(#inherent_methods)*
             pub fn new(client_configs: &soroban_rs::ClientContractConfigs) -> Self {
                 let contract = soroban_rs::Contract::from_configs(client_configs.clone());
                 Self { client_configs: client_configs.clone(), contract }
             }
 
+            /// Deploys the contract from its WASM file, passing the constructor
+            /// arguments, and returns a client for the deployed instance.
+            pub async fn deploy(
+                env: &soroban_rs::Env,
+                account: &mut soroban_rs::Account,
+                wasm_path: &str
+                #constructor_inputs
+            ) -> Result<Self, soroban_rs::SorobanHelperError> {
+                let contract = soroban_rs::Contract::new(wasm_path, None)?
+                    .deploy(env, account, #constructor_args)
+                    .await?;
+                let contract_id = contract
+                    .contract_id()
+                    .ok_or(soroban_rs::SorobanHelperError::ContractDeployedConfigsNotSet)?;
+                Ok(Self::new(&soroban_rs::ClientContractConfigs::new(
+                    contract_id,
+                    env.clone(),
+                    account.clone(),
+                )))
+            }
+
+            /// Creates a client that can only simulate invocations, without a source account.
+            pub fn read_only(contract_id: soroban_rs::ContractId, env: soroban_rs::Env) -> Self {
+                Self::new(&soroban_rs::ClientContractConfigs::read_only(contract_id, env))
+            }
+
+            /// Simulates a contract function invocation and returns its result.
+            pub async fn invoke_read_only(
+                &self,
+                function_name: &str,
+                args: Vec<soroban_rs::xdr::ScVal>,
+            ) -> Result<soroban_rs::xdr::ScVal, soroban_rs::SorobanHelperError> {
+                self.contract.invoke_read_only(function_name, args).await
+            }
+
         }
-    };
 
-    expanded.into()
+        /// The contract functions of the client, to depend on instead of the
+        /// client itself and substitute it with a mock in tests.
+        #[soroban_rs::async_trait]
+        pub trait #trait_ident {
+            #(#trait_methods)*
+        }
+
+        #[soroban_rs::async_trait]
+        impl #trait_ident for #client_struct_ident {
+            #(#trait_impl_methods)*
+        }
+    }
+}
+
+/// Maps the type of a contract parameter to the client type passed to the generated method.
+///
+/// Soroban SDK types are replaced by native types implementing `IntoScVal`, e.g.
+/// `Address` by `ScAddress` and `Map<K, V>` by `BTreeMap<K, V>`. Types without a
+/// native counterpart, such as `#[contracttype]` structs, are passed as `ScVal`,
+/// and `Vec<u8>` as `Vec<ScVal>`, as only `Bytes` maps to a byte vector.
+fn client_type(ty: &Type) -> proc_macro2::TokenStream {
+    native_type(ty, false)
+}
+
+/// Maps the return type of a contract method to the type the generated method decodes to.
+///
+/// Uses the same native types as the parameters, which all implement `FromScVal`,
+/// except for `u8` that is returned as `ScVal`.
+fn return_type(ty: &Type) -> proc_macro2::TokenStream {
+    native_type(ty, true)
+}
+
+/// Maps a Soroban SDK type to its native counterpart, or to `ScVal` if it has none.
+///
+/// With `decode`, only types implementing `FromScVal` are used.
+fn native_type(ty: &Type, decode: bool) -> proc_macro2::TokenStream {
+    let fallback = quote! { soroban_rs::xdr::ScVal };
+    match ty {
+        Type::Reference(reference) => native_type(&reference.elem, decode),
+        Type::Paren(paren) => native_type(&paren.elem, decode),
+        Type::Tuple(tuple) if (2..=4).contains(&tuple.elems.len()) => {
+            let elems = tuple.elems.iter().map(|elem| native_type(elem, decode));
+            quote! { (#(#elems,)*) }
+        }
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let Some(segment) = type_path.path.segments.last() else {
+                return fallback;
+            };
+            let args: Vec<&Type> = match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            match (segment.ident.to_string().as_str(), args.as_slice()) {
+                // `u8` can't be decoded, as `Vec<u8>` decodes from `ScVal::Bytes`
+                ("u8", []) if decode => fallback,
+                (
+                    "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128"
+                    | "bool",
+                    [],
+                ) => {
+                    let ident = &segment.ident;
+                    quote! { #ident }
+                }
+                ("Address", []) => quote! { soroban_rs::xdr::ScAddress },
+                ("String", []) => quote! { ::std::string::String },
+                ("Symbol", []) => quote! { soroban_rs::scval::SymbolVal },
+                ("Bytes" | "BytesN", _) => quote! { ::std::vec::Vec<u8> },
+                ("U256", []) => quote! { soroban_rs::scval::U256Bytes },
+                ("I256", []) => quote! { soroban_rs::scval::I256Bytes },
+                // `Vec<u8>` converts from and to `ScVal::Bytes`, while the contract
+                // expects an `ScVal::Vec` of `U32`, so the elements are kept as `ScVal`
+                ("Vec", [elem]) if is_u8(elem) => quote! { ::std::vec::Vec<#fallback> },
+                ("Vec", [elem]) => {
+                    let elem = native_type(elem, decode);
+                    quote! { ::std::vec::Vec<#elem> }
+                }
+                ("Map", [key, val]) => {
+                    let key = native_type(key, decode);
+                    let val = native_type(val, decode);
+                    quote! { ::std::collections::BTreeMap<#key, #val> }
+                }
+                ("Option", [inner]) => {
+                    let inner = native_type(inner, decode);
+                    quote! { ::std::option::Option<#inner> }
+                }
+                _ => fallback,
+            }
+        }
+        _ => fallback,
+    }
+}
+
+/// Returns whether the contract method is marked with the `#[view]` attribute.
+fn is_view(method: &ImplItemFn) -> bool {
+    method.attrs.iter().any(|attr| attr.path().is_ident("view"))
+}
+
+/// Returns whether `ty` is the `u8` type.
+fn is_u8(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.qself.is_none() && type_path.path.is_ident("u8"))
+}
+
+/// Returns whether `ty` is the unit type `()`.
+fn is_unit(ty: &Type) -> bool {
+    matches!(ty, Type::Tuple(tuple) if tuple.elems.is_empty())
 }
@@ -0,0 +1,469 @@
+//! Client generation from a contract spec, see `soroban_spec!`.
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use stellar_xdr::curr::{
+    Limited, Limits, ReadXdr, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0,
+    ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, LitStr, Token,
+};
+
+use crate::{client_method, client_struct, read_file, ClientMethod};
+
+/// Name of the WASM custom section holding the contract spec.
+const SPEC_SECTION: &str = "contractspecv0";
+
+/// Input of `soroban_spec!`: the contract name followed by the path to its spec.
+pub(crate) struct SpecInput {
+    name: Ident,
+    path: LitStr,
+}
+
+impl Parse for SpecInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+        Ok(Self { name, path })
+    }
+}
+
+/// Generates the types and the client of the contract described by the spec at `input.path`.
+///
+/// # Errors
+///
+/// Returns an error pointing at the path if the file can't be read or doesn't
+/// hold a valid contract spec
+pub(crate) fn expand(input: SpecInput) -> syn::Result<TokenStream> {
+    let bytes = read_file(&input.path)?;
+
+    let spec = if bytes.starts_with(b"\0asm") {
+        custom_section(&bytes, SPEC_SECTION).ok_or_else(|| {
+            syn::Error::new_spanned(
+                &input.path,
+                format!("No {} section found in the WASM file", SPEC_SECTION),
+            )
+        })?
+    } else {
+        &bytes
+    };
+    let entries = read_entries(spec).map_err(|e| {
+        syn::Error::new_spanned(
+            &input.path,
+            format!("Failed to decode contract spec: {}", e),
+        )
+    })?;
+
+    // Error enums are left out, errors are returned as `SorobanHelperError`
+    let udts: HashSet<String> = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            ScSpecEntry::UdtStructV0(udt) => Some(udt.name.to_utf8_string_lossy()),
+            ScSpecEntry::UdtUnionV0(udt) => Some(udt.name.to_utf8_string_lossy()),
+            ScSpecEntry::UdtEnumV0(udt) => Some(udt.name.to_utf8_string_lossy()),
+            _ => None,
+        })
+        .collect();
+
+    let types = entries.iter().filter_map(|entry| match entry {
+        ScSpecEntry::UdtStructV0(udt) => Some(struct_type(udt, &udts)),
+        ScSpecEntry::UdtUnionV0(udt) => Some(union_type(udt, &udts)),
+        ScSpecEntry::UdtEnumV0(udt) => Some(enum_type(udt)),
+        _ => None,
+    });
+
+    let methods = entries.iter().filter_map(|entry| match entry {
+        ScSpecEntry::FunctionV0(function) if function.name.0.as_slice() != b"__constructor" => {
+            Some(function_method(function, &udts))
+        }
+        _ => None,
+    });
+
+    // The constructor parameters become the arguments of the generated deploy
+    let constructor = entries.iter().find_map(|entry| match entry {
+        ScSpecEntry::FunctionV0(function) if function.name.0.as_slice() == b"__constructor" => {
+            Some(function_params(function, &udts))
+        }
+        _ => None,
+    });
+
+    let client_struct_ident = format_ident!("{}Client", input.name);
+    let client = client_struct(&client_struct_ident, constructor, methods);
+
+    Ok(quote! {
+        #(#types)*
+        #client
+    })
+}
+
+/// Returns the content of the custom section `name` of a WASM module.
+fn custom_section<'a>(wasm: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    // Skips the magic number and the version
+    let mut pos = 8;
+    while pos < wasm.len() {
+        let id = wasm[pos];
+        pos += 1;
+        let size = read_leb128(wasm, &mut pos)? as usize;
+        let section = wasm.get(pos..pos + size)?;
+        pos += size;
+
+        if id == 0 {
+            let mut name_pos = 0;
+            let name_len = read_leb128(section, &mut name_pos)? as usize;
+            let section_name = section.get(name_pos..name_pos + name_len)?;
+            if section_name == name.as_bytes() {
+                return Some(&section[name_pos + name_len..]);
+            }
+        }
+    }
+    None
+}
+
+/// Reads an unsigned LEB128 integer at `pos`, advancing `pos` past it.
+fn read_leb128(bytes: &[u8], pos: &mut usize) -> Option<u32> {
+    let mut result = 0u32;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*pos)?;
+        *pos += 1;
+        result |= u32::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+    }
+    None
+}
+
+/// Decodes the stream of XDR spec entries of a contract.
+fn read_entries(spec: &[u8]) -> Result<Vec<ScSpecEntry>, stellar_xdr::curr::Error> {
+    let mut reader = Limited::new(spec, Limits::none());
+    ScSpecEntry::read_xdr_iter(&mut reader).collect()
+}
+
+/// Creates an identifier, as a raw identifier for Rust keywords.
+fn ident(name: &str) -> Ident {
+    syn::parse_str::<Ident>(name)
+        .unwrap_or_else(|_| Ident::new_raw(name, proc_macro2::Span::call_site()))
+}
+
+/// Generates the `#[doc]` attribute of a spec entry, if documented.
+fn doc_attr(doc: &str) -> Option<TokenStream> {
+    (!doc.is_empty()).then(|| quote! { #[doc = #doc] })
+}
+
+/// Maps a spec type to the client type implementing both `IntoScVal` and `FromScVal`.
+///
+/// User defined types map to the generated types, and types without a native
+/// counterpart, e.g. timepoints or error enums, are taken as `ScVal`. Results,
+/// only returned by contract functions, map to `Result<T, ContractError>`, which
+/// only implements `FromScVal`.
+fn spec_type(ty: &ScSpecTypeDef, udts: &HashSet<String>) -> TokenStream {
+    match ty {
+        ScSpecTypeDef::Bool => quote! { bool },
+        ScSpecTypeDef::Void => quote! { () },
+        ScSpecTypeDef::U32 => quote! { u32 },
+        ScSpecTypeDef::I32 => quote! { i32 },
+        ScSpecTypeDef::U64 => quote! { u64 },
+        ScSpecTypeDef::I64 => quote! { i64 },
+        ScSpecTypeDef::U128 => quote! { u128 },
+        ScSpecTypeDef::I128 => quote! { i128 },
+        ScSpecTypeDef::U256 => quote! { soroban_rs::scval::U256Bytes },
+        ScSpecTypeDef::I256 => quote! { soroban_rs::scval::I256Bytes },
+        ScSpecTypeDef::Bytes | ScSpecTypeDef::BytesN(_) => quote! { ::std::vec::Vec<u8> },
+        ScSpecTypeDef::String => quote! { ::std::string::String },
+        ScSpecTypeDef::Symbol => quote! { soroban_rs::scval::SymbolVal },
+        ScSpecTypeDef::Address | ScSpecTypeDef::MuxedAddress => {
+            quote! { soroban_rs::xdr::ScAddress }
+        }
+        ScSpecTypeDef::Option(option) => {
+            let inner = spec_type(&option.value_type, udts);
+            quote! { ::std::option::Option<#inner> }
+        }
+        ScSpecTypeDef::Result(result) => {
+            let ok = spec_type(&result.ok_type, udts);
+            quote! { ::std::result::Result<#ok, soroban_rs::ContractError> }
+        }
+        ScSpecTypeDef::Vec(vec) => {
+            let elem = spec_type(&vec.element_type, udts);
+            quote! { ::std::vec::Vec<#elem> }
+        }
+        ScSpecTypeDef::Map(map) => {
+            let key = spec_type(&map.key_type, udts);
+            let val = spec_type(&map.value_type, udts);
+            quote! { ::std::collections::BTreeMap<#key, #val> }
+        }
+        ScSpecTypeDef::Tuple(tuple) if (2..=4).contains(&tuple.value_types.len()) => {
+            let elems = tuple.value_types.iter().map(|ty| spec_type(ty, udts));
+            quote! { (#(#elems,)*) }
+        }
+        ScSpecTypeDef::Udt(udt) if udts.contains(&udt.name.to_utf8_string_lossy()) => {
+            let name = ident(&udt.name.to_utf8_string_lossy());
+            quote! { #name }
+        }
+        _ => quote! { soroban_rs::xdr::ScVal },
+    }
+}
+
+/// Returns the parameter names of a contract function along with their client types.
+fn function_params(
+    function: &ScSpecFunctionV0,
+    udts: &HashSet<String>,
+) -> Vec<(TokenStream, TokenStream)> {
+    function
+        .inputs
+        .iter()
+        .map(|input| {
+            let name = ident(&input.name.to_utf8_string_lossy());
+            (quote! { #name }, spec_type(&input.type_, udts))
+        })
+        .collect()
+}
+
+/// Generates the client method of a contract function.
+fn function_method(function: &ScSpecFunctionV0, udts: &HashSet<String>) -> ClientMethod {
+    let method_name = ident(&function.name.to_utf8_string_lossy());
+    let params = function_params(function, udts);
+    let output = function
+        .outputs
+        .first()
+        .filter(|ty| !matches!(ty, ScSpecTypeDef::Void))
+        .map(|ty| spec_type(ty, udts));
+
+    client_method(
+        &method_name,
+        &function.doc.to_utf8_string_lossy(),
+        &params,
+        output,
+        false,
+    )
+}
+
+/// Generates a `#[contracttype]` struct, encoded as a map keyed by the field
+/// names, or as a vector for tuple structs.
+fn struct_type(udt: &ScSpecUdtStructV0, udts: &HashSet<String>) -> TokenStream {
+    let name = ident(&udt.name.to_utf8_string_lossy());
+    let doc = doc_attr(&udt.doc.to_utf8_string_lossy());
+    let field_names: Vec<String> = udt
+        .fields
+        .iter()
+        .map(|field| field.name.to_utf8_string_lossy())
+        .collect();
+    let field_types: Vec<TokenStream> = udt
+        .fields
+        .iter()
+        .map(|field| spec_type(&field.type_, udts))
+        .collect();
+
+    let is_tuple = !field_names.is_empty()
+        && field_names
+            .iter()
+            .all(|name| name.chars().all(|c| c.is_ascii_digit()));
+
+    let (definition, encode, decode) = if is_tuple {
+        let len = field_names.len();
+        let indexes = (0..len).map(syn::Index::from);
+        let positions = 0..len;
+        (
+            quote! { pub struct #name(#(pub #field_types),*); },
+            quote! {
+                let fields: Vec<soroban_rs::xdr::ScVal> = vec![
+                    #(soroban_rs::IntoScVal::try_into_val(&self.#indexes)?),*
+                ];
+                soroban_rs::IntoScVal::try_into_val(&fields)
+            },
+            quote! {
+                let fields =
+                    <Vec<soroban_rs::xdr::ScVal> as soroban_rs::FromScVal>::try_from_val(val)?;
+                if fields.len() != #len {
+                    return Err(soroban_rs::SorobanHelperError::ConversionError(format!(
+                        "Expected {} fields of {}, got {}",
+                        #len,
+                        stringify!(#name),
+                        fields.len()
+                    )));
+                }
+                Ok(Self(#(soroban_rs::FromScVal::try_from_val(&fields[#positions])?),*))
+            },
+        )
+    } else {
+        let fields: Vec<Ident> = field_names.iter().map(|field| ident(field)).collect();
+        let positions = 0..fields.len();
+        (
+            quote! {
+                pub struct #name {
+                    #(pub #fields: #field_types),*
+                }
+            },
+            quote! {
+                soroban_rs::scval::encode_struct_fields(vec![
+                    #((#field_names, soroban_rs::IntoScVal::try_into_val(&self.#fields)?)),*
+                ])
+            },
+            quote! {
+                let fields = soroban_rs::scval::decode_struct_fields(val, &[#(#field_names),*])?;
+                Ok(Self {
+                    #(#fields: soroban_rs::FromScVal::try_from_val(&fields[#positions])?),*
+                })
+            },
+        )
+    };
+
+    quote! {
+        #doc
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+        #definition
+
+        impl soroban_rs::IntoScVal for #name {
+            fn try_into_val(&self) -> Result<soroban_rs::xdr::ScVal, soroban_rs::SorobanHelperError> {
+                #encode
+            }
+
+            fn into_val(self) -> soroban_rs::xdr::ScVal {
+                self.try_into_val().expect(concat!("Failed to convert ", stringify!(#name), " to ScVal"))
+            }
+        }
+
+        impl soroban_rs::VecElement for #name {}
+
+        impl soroban_rs::FromScVal for #name {
+            fn try_from_val(val: &soroban_rs::xdr::ScVal) -> Result<Self, soroban_rs::SorobanHelperError> {
+                #decode
+            }
+        }
+    }
+}
+
+/// Generates a `#[contracttype]` enum with data variants, encoded as a vector
+/// holding the variant name followed by its payload.
+fn union_type(udt: &ScSpecUdtUnionV0, udts: &HashSet<String>) -> TokenStream {
+    let name = ident(&udt.name.to_utf8_string_lossy());
+    let doc = doc_attr(&udt.doc.to_utf8_string_lossy());
+
+    let mut variants = Vec::new();
+    let mut encode_arms = Vec::new();
+    let mut decode_arms = Vec::new();
+    for case in udt.cases.iter() {
+        match case {
+            ScSpecUdtUnionCaseV0::VoidV0(case) => {
+                let case_name = case.name.to_utf8_string_lossy();
+                let variant = ident(&case_name);
+                let doc = doc_attr(&case.doc.to_utf8_string_lossy());
+                variants.push(quote! { #doc #variant });
+                encode_arms.push(quote! {
+                    Self::#variant => soroban_rs::scval::unit_variant(#case_name)
+                });
+                decode_arms.push(quote! { (#case_name, []) => Ok(Self::#variant) });
+            }
+            ScSpecUdtUnionCaseV0::TupleV0(case) => {
+                let case_name = case.name.to_utf8_string_lossy();
+                let variant = ident(&case_name);
+                let doc = doc_attr(&case.doc.to_utf8_string_lossy());
+                let types = case.type_.iter().map(|ty| spec_type(ty, udts));
+                let values: Vec<Ident> = (0..case.type_.len())
+                    .map(|i| format_ident!("v{}", i))
+                    .collect();
+                variants.push(quote! { #doc #variant(#(#types),*) });
+                encode_arms.push(quote! {
+                    Self::#variant(#(#values),*) => soroban_rs::scval::data_variant(
+                        #case_name,
+                        vec![#(soroban_rs::IntoScVal::try_into_val(#values)?),*],
+                    )
+                });
+                decode_arms.push(quote! {
+                    (#case_name, [#(#values),*]) => Ok(Self::#variant(
+                        #(soroban_rs::FromScVal::try_from_val(#values)?),*
+                    ))
+                });
+            }
+        }
+    }
+
+    quote! {
+        #doc
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+        pub enum #name {
+            #(#variants),*
+        }
+
+        impl soroban_rs::IntoScVal for #name {
+            fn try_into_val(&self) -> Result<soroban_rs::xdr::ScVal, soroban_rs::SorobanHelperError> {
+                match self {
+                    #(#encode_arms),*
+                }
+            }
+
+            fn into_val(self) -> soroban_rs::xdr::ScVal {
+                self.try_into_val().expect(concat!("Failed to convert ", stringify!(#name), " to ScVal"))
+            }
+        }
+
+        impl soroban_rs::VecElement for #name {}
+
+        impl soroban_rs::FromScVal for #name {
+            fn try_from_val(val: &soroban_rs::xdr::ScVal) -> Result<Self, soroban_rs::SorobanHelperError> {
+                let (variant, payload) = soroban_rs::scval::decode_variant(val)?;
+                match (variant.as_str(), payload.as_slice()) {
+                    #(#decode_arms,)*
+                    _ => Err(soroban_rs::SorobanHelperError::ConversionError(format!(
+                        "Unknown variant {} of {}",
+                        variant,
+                        stringify!(#name)
+                    ))),
+                }
+            }
+        }
+    }
+}
+
+/// Generates a `#[contracttype]` integer enum, encoded as an `ScVal::U32`.
+fn enum_type(udt: &ScSpecUdtEnumV0) -> TokenStream {
+    let name = ident(&udt.name.to_utf8_string_lossy());
+    let doc = doc_attr(&udt.doc.to_utf8_string_lossy());
+    let variants: Vec<Ident> = udt
+        .cases
+        .iter()
+        .map(|case| ident(&case.name.to_utf8_string_lossy()))
+        .collect();
+    let docs = udt
+        .cases
+        .iter()
+        .map(|case| doc_attr(&case.doc.to_utf8_string_lossy()));
+    let values: Vec<u32> = udt.cases.iter().map(|case| case.value).collect();
+
+    quote! {
+        #doc
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(u32)]
+        pub enum #name {
+            #(#docs #variants = #values),*
+        }
+
+        impl soroban_rs::IntoScVal for #name {
+            fn try_into_val(&self) -> Result<soroban_rs::xdr::ScVal, soroban_rs::SorobanHelperError> {
+                Ok(soroban_rs::xdr::ScVal::U32(*self as u32))
+            }
+
+            fn into_val(self) -> soroban_rs::xdr::ScVal {
+                soroban_rs::xdr::ScVal::U32(self as u32)
+            }
+        }
+
+        impl soroban_rs::VecElement for #name {}
+
+        impl soroban_rs::FromScVal for #name {
+            fn try_from_val(val: &soroban_rs::xdr::ScVal) -> Result<Self, soroban_rs::SorobanHelperError> {
+                match <u32 as soroban_rs::FromScVal>::try_from_val(val)? {
+                    #(#values => Ok(Self::#variants),)*
+                    value => Err(soroban_rs::SorobanHelperError::ConversionError(format!(
+                        "Unknown value {} of {}",
+                        value,
+                        stringify!(#name)
+                    ))),
+                }
+            }
+        }
+    }
+}
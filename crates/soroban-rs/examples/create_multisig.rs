@@ -30,10 +30,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let target_account = Account::single(signer_3.clone());
 
     // Setup provider
-    let env = Env::new(EnvConfigs {
-        rpc_url: "https://soroban-testnet.stellar.org".to_string(),
-        network_passphrase: "Test SDF Network ; September 2015".to_string(),
-    })?;
+    let env = Env::new(EnvConfigs::new(
+        "https://soroban-testnet.stellar.org",
+        "Test SDF Network ; September 2015",
+    ))?;
 
     // Create 1-of-3 multisig configuration
     let config = AccountConfig::new()
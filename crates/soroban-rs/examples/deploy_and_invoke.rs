@@ -1,7 +1,8 @@
 use dotenv::from_path;
 use ed25519_dalek::SigningKey;
 use soroban_rs::{
-    Account, ClientContractConfigs, Contract, Env, EnvConfigs, Guard, IntoScVal, Signer,
+    xdr::ScAddress, Account, ClientContractConfigs, Contract, Env, EnvConfigs, Guard, IntoScVal,
+    Signer,
 };
 use soroban_rs_macros::soroban;
 use std::{env, error::Error, path::Path};
@@ -23,10 +24,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let signing_key = SigningKey::from_bytes(&private_key.0);
 
     // Creates a new environment
-    let configs = EnvConfigs {
-        rpc_url: "https://soroban-testnet.stellar.org".to_string(),
-        network_passphrase: "Test SDF Network ; September 2015".to_string(),
-    };
+    let configs = EnvConfigs::new(
+        "https://soroban-testnet.stellar.org",
+        "Test SDF Network ; September 2015",
+    );
     let env = Env::new(configs)?;
 
     // Initializes a new account
@@ -61,17 +62,17 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let client_configs = ClientContractConfigs {
         contract_id: deployed.contract_id().expect("Contract ID not found"),
         env: env.clone(),
-        source_account: account.clone(),
+        source_account: Some(account.clone()),
     };
     // Instances the client for the deployed contract.
     let mut deployed_contract_client = TokenMockClient::new(&client_configs);
 
     // Calls send function in contract from Alice and Bob
-    let alice = account.account_id().try_into_val()?;
-    let bob = account.account_id().try_into_val()?;
+    let alice = ScAddress::Account(account.account_id());
+    let bob = ScAddress::Account(account.account_id());
 
     let invoke_res = deployed_contract_client.send(alice, bob).await?;
 
-    println!("Result value: {:?}", invoke_res.get_return_value());
+    println!("Result value: {:?}", invoke_res);
     Ok(())
 }
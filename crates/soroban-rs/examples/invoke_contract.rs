@@ -1,8 +1,8 @@
 use dotenv::from_path;
 use ed25519_dalek::SigningKey;
 use soroban_rs::{
-    Account, AuthorizedCallsForContract, ClientContractConfigs, Env, EnvConfigs, Guard, IntoScVal,
-    Signer,
+    xdr::ScAddress, Account, AuthorizedCallsForContract, ClientContractConfigs, Env, EnvConfigs,
+    Guard, Signer,
 };
 use soroban_rs_macros::soroban;
 use std::{env, path::Path};
@@ -24,10 +24,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let signing_key = SigningKey::from_bytes(&private_key.0);
 
     // Creates a new environment
-    let env = Env::new(EnvConfigs {
-        rpc_url: "https://soroban-testnet.stellar.org".to_string(),
-        network_passphrase: "Test SDF Network ; September 2015".to_string(),
-    })?;
+    let env = Env::new(EnvConfigs::new(
+        "https://soroban-testnet.stellar.org",
+        "Test SDF Network ; September 2015",
+    ))?;
 
     // Initializes a new account
     let mut account = Account::single(Signer::new(signing_key));
@@ -47,18 +47,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client_configs = ClientContractConfigs {
         contract_id,
         env: env.clone(),
-        source_account: account.clone(),
+        source_account: Some(account.clone()),
     };
 
     // TokenMockClient is autogenerated using soroban!("fixtures/lib.rs");
     let mut token = TokenMockClient::new(&client_configs);
 
     // Calls send function in contract from Alice and Bob
-    let alice = account.account_id().try_into_val()?;
-    let bob = account.account_id().try_into_val()?;
+    let alice = ScAddress::Account(account.account_id());
+    let bob = ScAddress::Account(account.account_id());
 
     let invoke_res = token.send(alice, bob).await?;
 
-    println!("Result value: {:?}", invoke_res.get_return_value());
+    println!("Result value: {:?}", invoke_res);
     Ok(())
 }
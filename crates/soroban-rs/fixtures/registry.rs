@@ -0,0 +1,85 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, Symbol,
+    Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allowance {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Level {
+    Low = 1,
+    High = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    Balance(Address),
+    Allowance(Address, Address),
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    NotFound = 1,
+}
+
+const KEY: Symbol = symbol_short!("value");
+
+#[contract]
+pub struct Registry;
+
+#[contractimpl]
+impl Registry {
+    pub fn __constructor(env: Env, value: u32) {
+        env.storage().instance().set(&KEY, &value);
+    }
+
+    pub fn approve(env: Env, from: Address, spender: Address, allowance: Allowance) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Allowance(from, spender), &allowance);
+    }
+
+    pub fn allowance(env: Env, from: Address, spender: Address) -> Allowance {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Allowance(from, spender))
+            .unwrap_or(Allowance {
+                amount: 0,
+                expiration_ledger: 0,
+            })
+    }
+
+    pub fn set_level(env: Env, key: DataKey, level: Level) -> Level {
+        env.storage().persistent().set(&key, &level);
+        level
+    }
+
+    pub fn balances(env: Env, ids: Vec<Address>) -> Map<Address, i128> {
+        let mut balances = Map::new(&env);
+        for id in ids.iter() {
+            let balance = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Balance(id.clone()))
+                .unwrap_or(0);
+            balances.set(id, balance);
+        }
+        balances
+    }
+
+    pub fn admin(env: Env) -> Result<Option<Address>, Error> {
+        Ok(env.storage().instance().get(&DataKey::Admin))
+    }
+}
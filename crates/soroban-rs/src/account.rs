@@ -29,12 +29,23 @@
 //! // Single-signature account
 //! let account = Account::single(signer);
 //! ```
-use crate::{error::SorobanHelperError, guard::Guard, Env, Signer, TransactionBuilder};
+use crate::{
+    amount, crypto,
+    error::SorobanHelperError,
+    guard::{Guard, GuardLabel},
+    operation::Operations,
+    signer::{self, TransactionSigner},
+    Env, Signer, SorobanTransactionResponse, TransactionBuilder,
+};
 use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use stellar_strkey::ed25519::PublicKey;
 use stellar_xdr::curr::{
-    AccountEntry, AccountId, DecoratedSignature, Hash, Operation, OperationBody, SetOptionsOp,
-    Signer as XdrSigner, SignerKey, Transaction, TransactionEnvelope, TransactionV1Envelope, VecM,
+    AccountEntry, AccountId, Asset, DecoratedSignature, Hash, Operation, OperationBody,
+    PublicKey as XdrPublicKey, SetOptionsOp, Signer as XdrSigner, SignerKey, Transaction,
+    TransactionEnvelope, TransactionSignaturePayloadTaggedTransaction, TransactionV1Envelope,
+    Uint256, VecM,
 };
 
 /// Represents a transaction sequence number for a Stellar account.
@@ -161,6 +172,60 @@ impl AccountConfig {
         self
     }
 
+    /// Checks that applying this configuration on top of the given account
+    /// state leaves the account able to sign high threshold operations.
+    ///
+    /// Set options operations that change signers or thresholds require the high
+    /// threshold, so a configuration where the total weight of the remaining
+    /// signers falls below it would lock the account out permanently.
+    ///
+    /// # Parameters
+    ///
+    /// * `entry` - The current account entry
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::InvalidArgument` if the configuration would
+    /// lock the account
+    fn check_lockout(&self, entry: &AccountEntry) -> Result<(), SorobanHelperError> {
+        let [master, low, med, high] = entry.thresholds.0;
+        let master_weight = self.master_weight.unwrap_or(master as u32);
+        let thresholds = [
+            self.low_threshold.unwrap_or(low as u32),
+            self.med_threshold.unwrap_or(med as u32),
+            self.high_threshold.unwrap_or(high as u32),
+        ];
+
+        let mut signers: Vec<(SignerKey, u32)> = entry
+            .signers
+            .iter()
+            .map(|signer| (signer.key.clone(), signer.weight))
+            .collect();
+        for (public_key, weight) in &self.signers {
+            let key = SignerKey::Ed25519(public_key.0.into());
+            signers.retain(|(existing, _)| existing != &key);
+            if *weight > 0 {
+                signers.push((key, *weight));
+            }
+        }
+
+        let total_weight = signers
+            .iter()
+            .fold(master_weight as u64, |acc, (_, weight)| {
+                acc + *weight as u64
+            });
+        let max_threshold = thresholds.into_iter().max().unwrap_or_default() as u64;
+
+        if total_weight == 0 || total_weight < max_threshold {
+            return Err(SorobanHelperError::InvalidArgument(format!(
+                "Account configuration would lock the account: total signer weight {} is below threshold {}",
+                total_weight, max_threshold
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Helper function to create a signer operation
     fn create_signer_operation(&self, public_key: &PublicKey, weight: u32) -> Operation {
         let signer_key = SignerKey::Ed25519(public_key.0.into());
@@ -211,15 +276,26 @@ impl AccountConfig {
     }
 }
 
+/// Sequence number tracked locally, shared by the clones of an account.
+#[derive(Clone, Default)]
+struct SequenceCache {
+    /// Whether transactions are built from the cached sequence number
+    enabled: bool,
+    /// Last sequence number used by the account, unknown until fetched or set
+    current: Arc<Mutex<Option<AccountSequence>>>,
+}
+
 /// Represents a single-signature account.
 #[derive(Clone)]
 pub struct SingleAccount {
     /// The account's identifier
     account_id: AccountId,
     /// Signer associated with this account
-    signer: Box<Signer>,
+    signer: Arc<dyn TransactionSigner>,
     /// List of guards associated with this account
     pub guards: Vec<Guard>,
+    /// Locally tracked sequence number
+    sequence_cache: SequenceCache,
 }
 
 impl SingleAccount {
@@ -229,11 +305,12 @@ impl SingleAccount {
     ///
     /// * `signer` - The signer for this account
     /// * `authorized_calls` - The number of calls to authorize
-    pub fn new(signer: Signer) -> Self {
+    pub fn new(signer: impl TransactionSigner + 'static) -> Self {
         Self {
             account_id: signer.account_id(),
-            signer: Box::new(signer),
+            signer: Arc::new(signer),
             guards: Vec::new(),
+            sequence_cache: SequenceCache::default(),
         }
     }
 }
@@ -250,9 +327,11 @@ pub struct MultisigAccount {
     /// The account's identifier
     account_id: AccountId,
     /// Signers associated with this account
-    pub signers: Vec<Signer>,
+    pub signers: Vec<Arc<dyn TransactionSigner>>,
     /// List of guards associated with this account
     pub guards: Vec<Guard>,
+    /// Locally tracked sequence number
+    sequence_cache: SequenceCache,
 }
 
 impl MultisigAccount {
@@ -263,11 +342,15 @@ impl MultisigAccount {
     /// * `account_id` - The identifier for this account
     /// * `signers` - A vector of signers for this account
     /// * `authorized_calls` - The number of calls to authorize
-    pub fn new(account_id: AccountId, signers: Vec<Signer>) -> Self {
+    pub fn new<S: TransactionSigner + 'static>(account_id: AccountId, signers: Vec<S>) -> Self {
         Self {
             account_id,
-            signers,
+            signers: signers
+                .into_iter()
+                .map(|signer| Arc::new(signer) as Arc<dyn TransactionSigner>)
+                .collect(),
             guards: Vec::new(),
+            sequence_cache: SequenceCache::default(),
         }
     }
 }
@@ -336,12 +419,8 @@ impl Account {
     /// # Returns
     ///
     /// A new `Account` instance with the KeyPair variant
-    pub fn single(signer: Signer) -> Self {
-        Self::KeyPair(SingleAccount {
-            signer: Box::new(signer.clone()),
-            account_id: signer.account_id(),
-            guards: Vec::new(),
-        })
+    pub fn single(signer: impl TransactionSigner + 'static) -> Self {
+        Self::KeyPair(SingleAccount::new(signer))
     }
 
     /// Creates a new multisig Account instance with the provided account ID and signers.
@@ -354,12 +433,11 @@ impl Account {
     /// # Returns
     ///
     /// A new `Account` instance with the Multisig variant
-    pub fn multisig(account_id: AccountId, signers: Vec<Signer>) -> Self {
-        Self::Multisig(MultisigAccount {
-            account_id,
-            signers,
-            guards: Vec::new(),
-        })
+    pub fn multisig<S: TransactionSigner + 'static>(
+        account_id: AccountId,
+        signers: Vec<S>,
+    ) -> Self {
+        Self::Multisig(MultisigAccount::new(account_id, signers))
     }
 
     /// Returns the account's identifier.
@@ -371,9 +449,9 @@ impl Account {
     }
 
     /// Returns a reference to the account's signers.
-    pub fn signers(&self) -> &[Signer] {
+    pub fn signers(&self) -> &[Arc<dyn TransactionSigner>] {
         match self {
-            Self::KeyPair(account) => std::slice::from_ref(&*account.signer),
+            Self::KeyPair(account) => std::slice::from_ref(&account.signer),
             Self::Multisig(account) => &account.signers,
         }
     }
@@ -403,6 +481,154 @@ impl Account {
         Ok(AccountSequence::from(entry.seq_num.0))
     }
 
+    /// Enables or disables the local sequence number cache.
+    ///
+    /// When enabled, transactions are built from the cached sequence number
+    /// instead of fetching it from the network every time. The cache is shared
+    /// by the clones of the account and filled from the network on first use,
+    /// unless set with `set_cached_sequence`.
+    ///
+    /// The cached sequence number is only incremented once a transaction is
+    /// successfully submitted through the account, e.g. by `Contract::invoke`,
+    /// so built or simulated transactions that are never submitted don't use
+    /// it up. After submitting a transaction in another way, e.g. with
+    /// `Env::send_transaction`, use `refresh_sequence` to resynchronize it.
+    ///
+    /// # Parameters
+    ///
+    /// * `enabled` - Whether to use the cached sequence number
+    pub fn use_sequence_cache(&mut self, enabled: bool) {
+        self.sequence_cache_mut().enabled = enabled;
+    }
+
+    /// Sets the cached sequence number, the last one used by the account.
+    ///
+    /// # Parameters
+    ///
+    /// * `sequence` - The current sequence number of the account
+    pub fn set_cached_sequence(&mut self, sequence: AccountSequence) {
+        *self.sequence_cache().current.lock().unwrap() = Some(sequence);
+    }
+
+    /// Clears the cached sequence number, so the next transaction built with
+    /// the sequence cache enabled fetches it from the network again.
+    ///
+    /// Submissions rejected with `txBAD_SEQ` invalidate the cache automatically.
+    pub fn invalidate_sequence_cache(&mut self) {
+        *self.sequence_cache().current.lock().unwrap() = None;
+    }
+
+    /// Returns the cached sequence number, if known.
+    pub fn cached_sequence(&self) -> Option<AccountSequence> {
+        *self.sequence_cache().current.lock().unwrap()
+    }
+
+    /// Fetches the current sequence number from the network and caches it.
+    ///
+    /// # Parameters
+    ///
+    /// * `env` - The environment to use for fetching the sequence number
+    ///
+    /// # Returns
+    ///
+    /// The current sequence number
+    pub async fn refresh_sequence(
+        &mut self,
+        env: &Env,
+    ) -> Result<AccountSequence, SorobanHelperError> {
+        let sequence = self.get_sequence(env).await?;
+        self.set_cached_sequence(sequence);
+        Ok(sequence)
+    }
+
+    /// Returns the sequence number of the next transaction of the account.
+    ///
+    /// With the sequence cache enabled, the cached value is used, filled from
+    /// the network if unknown, and left unchanged until the transaction is
+    /// submitted, see `commit_sequence`. Otherwise, it is fetched from the network.
+    pub(crate) async fn peek_sequence(
+        &self,
+        env: &Env,
+    ) -> Result<AccountSequence, SorobanHelperError> {
+        let cache = self.sequence_cache();
+        if !cache.enabled {
+            return self.next_sequence(env).await;
+        }
+
+        let cached = *cache.current.lock().unwrap();
+        let fetched = match cached {
+            Some(sequence) => sequence,
+            None => self.get_sequence(env).await?,
+        };
+
+        // the lock is not held across the fetch, another clone may have filled the cache meanwhile
+        let mut current = cache.current.lock().unwrap();
+        Ok(current.get_or_insert(fetched).next())
+    }
+
+    /// Records the sequence number of a successfully submitted transaction as
+    /// the last one used by the account, if the sequence cache is enabled.
+    pub(crate) fn commit_sequence(&self, sequence: AccountSequence) {
+        let cache = self.sequence_cache();
+        if !cache.enabled {
+            return;
+        }
+
+        let mut current = cache.current.lock().unwrap();
+        if current.is_none_or(|current| current.value() < sequence.value()) {
+            *current = Some(sequence);
+        }
+    }
+
+    /// Simulates, signs and submits the transaction configured by `builder`.
+    ///
+    /// If the network rejects the transaction with `txBAD_SEQ`, the sequence
+    /// cache is invalidated and, unless the builder sets an explicit sequence
+    /// number, the transaction is rebuilt with a fresh one and submitted again once.
+    pub(crate) async fn submit(
+        &mut self,
+        env: &Env,
+        builder: TransactionBuilder,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let explicit_sequence = builder.sequence.is_some();
+        match self.build_and_send(env, builder.clone()).await {
+            Err(e) if is_bad_sequence(&e) => {
+                self.invalidate_sequence_cache();
+                if explicit_sequence {
+                    return Err(e);
+                }
+                self.build_and_send(env, builder).await
+            }
+            result => result,
+        }
+    }
+
+    async fn build_and_send(
+        &mut self,
+        env: &Env,
+        builder: TransactionBuilder,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let tx = builder.simulate_and_build(env, self).await?;
+        let tx_envelope = self.sign_transaction(&tx, &env.network_id())?;
+        let response = env.send_transaction(&tx_envelope).await?;
+        self.commit_sequence(AccountSequence::new(tx.seq_num.0));
+        Ok(response)
+    }
+
+    fn sequence_cache(&self) -> &SequenceCache {
+        match self {
+            Self::KeyPair(account) => &account.sequence_cache,
+            Self::Multisig(account) => &account.sequence_cache,
+        }
+    }
+
+    fn sequence_cache_mut(&mut self) -> &mut SequenceCache {
+        match self {
+            Self::KeyPair(account) => &mut account.sequence_cache,
+            Self::Multisig(account) => &mut account.sequence_cache,
+        }
+    }
+
     /// Retrieves the next available sequence number.
     ///
     /// This is useful when preparing a new transaction.
@@ -418,6 +644,46 @@ impl Account {
         Ok(self.get_sequence(env).await?.next())
     }
 
+    /// Waits until the account's sequence number reaches or passes a target value.
+    ///
+    /// Polls the network for the current sequence number, sleeping `poll` between
+    /// attempts. Useful to coordinate dependent transactions submitted by another process.
+    ///
+    /// # Parameters
+    ///
+    /// * `env` - The environment to use for fetching the sequence number
+    /// * `target` - The sequence number to wait for
+    /// * `poll` - The delay between consecutive attempts
+    /// * `max_attempts` - The maximum number of times the sequence is fetched
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::NetworkRequestFailed` if the target is not reached
+    /// within `max_attempts`, or if fetching the sequence number fails
+    pub async fn wait_for_sequence(
+        &self,
+        env: &Env,
+        target: AccountSequence,
+        poll: Duration,
+        max_attempts: u32,
+    ) -> Result<(), SorobanHelperError> {
+        for attempt in 1..=max_attempts {
+            if self.get_sequence(env).await?.value() >= target.value() {
+                return Ok(());
+            }
+            if attempt < max_attempts {
+                tokio::time::sleep(poll).await;
+            }
+        }
+
+        Err(SorobanHelperError::NetworkRequestFailed(format!(
+            "Timed out waiting for account {} to reach sequence {} after {} attempts",
+            self.account_id().0,
+            target,
+            max_attempts
+        )))
+    }
+
     /// Adds a guard to the account.
     ///
     /// Guards are used to control and limit operations that can be performed with this account.
@@ -462,6 +728,29 @@ impl Account {
         }
     }
 
+    /// Evaluates each guard associated with this account, without updating their state.
+    ///
+    /// Unlike `check_guards`, this reports which guards would block the transaction.
+    /// A guard whose check fails with an error is reported as blocking.
+    ///
+    /// # Parameters
+    ///
+    /// * `tx` - The transaction to evaluate
+    ///
+    /// # Returns
+    ///
+    /// The label of each guard along with whether it passes, in the order the guards were added
+    pub fn evaluate_guards(&self, tx: &Transaction) -> Vec<(GuardLabel, bool)> {
+        let guards = match self {
+            Self::KeyPair(account) => &account.guards,
+            Self::Multisig(account) => &account.guards,
+        };
+        guards
+            .iter()
+            .map(|guard| (guard.label(), guard.check(tx).unwrap_or(false)))
+            .collect()
+    }
+
     /// Updates the state of all guards after an operation has been performed.
     ///
     /// This method should be called after a successful operation to update
@@ -497,18 +786,37 @@ impl Account {
     fn sign_with_tx(
         tx: &Transaction,
         network_id: &Hash,
-        signers: &[Signer],
+        signers: &[Arc<dyn TransactionSigner>],
     ) -> Result<VecM<DecoratedSignature, 20>, SorobanHelperError> {
         let signatures: Vec<DecoratedSignature> = signers
             .iter()
-            .map(|signer| signer.sign_transaction(tx, network_id))
-            .collect::<Result<_, _>>()
-            .map_err(|e| SorobanHelperError::XdrEncodingFailed(e.to_string()))?;
+            .map(|signer| sign_transaction(signer.as_ref(), tx, network_id))
+            .collect::<Result<_, _>>()?;
         signatures.try_into().map_err(|_| {
             SorobanHelperError::XdrEncodingFailed("Failed to convert signatures to XDR".to_string())
         })
     }
 
+    /// Computes the hash of a transaction without signing nor submitting it.
+    ///
+    /// The hash identifies the transaction on the network, so it can be logged
+    /// or used to track the transaction before it is submitted.
+    ///
+    /// # Parameters
+    ///
+    /// * `tx` - The transaction to hash
+    /// * `network_id` - The network ID hash
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::XdrEncodingFailed` if the transaction cannot be encoded
+    pub fn transaction_hash(
+        tx: &Transaction,
+        network_id: &Hash,
+    ) -> Result<[u8; 32], SorobanHelperError> {
+        crypto::transaction_hash(tx, network_id)
+    }
+
     /// Signs a transaction, ensuring the account still has authorized calls.
     ///
     /// Decrements the authorized call counter when successful.
@@ -602,11 +910,21 @@ impl Account {
     /// # Returns
     ///
     /// A signed transaction envelope containing the set options operations
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - The configuration would leave the account unable to meet its thresholds
+    /// - Simulation of the set options transaction fails
+    /// - Transaction building or signing fails
     pub async fn configure(
         mut self,
         env: &Env,
         config: AccountConfig,
     ) -> Result<TransactionEnvelope, SorobanHelperError> {
+        let entry = self.load(env).await?;
+        config.check_lockout(&entry)?;
+
         let mut tx = TransactionBuilder::new(&self, env);
 
         // Add set options operation for each signer configuration
@@ -619,10 +937,79 @@ impl Account {
             tx = tx.add_operation(op);
         }
 
-        let tx = tx.simulate_and_build(env, &self).await?;
+        let tx = tx.simulate_and_build_strict(env, &self).await?;
         self.sign_transaction(&tx, &env.network_id())
     }
 
+    /// Sends a payment from this account and waits for the result.
+    ///
+    /// # Parameters
+    ///
+    /// * `env` - The environment to submit the payment to
+    /// * `to` - The destination account, as a strkey (`G...`)
+    /// * `amount` - The decimal amount to send, e.g. `"12.5"`
+    /// * `asset` - The asset to send
+    ///
+    /// # Returns
+    ///
+    /// The transaction response from the network
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - The destination or the amount are malformed (`SorobanHelperError::InvalidArgument`)
+    /// - The transaction doesn't pass the account guards
+    /// - Transaction building, signing or submission fails
+    pub async fn pay(
+        &mut self,
+        env: &Env,
+        to: &str,
+        amount: &str,
+        asset: Asset,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let destination = PublicKey::from_string(to).map_err(|e| {
+            SorobanHelperError::InvalidArgument(format!("Invalid destination {}: {}", to, e))
+        })?;
+        let destination = AccountId(XdrPublicKey::PublicKeyTypeEd25519(Uint256(destination.0)));
+        let stroops = amount::parse_decimal(amount)?;
+
+        let payment = Operations::send_payment(destination, stroops, asset)?;
+        let builder = TransactionBuilder::new(self, env).add_operation(payment);
+
+        self.submit(env, builder).await
+    }
+
+    /// Creates a new account funded by another one and waits for the result.
+    ///
+    /// # Parameters
+    ///
+    /// * `env` - The environment to submit the transaction to
+    /// * `funder` - The existing account paying the starting balance
+    /// * `destination` - The account to create
+    /// * `starting_balance` - The initial balance of the new account, in stroops
+    ///
+    /// # Returns
+    ///
+    /// The transaction response from the network
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - The starting balance is not positive (`SorobanHelperError::InvalidArgument`)
+    /// - The transaction doesn't pass the funder guards
+    /// - Transaction building, signing or submission fails
+    pub async fn fund_new(
+        env: &Env,
+        funder: &mut Account,
+        destination: AccountId,
+        starting_balance: i64,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let create_account = Operations::create_account(destination, starting_balance)?;
+        let builder = TransactionBuilder::new(funder, env).add_operation(create_account);
+
+        funder.submit(env, builder).await
+    }
+
     /// Signs a transaction without checking or decrementing the authorized_calls counter.
     ///
     /// This method bypasses authorization checks and should be used with caution.
@@ -662,7 +1049,7 @@ impl Account {
     pub fn sign_with_signers(
         tx: &Transaction,
         network_id: &Hash,
-        signers: &[Signer],
+        signers: &[Arc<dyn TransactionSigner>],
     ) -> Result<VecM<DecoratedSignature, 20>, SorobanHelperError> {
         if signers.is_empty() {
             return Err(SorobanHelperError::SigningFailed(
@@ -672,7 +1059,7 @@ impl Account {
 
         let signatures: Vec<DecoratedSignature> = signers
             .iter()
-            .map(|signer| signer.sign_transaction(tx, network_id))
+            .map(|signer| sign_transaction(signer.as_ref(), tx, network_id))
             .collect::<Result<_, _>>()?;
 
         signatures.try_into().map_err(|_| {
@@ -683,16 +1070,110 @@ impl Account {
     }
 }
 
+/// Signs a transaction with a single transaction signer.
+fn sign_transaction(
+    signer: &dyn TransactionSigner,
+    tx: &Transaction,
+    network_id: &Hash,
+) -> Result<DecoratedSignature, SorobanHelperError> {
+    signer::sign_tagged_transaction(
+        signer,
+        TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+        network_id,
+    )
+}
+
+/// Returns whether a submission was rejected because of a bad sequence number (`txBAD_SEQ`).
+fn is_bad_sequence(error: &SorobanHelperError) -> bool {
+    matches!(
+        error,
+        SorobanHelperError::TransactionFailedWithCode { code, .. } if code == "txBadSeq"
+    )
+}
+
+/// Signs a transaction with several independent accounts, e.g. the co-signers of an M-of-N workflow.
+///
+/// The guards of every account are checked before signing, so either all the
+/// accounts sign and their guards are updated, or none of them does.
+///
+/// # Parameters
+///
+/// * `tx` - The transaction to sign
+/// * `network_id` - The network ID hash
+/// * `accounts` - The accounts signing the transaction
+///
+/// # Returns
+///
+/// A transaction envelope holding the signatures of all the accounts
+///
+/// # Errors
+///
+/// Returns:
+/// - `SorobanHelperError::SigningFailed` if no account is provided
+/// - `SorobanHelperError::Unauthorized` if the transaction doesn't pass the guards of an account
+/// - `SorobanHelperError::XdrEncodingFailed` if there are more than 20 signatures
+pub fn sign_with_accounts(
+    tx: &Transaction,
+    network_id: &Hash,
+    accounts: &mut [Account],
+) -> Result<TransactionEnvelope, SorobanHelperError> {
+    if accounts.is_empty() {
+        return Err(SorobanHelperError::SigningFailed(
+            "No accounts provided".to_string(),
+        ));
+    }
+
+    for account in accounts.iter() {
+        if !account.check_guards(tx)? {
+            return Err(SorobanHelperError::Unauthorized(format!(
+                "The transaction didn't pass one or more guards of account {}",
+                account.account_id()
+            )));
+        }
+    }
+
+    let mut all_signatures = Vec::new();
+    for account in accounts.iter() {
+        all_signatures
+            .extend(Account::sign_with_signers(tx, network_id, account.signers())?.to_vec());
+    }
+    let signatures: VecM<DecoratedSignature, 20> = all_signatures.try_into().map_err(|_| {
+        SorobanHelperError::XdrEncodingFailed(
+            "Too many signatures for XDR vector (max 20)".to_string(),
+        )
+    })?;
+
+    for account in accounts.iter_mut() {
+        account.update_guards(tx)?;
+    }
+
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: tx.clone(),
+        signatures,
+    }))
+}
+
 #[cfg(test)]
 mod test {
-    use stellar_xdr::curr::{OperationBody, Signer as XdrSigner, SignerKey, TransactionEnvelope};
+    use stellar_rpc_client::SimulateTransactionResponse;
+    use stellar_xdr::curr::{
+        Asset, OperationBody, Signer as XdrSigner, SignerKey, Thresholds, TransactionEnvelope,
+    };
+
+    use std::sync::Arc;
+    use std::time::Duration;
 
-    use crate::account::AccountSequence;
-    use crate::guard::Guard;
-    use crate::mock::{all_signers, mock_env, mock_signer1, mock_signer3};
+    use crate::account::{is_bad_sequence, sign_with_accounts, AccountSequence};
+    use crate::guard::{Guard, GuardLabel};
+    use crate::mock::{
+        all_signers, mock_account_entry, mock_contract_id, mock_env, mock_env_with_rpc_client,
+        mock_signer1, mock_signer3, mock_simulate_tx_response, mock_transaction_response,
+        MockRpcClient, MockTransactionSigner,
+    };
+    use crate::operation::Operations;
     use crate::{
-        Account, AccountConfig, MultisigAccount, SingleAccount, SorobanHelperError,
-        TransactionBuilder,
+        Account, AccountConfig, AuthorizedCallsForContract, MultisigAccount, SingleAccount,
+        SorobanHelperError, TransactionBuilder, TransactionSigner,
     };
 
     #[tokio::test]
@@ -733,6 +1214,56 @@ mod test {
         assert_eq!(expected_account_id, res_account_id);
     }
 
+    #[tokio::test]
+    async fn test_custom_transaction_signer() {
+        let env = mock_env(None, None, None);
+        let signer = Arc::new(MockTransactionSigner::new(mock_signer1()));
+        let mut account = Account::single(signer.clone());
+        assert_eq!(account.account_id(), mock_signer1().account_id());
+
+        let tx = TransactionBuilder::new(&account, &env)
+            .build()
+            .await
+            .unwrap();
+        let TransactionEnvelope::Tx(envelope) =
+            account.sign_transaction(&tx, &env.network_id()).unwrap()
+        else {
+            panic!("Expected TransactionEnvelope::Tx");
+        };
+
+        // signatures are the same as the in-process signer's
+        assert_eq!(
+            envelope.signatures.to_vec(),
+            vec![mock_signer1()
+                .sign_transaction(&tx, &env.network_id())
+                .unwrap()]
+        );
+        assert_eq!(
+            signer.signed_hashes(),
+            vec![Account::transaction_hash(&tx, &env.network_id()).unwrap()]
+        );
+
+        // custom and in-process signers can be mixed
+        let signers: Vec<Arc<dyn TransactionSigner>> = vec![
+            Arc::new(mock_signer1()),
+            Arc::new(MockTransactionSigner::new(mock_signer3())),
+        ];
+        let mut account = Account::multisig(mock_signer3().account_id(), signers);
+        let TransactionEnvelope::Tx(envelope) =
+            account.sign_transaction(&tx, &env.network_id()).unwrap()
+        else {
+            panic!("Expected TransactionEnvelope::Tx");
+        };
+        assert_eq!(envelope.signatures.len(), 2);
+
+        // signer errors are returned as they are
+        let mut account = Account::single(MockTransactionSigner::failing(mock_signer1()));
+        assert!(matches!(
+            account.sign_transaction(&tx, &env.network_id()),
+            Err(SorobanHelperError::SigningFailed(_))
+        ));
+    }
+
     #[tokio::test]
     async fn sign_transaction() {
         let env = mock_env(None, None, None);
@@ -904,6 +1435,252 @@ mod test {
         assert_eq!(second_next.value(), second_current.value() + 1);
     }
 
+    #[tokio::test]
+    async fn test_sequence_cache() {
+        let rpc_client = Arc::new(MockRpcClient::new(None, None, None));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut account = Account::single(mock_signer1());
+        account.use_sequence_cache(true);
+        assert_eq!(account.cached_sequence(), None);
+
+        // the cache is filled once, and building doesn't use up the sequence number
+        let first = TransactionBuilder::new(&account, &env)
+            .build()
+            .await
+            .unwrap();
+        let second = TransactionBuilder::new(&account, &env)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(first.seq_num.0, 1);
+        assert_eq!(second.seq_num.0, 1);
+        assert_eq!(account.cached_sequence(), Some(AccountSequence::new(0)));
+        assert_eq!(rpc_client.get_account_calls(), 1);
+
+        account.set_cached_sequence(AccountSequence::new(10));
+        let tx = TransactionBuilder::new(&account, &env)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(tx.seq_num.0, 11);
+        assert_eq!(rpc_client.get_account_calls(), 1);
+
+        // the mock account sequence is 0
+        let refreshed = account.refresh_sequence(&env).await.unwrap();
+        assert_eq!(refreshed, AccountSequence::new(0));
+        assert_eq!(account.cached_sequence(), Some(refreshed));
+        assert_eq!(rpc_client.get_account_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sequence_cache_consecutive_submissions() {
+        let rpc_client = Arc::new(MockRpcClient::new(
+            None,
+            Some(Ok(mock_simulate_tx_response(None))),
+            None,
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut account = Account::single(mock_signer1());
+        account.use_sequence_cache(true);
+        let destination = mock_signer3().account_id().to_string();
+
+        // the mock account sequence is 0, fetched once for both transactions
+        for _ in 0..2 {
+            account
+                .pay(&env, &destination, "1", Asset::Native)
+                .await
+                .unwrap();
+        }
+
+        let seq_nums: Vec<i64> = rpc_client
+            .sent_envelopes()
+            .iter()
+            .map(|envelope| match envelope {
+                TransactionEnvelope::Tx(tx_v1) => tx_v1.tx.seq_num.0,
+                _ => panic!("Expected TransactionEnvelope::Tx"),
+            })
+            .collect();
+        assert_eq!(seq_nums, vec![1, 2]);
+        assert_eq!(account.cached_sequence(), Some(AccountSequence::new(2)));
+        assert_eq!(rpc_client.get_account_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sequence_cache_simulate_then_submit() {
+        let rpc_client = Arc::new(
+            MockRpcClient::new(None, Some(Ok(mock_simulate_tx_response(None))), None)
+                .with_send_transaction_polling_queue(vec![
+                    Ok(mock_transaction_response()),
+                    Err(SorobanHelperError::TransactionFailedWithCode {
+                        code: "txInsufficientFee".to_string(),
+                        detail: String::new(),
+                    }),
+                ]),
+        );
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut account = Account::single(mock_signer1());
+        account.use_sequence_cache(true);
+        account.set_cached_sequence(AccountSequence::new(10));
+        let destination = mock_signer3().account_id().to_string();
+
+        // simulations don't use up the sequence number
+        TransactionBuilder::new(&account, &env)
+            .add_operation(Operations::create_account(mock_signer3().account_id(), 100).unwrap())
+            .simulate_and_build(&env, &account)
+            .await
+            .unwrap();
+        assert_eq!(account.cached_sequence(), Some(AccountSequence::new(10)));
+
+        account
+            .pay(&env, &destination, "1", Asset::Native)
+            .await
+            .unwrap();
+        assert_eq!(account.cached_sequence(), Some(AccountSequence::new(11)));
+
+        // nor do failed submissions
+        assert!(account
+            .pay(&env, &destination, "1", Asset::Native)
+            .await
+            .is_err());
+        assert_eq!(account.cached_sequence(), Some(AccountSequence::new(11)));
+
+        let seq_nums: Vec<i64> = rpc_client
+            .sent_envelopes()
+            .iter()
+            .map(|envelope| match envelope {
+                TransactionEnvelope::Tx(tx_v1) => tx_v1.tx.seq_num.0,
+                _ => panic!("Expected TransactionEnvelope::Tx"),
+            })
+            .collect();
+        assert_eq!(seq_nums, vec![11, 12]);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_sequence_cache() {
+        let rpc_client = Arc::new(MockRpcClient::new(None, None, None));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut account = Account::single(mock_signer1());
+        account.use_sequence_cache(true);
+        account.set_cached_sequence(AccountSequence::new(10));
+
+        account.invalidate_sequence_cache();
+        assert_eq!(account.cached_sequence(), None);
+
+        // the mock account sequence is 0
+        let tx = TransactionBuilder::new(&account, &env)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(tx.seq_num.0, 1);
+        assert_eq!(rpc_client.get_account_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bad_sequence_retry() {
+        let rpc_client = Arc::new(
+            MockRpcClient::new(None, Some(Ok(mock_simulate_tx_response(None))), None)
+                .with_send_transaction_polling_queue(vec![Err(
+                    SorobanHelperError::TransactionFailedWithCode {
+                        code: "txBadSeq".to_string(),
+                        detail: String::new(),
+                    },
+                )]),
+        );
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut account = Account::single(mock_signer1());
+        account.use_sequence_cache(true);
+        account.set_cached_sequence(AccountSequence::new(10));
+
+        account
+            .pay(
+                &env,
+                &mock_signer3().account_id().to_string(),
+                "1",
+                Asset::Native,
+            )
+            .await
+            .unwrap();
+
+        // the stale cached sequence is dropped and fetched again for the retry
+        let seq_nums: Vec<i64> = rpc_client
+            .sent_envelopes()
+            .iter()
+            .map(|envelope| match envelope {
+                TransactionEnvelope::Tx(tx_v1) => tx_v1.tx.seq_num.0,
+                _ => panic!("Expected TransactionEnvelope::Tx"),
+            })
+            .collect();
+        assert_eq!(seq_nums, vec![11, 1]);
+        assert_eq!(rpc_client.get_account_calls(), 1);
+        assert_eq!(account.cached_sequence(), Some(AccountSequence::new(1)));
+    }
+
+    #[test]
+    fn test_is_bad_sequence() {
+        let failed_with = |code: &str| SorobanHelperError::TransactionFailedWithCode {
+            code: code.to_string(),
+            detail: String::new(),
+        };
+        assert!(is_bad_sequence(&failed_with("txBadSeq")));
+        assert!(!is_bad_sequence(&failed_with("txFailed")));
+        // only the result code of a rejected transaction is trusted
+        assert!(!is_bad_sequence(&SorobanHelperError::NetworkRequestFailed(
+            "Error: TxBadSeq".to_string()
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_sequence_cache_disabled() {
+        let rpc_client = Arc::new(MockRpcClient::new(None, None, None));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut account = Account::single(mock_signer1());
+        account.set_cached_sequence(AccountSequence::new(10));
+
+        for _ in 0..2 {
+            let tx = TransactionBuilder::new(&account, &env)
+                .build()
+                .await
+                .unwrap();
+            assert_eq!(tx.seq_num.0, 1);
+        }
+        assert_eq!(rpc_client.get_account_calls(), 2);
+        assert_eq!(account.cached_sequence(), Some(AccountSequence::new(10)));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_sequence() {
+        let account = Account::single(mock_signer1());
+        let account_id = account.account_id().0.to_string();
+        let entry_at = |seq: i64| {
+            let mut entry = mock_account_entry(&account_id);
+            entry.seq_num = seq.into();
+            Ok(entry)
+        };
+
+        let rpc_client = MockRpcClient::new(Some(entry_at(3)), None, None)
+            .with_get_account_queue(vec![entry_at(1), entry_at(2)]);
+        let env = mock_env_with_rpc_client(rpc_client);
+
+        let res = account
+            .wait_for_sequence(&env, AccountSequence::new(3), Duration::from_millis(1), 3)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_sequence_timeout() {
+        let env = mock_env(None, None, None);
+        let account = Account::single(mock_signer1());
+
+        let res = account
+            .wait_for_sequence(&env, AccountSequence::new(5), Duration::from_millis(1), 2)
+            .await;
+        assert!(matches!(
+            res,
+            Err(SorobanHelperError::NetworkRequestFailed(_))
+        ));
+    }
+
     #[test]
     fn test_create_thresholds_operation() {
         let empty_config = AccountConfig::new();
@@ -989,6 +1766,69 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_configure_rejects_lockout() {
+        let env = mock_env(None, None, None);
+        let account = Account::single(mock_signer1());
+
+        // master key disabled without any other signer
+        let config = AccountConfig::new()
+            .with_master_weight(0)
+            .with_thresholds(1, 1, 1);
+        let result = account.clone().configure(&env, config).await;
+        assert!(matches!(
+            result,
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+
+        // total weight below the high threshold
+        let config = AccountConfig::new()
+            .with_master_weight(1)
+            .with_thresholds(1, 2, 10)
+            .add_signer(mock_signer3().public_key(), 2);
+        let result = account.clone().configure(&env, config).await;
+        assert!(matches!(
+            result,
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+
+        // removing an existing signer is taken into account
+        let mut entry = mock_account_entry(&mock_signer1().account_id().0.to_string());
+        entry.thresholds = Thresholds([0, 1, 1, 1]);
+        entry.signers = vec![XdrSigner {
+            key: SignerKey::Ed25519(mock_signer3().public_key().0.into()),
+            weight: 1,
+        }]
+        .try_into()
+        .unwrap();
+        let env = mock_env(Some(Ok(entry)), None, None);
+        let config = AccountConfig::new().add_signer(mock_signer3().public_key(), 0);
+        let result = account.configure(&env, config).await;
+        assert!(matches!(
+            result,
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_configure_simulation_error() {
+        let simulation = SimulateTransactionResponse {
+            error: Some("invalid set options".to_string()),
+            ..mock_simulate_tx_response(None)
+        };
+        let env = mock_env(None, Some(Ok(simulation)), None);
+        let account = Account::single(mock_signer1());
+        let config = AccountConfig::new()
+            .with_master_weight(10)
+            .with_thresholds(1, 2, 3);
+
+        let result = account.configure(&env, config).await;
+        assert_eq!(
+            result.unwrap_err(),
+            SorobanHelperError::TransactionSimulationFailed("invalid set options".to_string())
+        );
+    }
+
     #[tokio::test]
     async fn test_sign_transaction_envelope() {
         let env = mock_env(None, None, None);
@@ -1034,4 +1874,176 @@ mod test {
         assert_eq!(final_signatures[0].hint.0, &first_public_key.0[28..32]); // First signature should match first account's public key
         assert_eq!(final_signatures[1].hint.0, &second_public_key.0[28..32]); // Second signature should match second account's public key
     }
+
+    #[tokio::test]
+    async fn test_sign_with_accounts() {
+        let env = mock_env(None, None, None);
+
+        let mut accounts = [
+            Account::single(mock_signer1()),
+            Account::single(mock_signer3()),
+        ];
+        accounts[0].add_guard(Guard::NumberOfAllowedCalls(1));
+        accounts[1].add_guard(Guard::NumberOfAllowedCalls(1));
+
+        let tx = TransactionBuilder::new(&accounts[0], &env)
+            .build()
+            .await
+            .unwrap();
+
+        let envelope = sign_with_accounts(&tx, &env.network_id(), &mut accounts).unwrap();
+        let signatures = match &envelope {
+            TransactionEnvelope::Tx(tx_v1) => tx_v1.signatures.to_vec(),
+            _ => panic!("Expected TransactionEnvelope::Tx"),
+        };
+        assert_eq!(signatures.len(), 2);
+        assert_eq!(signatures[0].hint.0, mock_signer1().public_key().0[28..32]);
+        assert_eq!(signatures[1].hint.0, mock_signer3().public_key().0[28..32]);
+
+        // the guards were consumed, so no account signs again
+        let res = sign_with_accounts(&tx, &env.network_id(), &mut accounts);
+        assert!(matches!(res, Err(SorobanHelperError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_payment_limit_guard() {
+        let env = mock_env(None, None, None);
+        let mut account = Account::single(mock_signer1());
+        account.add_guard(Guard::PaymentLimit {
+            asset: Asset::Native,
+            remaining: 1_000,
+        });
+        let destination = mock_signer3().account_id();
+
+        let payment = |amount: i64| {
+            TransactionBuilder::new(&account, &env)
+                .add_operation(Operations::pay_native(destination.clone(), amount).unwrap())
+        };
+        let within_limit = payment(600).build().await.unwrap();
+        let over_limit = payment(1_001).build().await.unwrap();
+
+        assert!(matches!(
+            account.sign_transaction(&over_limit, &env.network_id()),
+            Err(SorobanHelperError::Unauthorized(_))
+        ));
+        assert!(account
+            .sign_transaction(&within_limit, &env.network_id())
+            .is_ok());
+
+        // only 400 left in the budget
+        assert!(matches!(
+            account.sign_transaction(&within_limit, &env.network_id()),
+            Err(SorobanHelperError::Unauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_guards() {
+        let env = mock_env(None, None, None);
+        let mut account = Account::single(mock_signer1());
+        let contract_id = mock_contract_id(account.clone(), &env);
+        account.add_guard(Guard::NumberOfAllowedCalls(1));
+        account.add_guard(Guard::AuthorizedCallsFor(AuthorizedCallsForContract {
+            contract_id,
+            remaining: 1,
+        }));
+
+        // the transaction holds no authorized call for the contract
+        let tx = TransactionBuilder::new(&account, &env)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            account.evaluate_guards(&tx),
+            vec![
+                (GuardLabel::NumberOfAllowedCalls, true),
+                (GuardLabel::AuthorizedCallsFor(contract_id), false),
+            ]
+        );
+        assert!(!account.check_guards(&tx).unwrap());
+        // evaluating doesn't consume the allowed calls
+        assert_eq!(
+            account.evaluate_guards(&tx)[0],
+            (GuardLabel::NumberOfAllowedCalls, true)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fund_new() {
+        let rpc_client = Arc::new(MockRpcClient::new(
+            None,
+            Some(Ok(mock_simulate_tx_response(None))),
+            None,
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut funder = Account::single(mock_signer1());
+        let destination = mock_signer3().account_id();
+
+        Account::fund_new(&env, &mut funder, destination.clone(), 10_000_000)
+            .await
+            .unwrap();
+
+        let envelopes = rpc_client.sent_envelopes();
+        assert_eq!(envelopes.len(), 1);
+        let TransactionEnvelope::Tx(tx_v1) = &envelopes[0] else {
+            panic!("Expected TransactionEnvelope::Tx");
+        };
+        assert_eq!(tx_v1.tx.source_account, mock_signer1().account_id().into());
+        match &tx_v1.tx.operations[0].body {
+            OperationBody::CreateAccount(op) => {
+                assert_eq!(op.destination, destination);
+                assert_eq!(op.starting_balance, 10_000_000);
+            }
+            other => panic!("Expected OperationBody::CreateAccount, got {:?}", other),
+        }
+
+        assert!(matches!(
+            Account::fund_new(&env, &mut funder, destination, 0).await,
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pay() {
+        let rpc_client = Arc::new(MockRpcClient::new(
+            None,
+            Some(Ok(mock_simulate_tx_response(None))),
+            None,
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut account = Account::single(mock_signer1());
+        let destination = mock_signer3().account_id();
+
+        account
+            .pay(&env, &destination.to_string(), "12.5", Asset::Native)
+            .await
+            .unwrap();
+
+        let envelopes = rpc_client.sent_envelopes();
+        assert_eq!(envelopes.len(), 1);
+        let TransactionEnvelope::Tx(tx_v1) = &envelopes[0] else {
+            panic!("Expected TransactionEnvelope::Tx");
+        };
+        assert_eq!(tx_v1.tx.operations.len(), 1);
+        match &tx_v1.tx.operations[0].body {
+            OperationBody::Payment(payment) => {
+                assert_eq!(payment.destination, destination.clone().into());
+                assert_eq!(payment.amount, 125_000_000);
+                assert_eq!(payment.asset, Asset::Native);
+            }
+            other => panic!("Expected OperationBody::Payment, got {:?}", other),
+        }
+
+        assert!(matches!(
+            account.pay(&env, "not a key", "1", Asset::Native).await,
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            account
+                .pay(&env, &destination.to_string(), "-1", Asset::Native)
+                .await,
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+    }
 }
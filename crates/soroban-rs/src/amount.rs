@@ -0,0 +1,123 @@
+//! # Amount Conversions
+//!
+//! Classic Stellar operations (e.g. payments) express amounts as `i64` stroops,
+//! while Soroban token contracts use `i128`. This module provides the conversions
+//! needed when moving funds between a classic balance and a token contract.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use soroban_rs::amount;
+//!
+//! let token_amount = amount::i64_to_i128(10_000_000);
+//! let stroops = amount::i128_to_i64(token_amount).unwrap();
+//! assert_eq!(stroops, 10_000_000);
+//!
+//! assert_eq!(amount::parse_decimal("1.5").unwrap(), 15_000_000);
+//! ```
+use crate::SorobanHelperError;
+
+/// Number of decimal places of classic amounts.
+pub const DECIMALS: u32 = 7;
+
+/// Parses a decimal amount, e.g. `"12.5"`, into stroops.
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::InvalidArgument` if the amount is not a
+/// non-negative decimal number with at most 7 decimal places, or overflows `i64`
+pub fn parse_decimal(amount: &str) -> Result<i64, SorobanHelperError> {
+    let invalid = || SorobanHelperError::InvalidArgument(format!("Invalid amount: {}", amount));
+
+    let (units, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if units.is_empty() || !is_digits(units) || !is_digits(fraction) {
+        return Err(invalid());
+    }
+    if fraction.len() > DECIMALS as usize {
+        return Err(invalid());
+    }
+
+    let units: i64 = units.parse().map_err(|_| invalid())?;
+    let fraction: i64 = format!("{:0<width$}", fraction, width = DECIMALS as usize)
+        .parse()
+        .map_err(|_| invalid())?;
+    units
+        .checked_mul(10_i64.pow(DECIMALS))
+        .and_then(|stroops| stroops.checked_add(fraction))
+        .ok_or_else(invalid)
+}
+
+/// Converts a classic amount in stroops into a token contract amount.
+pub fn i64_to_i128(value: i64) -> i128 {
+    value as i128
+}
+
+/// Converts a token contract amount into a classic amount in stroops.
+///
+/// Classic amounts can't be negative, so negative values are rejected along
+/// with values that don't fit in an `i64`.
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::ConversionError` if the value is negative or
+/// greater than `i64::MAX`
+pub fn i128_to_i64(value: i128) -> Result<i64, SorobanHelperError> {
+    if value < 0 {
+        return Err(SorobanHelperError::ConversionError(format!(
+            "Negative amount {} can't be used as a classic amount",
+            value
+        )));
+    }
+    i64::try_from(value)
+        .map_err(|_| SorobanHelperError::ConversionError(format!("Amount {} overflows i64", value)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_i64_to_i128() {
+        assert_eq!(i64_to_i128(0), 0);
+        assert_eq!(i64_to_i128(i64::MAX), i64::MAX as i128);
+        assert_eq!(i64_to_i128(i64::MIN), i64::MIN as i128);
+    }
+
+    #[test]
+    fn test_i128_to_i64() {
+        assert_eq!(i128_to_i64(0).unwrap(), 0);
+        assert_eq!(i128_to_i64(i64::MAX as i128).unwrap(), i64::MAX);
+        assert!(matches!(
+            i128_to_i64(i64::MAX as i128 + 1),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+        assert!(matches!(
+            i128_to_i64(-1),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+        assert!(matches!(
+            i128_to_i64(i64::MIN as i128),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal("0").unwrap(), 0);
+        assert_eq!(parse_decimal("1").unwrap(), 10_000_000);
+        assert_eq!(parse_decimal("12.5").unwrap(), 125_000_000);
+        assert_eq!(parse_decimal("0.0000001").unwrap(), 1);
+
+        for invalid in ["", ".5", "-1", "1.00000001", "1,5", "abc", "922337203686"] {
+            assert!(
+                matches!(
+                    parse_decimal(invalid),
+                    Err(SorobanHelperError::InvalidArgument(_))
+                ),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+}
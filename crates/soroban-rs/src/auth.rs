@@ -0,0 +1,475 @@
+//! # Soroban Authorization
+//!
+//! This module provides helpers to build and sign Soroban authorization entries
+//! on behalf of an address other than the transaction source account.
+//!
+//! This is the building block for relayer flows, where a user signs the
+//! authorization entries of an invocation while a relayer builds, signs and
+//! pays for the transaction itself.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use soroban_rs::{AuthorizationBuilder, Contract, Env, Signer};
+//! use stellar_xdr::curr::ScVal;
+//!
+//! async fn example(env: &Env, user: &Signer, contract: &mut Contract) {
+//!     let contract_id = contract.contract_id().unwrap();
+//!     let args = vec![ScVal::U32(1)];
+//!
+//!     // The user signs the authorization entry
+//!     let auth_entries = AuthorizationBuilder::for_contract_call(&contract_id, "increment", args.clone())
+//!         .unwrap()
+//!         .nonce(1)
+//!         .valid_until_ledger(1_000_000)
+//!         .build(user, &env.network_id())
+//!         .unwrap();
+//!
+//!     // The relayer attaches them to its own transaction
+//!     contract
+//!         .invoke_with_auth_entries("increment", args, auth_entries)
+//!         .await
+//!         .unwrap();
+//! }
+//! ```
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicI64, Ordering};
+use stellar_strkey::Contract as ContractId;
+use stellar_xdr::curr::{
+    AccountId, Hash, HashIdPreimage, HashIdPreimageSorobanAuthorization, InvokeContractArgs,
+    Limits, PublicKey, ScAddress, ScMap, ScMapEntry, ScSymbol, ScVal, SorobanAddressCredentials,
+    SorobanAuthorizationEntry, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+    SorobanCredentials, Uint256, VecM, WriteXdr,
+};
+
+use crate::{SorobanHelperError, TransactionSigner};
+
+/// Source of the nonces used by address-credentialed authorization entries.
+///
+/// An address can't use the same nonce twice while a previous entry is still
+/// valid, otherwise the entry is rejected as a replay.
+pub trait NonceProvider: Send + Sync {
+    /// Returns the nonce for the next authorization entry.
+    fn next_nonce(&self) -> i64;
+}
+
+/// Provides cryptographically random nonces.
+///
+/// This is the default strategy of `AuthorizationBuilder`, as it doesn't require
+/// keeping track of the nonces already used.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomNonceProvider;
+
+impl NonceProvider for RandomNonceProvider {
+    fn next_nonce(&self) -> i64 {
+        rand::random()
+    }
+}
+
+/// Provides monotonically increasing nonces, starting from a given value.
+#[derive(Debug, Default)]
+pub struct SequentialNonceProvider {
+    next: AtomicI64,
+}
+
+impl SequentialNonceProvider {
+    /// Creates a provider whose first nonce is `start`.
+    pub fn new(start: i64) -> Self {
+        Self {
+            next: AtomicI64::new(start),
+        }
+    }
+}
+
+impl NonceProvider for SequentialNonceProvider {
+    fn next_nonce(&self) -> i64 {
+        self.next.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+/// Builds signed authorization entries for an invocation tree.
+///
+/// The produced entries use address credentials for the signer's account and
+/// can be attached to a contract invocation built by another account.
+#[derive(Debug, Clone)]
+pub struct AuthorizationBuilder {
+    /// The invocation tree being authorized
+    invocation: SorobanAuthorizedInvocation,
+    /// Nonce preventing the entry from being replayed
+    nonce: i64,
+    /// Last ledger (inclusive) the signature is valid for
+    signature_expiration_ledger: u32,
+}
+
+impl AuthorizationBuilder {
+    /// Creates a builder authorizing the given invocation tree.
+    ///
+    /// The nonce defaults to a random value, see `RandomNonceProvider`.
+    ///
+    /// # Parameters
+    ///
+    /// * `invocation` - The root invocation, with its sub-invocations, to authorize
+    pub fn new(invocation: SorobanAuthorizedInvocation) -> Self {
+        Self {
+            invocation,
+            nonce: RandomNonceProvider.next_nonce(),
+            signature_expiration_ledger: 0,
+        }
+    }
+
+    /// Creates a builder authorizing a single contract call without sub-invocations.
+    ///
+    /// # Parameters
+    ///
+    /// * `contract_id` - The contract being invoked
+    /// * `function_name` - The name of the function being invoked
+    /// * `args` - The arguments passed to the function
+    ///
+    /// # Errors
+    ///
+    /// Returns:
+    /// - `SorobanHelperError::InvalidArgument` if the function name is invalid
+    /// - `SorobanHelperError::XdrEncodingFailed` if the arguments cannot be encoded
+    pub fn for_contract_call(
+        contract_id: &ContractId,
+        function_name: &str,
+        args: Vec<ScVal>,
+    ) -> Result<Self, SorobanHelperError> {
+        let invoke_contract_args = InvokeContractArgs {
+            contract_address: ScAddress::Contract(stellar_xdr::curr::ContractId(Hash(
+                contract_id.0,
+            ))),
+            function_name: ScSymbol(function_name.try_into().map_err(|e| {
+                SorobanHelperError::InvalidArgument(format!("Invalid function name: {}", e))
+            })?),
+            args: args.try_into().map_err(|e| {
+                SorobanHelperError::XdrEncodingFailed(format!("Failed to encode arguments: {}", e))
+            })?,
+        };
+
+        Ok(Self::new(SorobanAuthorizedInvocation {
+            function: SorobanAuthorizedFunction::ContractFn(invoke_contract_args),
+            sub_invocations: VecM::default(),
+        }))
+    }
+
+    /// Sets the nonce of the authorization entry.
+    ///
+    /// The nonce must not have been used before by the same address.
+    pub fn nonce(mut self, nonce: i64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Sets the nonce of the authorization entry to the next one of `provider`.
+    pub fn nonce_from(mut self, provider: &dyn NonceProvider) -> Self {
+        self.nonce = provider.next_nonce();
+        self
+    }
+
+    /// Sets the last ledger (inclusive) the signature is valid for.
+    pub fn valid_until_ledger(mut self, ledger: u32) -> Self {
+        self.signature_expiration_ledger = ledger;
+        self
+    }
+
+    /// Signs the invocation tree with the given signer.
+    ///
+    /// # Parameters
+    ///
+    /// * `signer` - The signer authorizing the invocation
+    /// * `network_id` - The network ID hash
+    ///
+    /// # Returns
+    ///
+    /// The signed authorization entries, ready to be attached to an invocation
+    ///
+    /// # Errors
+    ///
+    /// Returns:
+    /// - `SorobanHelperError::InvalidArgument` if no expiration ledger was set
+    /// - `SorobanHelperError::XdrEncodingFailed` if the signature payload cannot be encoded
+    pub fn build(
+        &self,
+        signer: &dyn TransactionSigner,
+        network_id: &Hash,
+    ) -> Result<Vec<SorobanAuthorizationEntry>, SorobanHelperError> {
+        if self.signature_expiration_ledger == 0 {
+            return Err(SorobanHelperError::InvalidArgument(
+                "Authorization entries require a valid until ledger".to_string(),
+            ));
+        }
+
+        let payload = authorization_payload(
+            network_id,
+            self.nonce,
+            self.signature_expiration_ledger,
+            &self.invocation,
+        )?;
+        let account_id = signer.account_id();
+        let AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(public_key))) = &account_id;
+        let signature: [u8; 64] = signer
+            .sign_hash(&payload)?
+            .signature
+            .0
+            .as_slice()
+            .try_into()
+            .map_err(|_| {
+                SorobanHelperError::SigningFailed("Invalid ed25519 signature length".to_string())
+            })?;
+
+        Ok(vec![SorobanAuthorizationEntry {
+            credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+                address: ScAddress::Account(account_id.clone()),
+                nonce: self.nonce,
+                signature_expiration_ledger: self.signature_expiration_ledger,
+                signature: signature_val(public_key, &signature)?,
+            }),
+            root_invocation: self.invocation.clone(),
+        }])
+    }
+}
+
+/// Verifies the signature of an authorization entry signed by an account.
+///
+/// # Parameters
+///
+/// * `entry` - The authorization entry to verify
+/// * `network_id` - The network ID hash the entry was signed for
+///
+/// # Errors
+///
+/// Returns:
+/// - `SorobanHelperError::InvalidArgument` if the entry doesn't use account address credentials
+///   or its signature is malformed
+/// - `SorobanHelperError::Unauthorized` if the signature is invalid or wasn't made by the account
+pub fn verify_authorization_entry(
+    entry: &SorobanAuthorizationEntry,
+    network_id: &Hash,
+) -> Result<(), SorobanHelperError> {
+    let credentials = match &entry.credentials {
+        SorobanCredentials::Address(credentials) => credentials,
+        SorobanCredentials::SourceAccount => {
+            return Err(SorobanHelperError::InvalidArgument(
+                "Source account credentials carry no signature".to_string(),
+            ))
+        }
+    };
+    let account_key = match &credentials.address {
+        ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(key))) => key.0,
+        _ => {
+            return Err(SorobanHelperError::InvalidArgument(
+                "Only account addresses can be verified".to_string(),
+            ))
+        }
+    };
+
+    let (public_key, signature) = parse_signature_val(&credentials.signature)?;
+    if public_key != account_key {
+        return Err(SorobanHelperError::Unauthorized(
+            "Authorization entry is not signed by its address".to_string(),
+        ));
+    }
+
+    let payload = authorization_payload(
+        network_id,
+        credentials.nonce,
+        credentials.signature_expiration_ledger,
+        &entry.root_invocation,
+    )?;
+    let verifying_key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|e| SorobanHelperError::InvalidArgument(format!("Invalid public key: {}", e)))?;
+    verifying_key
+        .verify_strict(&payload, &Signature::from_bytes(&signature))
+        .map_err(|_| {
+            SorobanHelperError::Unauthorized("Invalid authorization entry signature".to_string())
+        })
+}
+
+/// Computes the hash signed by an address authorizing an invocation.
+fn authorization_payload(
+    network_id: &Hash,
+    nonce: i64,
+    signature_expiration_ledger: u32,
+    invocation: &SorobanAuthorizedInvocation,
+) -> Result<[u8; 32], SorobanHelperError> {
+    let preimage = HashIdPreimage::SorobanAuthorization(HashIdPreimageSorobanAuthorization {
+        network_id: network_id.clone(),
+        nonce,
+        signature_expiration_ledger,
+        invocation: invocation.clone(),
+    });
+    let preimage_xdr = preimage
+        .to_xdr(Limits::none())
+        .map_err(|e| SorobanHelperError::XdrEncodingFailed(e.to_string()))?;
+    Ok(Sha256::digest(preimage_xdr).into())
+}
+
+/// Encodes an account signature as expected by the Stellar account contract:
+/// a vector of `{ public_key, signature }` maps.
+fn signature_val(public_key: &[u8; 32], signature: &[u8; 64]) -> Result<ScVal, SorobanHelperError> {
+    let bytes = |b: &[u8]| -> Result<ScVal, SorobanHelperError> {
+        Ok(ScVal::Bytes(b.to_vec().try_into().map_err(|_| {
+            SorobanHelperError::XdrEncodingFailed("Failed to encode signature bytes".to_string())
+        })?))
+    };
+    let symbol = |s: &str| -> Result<ScVal, SorobanHelperError> {
+        Ok(ScVal::Symbol(crate::scval::symbol(s)?))
+    };
+
+    let map = ScMap::sorted_from(vec![
+        ScMapEntry {
+            key: symbol("public_key")?,
+            val: bytes(public_key)?,
+        },
+        ScMapEntry {
+            key: symbol("signature")?,
+            val: bytes(signature)?,
+        },
+    ])?;
+    let vec_m: VecM<ScVal> = vec![ScVal::Map(Some(map))].try_into().map_err(|_| {
+        SorobanHelperError::XdrEncodingFailed("Failed to convert Vec to VecM".to_string())
+    })?;
+    Ok(ScVal::Vec(Some(vec_m.into())))
+}
+
+/// Extracts the public key and signature from an account signature value.
+fn parse_signature_val(val: &ScVal) -> Result<([u8; 32], [u8; 64]), SorobanHelperError> {
+    let malformed =
+        || SorobanHelperError::InvalidArgument("Malformed authorization signature".to_string());
+
+    let map = match val {
+        ScVal::Vec(Some(items)) if items.len() == 1 => match &items[0] {
+            ScVal::Map(Some(map)) => map,
+            _ => return Err(malformed()),
+        },
+        _ => return Err(malformed()),
+    };
+
+    let field = |name: &str| {
+        map.iter().find_map(|entry| match (&entry.key, &entry.val) {
+            (ScVal::Symbol(key), ScVal::Bytes(bytes)) if key.0.as_slice() == name.as_bytes() => {
+                Some(bytes.as_slice().to_vec())
+            }
+            _ => None,
+        })
+    };
+
+    let public_key = field("public_key")
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(malformed)?;
+    let signature = field("signature")
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(malformed)?;
+    Ok((public_key, signature))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{mock_signer1, mock_signer2};
+
+    fn builder() -> AuthorizationBuilder {
+        AuthorizationBuilder::for_contract_call(&ContractId([1; 32]), "increment", vec![])
+            .unwrap()
+            .nonce(42)
+            .valid_until_ledger(1000)
+    }
+
+    #[test]
+    fn test_build_and_verify() {
+        let network_id = Hash([9; 32]);
+        let signer = mock_signer1();
+
+        let entries = builder().build(&signer, &network_id).unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let entry = &entries[0];
+        match &entry.credentials {
+            SorobanCredentials::Address(credentials) => {
+                assert_eq!(credentials.address, ScAddress::Account(signer.account_id()));
+                assert_eq!(credentials.nonce, 42);
+                assert_eq!(credentials.signature_expiration_ledger, 1000);
+            }
+            _ => panic!("Expected address credentials"),
+        }
+
+        assert!(verify_authorization_entry(entry, &network_id).is_ok());
+
+        // signed for another network
+        assert!(matches!(
+            verify_authorization_entry(entry, &Hash([8; 32])),
+            Err(SorobanHelperError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_tampered_entry() {
+        let network_id = Hash([9; 32]);
+        let mut entry = builder()
+            .build(&mock_signer1(), &network_id)
+            .unwrap()
+            .remove(0);
+
+        // claim the entry was signed by another account
+        if let SorobanCredentials::Address(credentials) = &mut entry.credentials {
+            credentials.address = ScAddress::Account(mock_signer2().account_id());
+        }
+        assert!(matches!(
+            verify_authorization_entry(&entry, &network_id),
+            Err(SorobanHelperError::Unauthorized(_))
+        ));
+    }
+
+    fn entry_nonce(entry: &SorobanAuthorizationEntry) -> i64 {
+        match &entry.credentials {
+            SorobanCredentials::Address(credentials) => credentials.nonce,
+            _ => panic!("Expected address credentials"),
+        }
+    }
+
+    #[test]
+    fn test_default_nonces_are_distinct() {
+        let network_id = Hash([9; 32]);
+        let build = || {
+            AuthorizationBuilder::for_contract_call(&ContractId([1; 32]), "increment", vec![])
+                .unwrap()
+                .valid_until_ledger(1000)
+                .build(&mock_signer1(), &network_id)
+                .unwrap()
+                .remove(0)
+        };
+
+        assert_ne!(entry_nonce(&build()), entry_nonce(&build()));
+    }
+
+    #[test]
+    fn test_sequential_nonces() {
+        let provider = SequentialNonceProvider::new(7);
+        let network_id = Hash([9; 32]);
+
+        let first = builder()
+            .nonce_from(&provider)
+            .build(&mock_signer1(), &network_id)
+            .unwrap()
+            .remove(0);
+        let second = builder()
+            .nonce_from(&provider)
+            .build(&mock_signer1(), &network_id)
+            .unwrap()
+            .remove(0);
+
+        assert_eq!(entry_nonce(&first), 7);
+        assert_eq!(entry_nonce(&second), 8);
+    }
+
+    #[test]
+    fn test_build_requires_expiration() {
+        let res =
+            AuthorizationBuilder::for_contract_call(&ContractId([1; 32]), "increment", vec![])
+                .unwrap()
+                .build(&mock_signer1(), &Hash([9; 32]));
+        assert!(matches!(res, Err(SorobanHelperError::InvalidArgument(_))));
+    }
+}
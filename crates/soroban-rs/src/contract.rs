@@ -7,6 +7,7 @@
 //!
 //! - Loading contract WASM bytecode from file
 //! - Deploying contracts to the Soroban network
+//! - Upgrading the code of deployed contracts
 //! - Invoking contract functions with arguments
 //! - Managing contract identifiers
 //!
@@ -19,10 +20,10 @@
 //!
 //! async fn deploy_and_invoke() {
 //!     // Setup environment and account
-//!     let env = Env::new(EnvConfigs {
-//!         rpc_url: "https://soroban-testnet.stellar.org".to_string(),
-//!         network_passphrase: "Test SDF Network ; September 2015".to_string(),
-//!     }).unwrap();
+//!     let env = Env::new(EnvConfigs::new(
+//!         "https://soroban-testnet.stellar.org",
+//!         "Test SDF Network ; September 2015",
+//!     )).unwrap();
 //!
 //!     let private_key_bytes: [u8; 32] = [
 //!         1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
@@ -42,20 +43,30 @@
 //! ```
 use crate::{
     crypto,
-    error::SorobanHelperError,
+    error::{InvokeSequenceError, SorobanHelperError},
     fs::{DefaultFileReader, FileReader},
     operation::Operations,
+    storage::{ContractDataKey, ContractDataResult},
     transaction::TransactionBuilder,
-    Account, Env, ParseResult, Parser, ParserType, SorobanTransactionResponse,
+    Account, Env, IntoScVal, ParseResult, Parser, ParserType, SorobanTransactionResponse,
 };
 use stellar_strkey::Contract as ContractId;
 use stellar_xdr::curr::{
-    ContractIdPreimage, ContractIdPreimageFromAddress, Hash, ScAddress, ScVal,
+    ContractDataDurability, ContractExecutable, ContractIdPreimage, ContractIdPreimageFromAddress,
+    Hash, LedgerEntryData, LedgerFootprint, LedgerKey, LedgerKeyContractCode, Limits, Operation,
+    OperationBody, ReadXdr, ScAddress, ScVal, SorobanAuthorizationEntry, SorobanResources,
+    SorobanTransactionData, SorobanTransactionDataExt, Uint256, VecM,
 };
 
 /// Name of the constructor function
 const CONSTRUCTOR_FUNCTION_NAME: &str = "__constructor";
 
+/// Name of the function used by `Contract::upgrade` to update the contract code
+pub const UPGRADE_FUNCTION_NAME: &str = "upgrade";
+
+/// Maximum size of contract WASM code accepted by the network (128 KiB)
+pub const MAX_WASM_SIZE: usize = 131_072;
+
 /// Configuration for client interaction with a deployed contract
 ///
 /// Contains all necessary information to interact with a deployed contract,
@@ -66,8 +77,43 @@ pub struct ClientContractConfigs {
     pub contract_id: ContractId,
     /// The environment for interacting with the network
     pub env: Env,
-    /// The account used for signing transactions
-    pub source_account: Account,
+    /// The account used for signing transactions, `None` for a read-only client
+    pub source_account: Option<Account>,
+}
+
+impl ClientContractConfigs {
+    /// Creates the configuration of a client submitting transactions from `source_account`
+    ///
+    /// # Parameters
+    ///
+    /// * `contract_id` - The deployed contract's identifier
+    /// * `env` - The environment for interacting with the network
+    /// * `source_account` - The account used for signing transactions
+    pub fn new(contract_id: ContractId, env: Env, source_account: Account) -> Self {
+        Self {
+            contract_id,
+            env,
+            source_account: Some(source_account),
+        }
+    }
+
+    /// Creates the configuration of a read-only client
+    ///
+    /// A read-only client can only simulate invocations, e.g. to call view
+    /// functions. Submitting a transaction fails with
+    /// `SorobanHelperError::SourceAccountNotSet`.
+    ///
+    /// # Parameters
+    ///
+    /// * `contract_id` - The deployed contract's identifier
+    /// * `env` - The environment for interacting with the network
+    pub fn read_only(contract_id: ContractId, env: Env) -> Self {
+        Self {
+            contract_id,
+            env,
+            source_account: None,
+        }
+    }
 }
 
 /// Represents a Soroban smart contract
@@ -155,6 +201,20 @@ impl Contract {
         })
     }
 
+    /// Computes the hash of a WASM file, as used to identify uploaded contract code
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path to the contract's WASM file
+    ///
+    /// # Returns
+    ///
+    /// The SHA-256 hash of the file or an error if the file couldn't be read
+    pub fn wasm_hash_of_file(path: &str) -> Result<Hash, SorobanHelperError> {
+        let wasm_bytes = DefaultFileReader.read(path)?;
+        Ok(crypto::sha256_hash(&wasm_bytes))
+    }
+
     /// Deploys the contract to the Soroban network
     ///
     /// This method performs two operations:
@@ -174,39 +234,132 @@ impl Contract {
     ///
     /// The Contract instance updated with client configuration for the deployed contract
     pub async fn deploy(
-        mut self,
+        self,
         env: &Env,
         account: &mut Account,
         constructor_args: Option<Vec<ScVal>>,
     ) -> Result<Self, SorobanHelperError> {
-        self.upload_wasm(account, env).await?;
+        let deployer = ScAddress::Account(account.account_id());
+        self.deploy_from_address(
+            env,
+            account,
+            deployer,
+            crypto::generate_salt(),
+            constructor_args,
+        )
+        .await
+    }
+
+    /// Deploys the contract to the Soroban network with the given salt
+    ///
+    /// The contract ID is derived from the address of `account` and the salt,
+    /// so it can be computed before the deployment. To deploy on behalf of
+    /// another address, e.g. a factory contract, use
+    /// `deploy_from_address_with_auth_entries` with the deployer's authorization.
+    ///
+    /// # Parameters
+    ///
+    /// * `env` - The environment to use for deployment
+    /// * `account` - The account deploying the contract and paying for the transactions
+    /// * `deployer` - The deployer address, which must be the address of `account`
+    /// * `salt` - The salt used to derive the contract ID
+    /// * `constructor_args` - Optional arguments to pass to the contract's constructor
+    ///
+    /// # Returns
+    ///
+    /// The Contract instance updated with client configuration for the deployed contract
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::Unauthorized` if the deployer is not the
+    /// address of `account`, or any error from the upload or deployment
+    pub async fn deploy_from_address(
+        self,
+        env: &Env,
+        account: &mut Account,
+        deployer: ScAddress,
+        salt: Uint256,
+        constructor_args: Option<Vec<ScVal>>,
+    ) -> Result<Self, SorobanHelperError> {
+        if deployer != ScAddress::Account(account.account_id()) {
+            return Err(SorobanHelperError::Unauthorized(format!(
+                "Deployer {:?} is not the source account: use \
+                 `deploy_from_address_with_auth_entries` with its authorization",
+                deployer
+            )));
+        }
 
-        let salt = crypto::generate_salt();
+        let create_operation = self.create_operation(deployer, salt, constructor_args)?;
+        self.deploy_with_operation(env, account, create_operation)
+            .await
+    }
 
-        let contract_id_preimage = ContractIdPreimage::Address(ContractIdPreimageFromAddress {
-            address: ScAddress::Account(account.account_id()),
-            salt,
-        });
+    /// Deploys the contract to the Soroban network on behalf of the given deployer,
+    /// attaching the authorization entries signed by the deployer
+    ///
+    /// The contract ID is derived from the deployer address and the salt, so the
+    /// deployer can be a contract, e.g. to model a factory contract deployment.
+    /// The authorization of a contract deployer is usually signed by a custom
+    /// account contract, see `AuthorizationBuilder`.
+    ///
+    /// # Parameters
+    ///
+    /// * `env` - The environment to use for deployment
+    /// * `account` - The account submitting and paying for the transactions
+    /// * `deployer` - The account or contract address the contract is deployed from
+    /// * `salt` - The salt used to derive the contract ID
+    /// * `constructor_args` - Optional arguments to pass to the contract's constructor
+    /// * `auth_entries` - The signed authorization entries of the deployment
+    ///
+    /// # Returns
+    ///
+    /// The Contract instance updated with client configuration for the deployed contract
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::Unauthorized` if the simulation requires an
+    /// authorization missing from `auth_entries`, or any error from the upload
+    /// or deployment
+    pub async fn deploy_from_address_with_auth_entries(
+        self,
+        env: &Env,
+        account: &mut Account,
+        deployer: ScAddress,
+        salt: Uint256,
+        constructor_args: Option<Vec<ScVal>>,
+        auth_entries: Vec<SorobanAuthorizationEntry>,
+    ) -> Result<Self, SorobanHelperError> {
+        let mut create_operation = self.create_operation(deployer, salt, constructor_args)?;
+        if let OperationBody::InvokeHostFunction(invoke) = &mut create_operation.body {
+            invoke.auth = auth_entries.try_into().map_err(|e| {
+                SorobanHelperError::XdrEncodingFailed(format!(
+                    "Failed to encode auth entries: {}",
+                    e
+                ))
+            })?;
+        }
 
-        let has_constructor =
-            String::from_utf8_lossy(&self.wasm_bytes).contains(CONSTRUCTOR_FUNCTION_NAME);
-        let create_operation = Operations::create_contract(
-            contract_id_preimage,
-            self.wasm_hash.clone(),
-            if has_constructor {
-                constructor_args
-            } else {
-                None
-            },
-        )?;
+        self.deploy_with_operation(env, account, create_operation)
+            .await
+    }
 
-        let builder = TransactionBuilder::new(account, env).add_operation(create_operation);
+    /// Uploads the contract WASM, then submits the given contract creation operation
+    async fn deploy_with_operation(
+        mut self,
+        env: &Env,
+        account: &mut Account,
+        create_operation: Operation,
+    ) -> Result<Self, SorobanHelperError> {
+        self.upload_wasm(account, env).await?;
 
-        let deploy_tx = builder.simulate_and_build(env, account).await?;
-        let tx_envelope = account.sign_transaction(&deploy_tx, &env.network_id())?;
-        let tx_result = env.send_transaction(&tx_envelope).await?;
+        let builder = TransactionBuilder::new(account, env).add_operation(create_operation);
+        let tx_result = account.submit(env, builder).await?;
 
-        let parser = Parser::new(ParserType::Deploy);
+        let mut parser = Parser::new(ParserType::Deploy);
+        // without a protocol version, the metadata format is detected from the metadata
+        if let Ok(protocol_version) = env.protocol_version().await {
+            parser = parser.with_protocol_version(protocol_version);
+        }
         let result = parser.parse(&tx_result.response)?;
 
         let contract_id = match result {
@@ -217,12 +370,116 @@ impl Contract {
         self.set_client_configs(ClientContractConfigs {
             contract_id,
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         });
 
         Ok(self)
     }
 
+    /// Upgrades the code of the deployed contract
+    ///
+    /// Uploads the new WASM bytecode if it doesn't exist on the network, then
+    /// invokes the `upgrade` function of the contract with the hash of the new
+    /// code. Soroban contracts can't be upgraded from the outside: the contract
+    /// must expose this function and call `update_current_contract_wasm` with
+    /// the given hash, usually after checking the caller's authorization.
+    ///
+    /// # Parameters
+    ///
+    /// * `new_wasm_path` - Path to the WASM file of the new contract code
+    ///
+    /// # Returns
+    ///
+    /// The transaction response of the upgrade invocation
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract has not been deployed, there is no
+    /// source account, the file can't be read, or the upload or the
+    /// invocation fails
+    pub async fn upgrade(
+        &mut self,
+        new_wasm_path: &str,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        self.upgrade_with_function(new_wasm_path, UPGRADE_FUNCTION_NAME)
+            .await
+    }
+
+    /// Upgrades the code of the deployed contract through the given function
+    ///
+    /// Same as `upgrade`, for contracts exposing their upgrade entrypoint
+    /// under another name. The function must take the new WASM hash as its
+    /// only argument.
+    ///
+    /// # Parameters
+    ///
+    /// * `new_wasm_path` - Path to the WASM file of the new contract code
+    /// * `function_name` - The name of the contract function performing the upgrade
+    ///
+    /// # Returns
+    ///
+    /// The transaction response of the upgrade invocation
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract has not been deployed, there is no
+    /// source account, the file can't be read, or the upload or the
+    /// invocation fails
+    pub async fn upgrade_with_function(
+        &mut self,
+        new_wasm_path: &str,
+        function_name: &str,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let client_configs = self
+            .client_configs
+            .as_mut()
+            .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?;
+        let contract_id = client_configs.contract_id;
+        let env = client_configs.env.clone();
+        let source_account = client_configs
+            .source_account
+            .as_mut()
+            .ok_or(SorobanHelperError::SourceAccountNotSet)?;
+
+        let new_code = Self::new(new_wasm_path, None)?;
+        new_code.upload_wasm(source_account, &env).await?;
+
+        let args = vec![new_code.wasm_hash.0.try_into_val()?];
+        let invoke_operation = Operations::invoke_contract(&contract_id, function_name, args)?;
+        let response = self.submit_invocation(invoke_operation).await?;
+
+        self.wasm_bytes = new_code.wasm_bytes;
+        self.wasm_hash = new_code.wasm_hash;
+        Ok(response)
+    }
+
+    /// Builds the operation creating an instance of the contract
+    ///
+    /// Constructor arguments are only passed if the contract has a constructor.
+    fn create_operation(
+        &self,
+        deployer: ScAddress,
+        salt: Uint256,
+        constructor_args: Option<Vec<ScVal>>,
+    ) -> Result<Operation, SorobanHelperError> {
+        let contract_id_preimage = ContractIdPreimage::Address(ContractIdPreimageFromAddress {
+            address: deployer,
+            salt,
+        });
+
+        let has_constructor =
+            String::from_utf8_lossy(&self.wasm_bytes).contains(CONSTRUCTOR_FUNCTION_NAME);
+        Operations::create_contract(
+            contract_id_preimage,
+            self.wasm_hash.clone(),
+            if has_constructor {
+                constructor_args
+            } else {
+                None
+            },
+        )
+    }
+
     /// Sets the client configuration for interacting with a deployed contract
     ///
     /// # Parameters
@@ -251,90 +508,548 @@ impl Contract {
     /// # Returns
     ///
     /// Ok(()) if the upload was successful or the code already exists
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::InvalidArgument` if the WASM is larger than
+    /// `MAX_WASM_SIZE`, before anything is submitted
     async fn upload_wasm(
         &self,
         account: &mut Account,
         env: &Env,
     ) -> Result<(), SorobanHelperError> {
+        if self.wasm_bytes.len() > MAX_WASM_SIZE {
+            return Err(SorobanHelperError::InvalidArgument(format!(
+                "contract WASM exceeds maximum size of {} bytes",
+                MAX_WASM_SIZE
+            )));
+        }
+
         let upload_operation = Operations::upload_wasm(self.wasm_bytes.clone())?;
 
         let builder = TransactionBuilder::new(account, env).add_operation(upload_operation);
 
-        let upload_tx = builder.simulate_and_build(env, account).await?;
-        let tx_envelope = account.sign_transaction(&upload_tx, &env.network_id())?;
+        match account.submit(env, builder).await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                // If it failed because the code already exists, that's fine
+                if let SorobanHelperError::ContractCodeAlreadyExists = e {
+                    Ok(())
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    /// Invokes a function on the deployed contract
+    ///
+    /// # Parameters
+    ///
+    /// * `function_name` - The name of the function to invoke
+    /// * `args` - The arguments to pass to the function
+    ///
+    /// # Returns
+    ///
+    /// The transaction response from the network
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract has not been deployed, if the client
+    /// has no source account or if there's an issue with the invocation
+    pub async fn invoke(
+        &mut self,
+        function_name: &str,
+        args: Vec<ScVal>,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let client_configs = self
+            .client_configs
+            .as_mut()
+            .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?;
+
+        let contract_id = client_configs.contract_id;
+        let invoke_operation = Operations::invoke_contract(&contract_id, function_name, args)?;
+
+        self.submit_invocation(invoke_operation).await
+    }
+
+    /// Invokes a function on the deployed contract, attaching authorization
+    /// entries signed by other addresses
+    ///
+    /// This allows the source account to submit, and pay for, an invocation
+    /// authorized by someone else, e.g. entries built with `AuthorizationBuilder`.
+    ///
+    /// # Parameters
+    ///
+    /// * `function_name` - The name of the function to invoke
+    /// * `args` - The arguments to pass to the function
+    /// * `auth_entries` - The signed authorization entries for the invocation
+    ///
+    /// # Returns
+    ///
+    /// The transaction response from the network
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract has not been deployed, if the client
+    /// has no source account or if there's an issue with the invocation
+    pub async fn invoke_with_auth_entries(
+        &mut self,
+        function_name: &str,
+        args: Vec<ScVal>,
+        auth_entries: Vec<SorobanAuthorizationEntry>,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let contract_id = self
+            .contract_id()
+            .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?;
+        let invoke_operation =
+            Operations::invoke_contract_with_auth(&contract_id, function_name, args, auth_entries)?;
+
+        self.submit_invocation(invoke_operation).await
+    }
+
+    /// Invokes several functions on the deployed contract, one transaction per call
+    ///
+    /// A transaction can only hold one contract invocation, so each call is
+    /// submitted as its own transaction, in order. The sequence number of the
+    /// first transaction comes from the source account, through its sequence
+    /// cache if enabled, and is incremented locally for the following ones.
+    ///
+    /// # Parameters
+    ///
+    /// * `calls` - The function names and arguments of the invocations
+    ///
+    /// # Returns
+    ///
+    /// The transaction responses, in the order of `calls`
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvokeSequenceError` holding the responses of the calls
+    /// submitted so far if the contract has not been deployed, there is no
+    /// source account, or any invocation fails. Calls after a failing one are
+    /// not submitted.
+    pub async fn invoke_sequence(
+        &mut self,
+        calls: Vec<(String, Vec<ScVal>)>,
+    ) -> Result<Vec<SorobanTransactionResponse>, InvokeSequenceError> {
+        let mut responses = Vec::with_capacity(calls.len());
+        match self.invoke_sequence_impl(calls, &mut responses).await {
+            Ok(()) => Ok(responses),
+            Err(error) => Err(InvokeSequenceError { responses, error }),
+        }
+    }
+
+    async fn invoke_sequence_impl(
+        &mut self,
+        calls: Vec<(String, Vec<ScVal>)>,
+        responses: &mut Vec<SorobanTransactionResponse>,
+    ) -> Result<(), SorobanHelperError> {
+        let client_configs = self
+            .client_configs
+            .as_ref()
+            .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?;
+        let source_account = client_configs
+            .source_account
+            .as_ref()
+            .ok_or(SorobanHelperError::SourceAccountNotSet)?;
+        let contract_id = client_configs.contract_id;
+        let mut sequence = source_account.peek_sequence(&client_configs.env).await?;
+
+        for (function_name, args) in calls {
+            let invoke_operation = Operations::invoke_contract(&contract_id, &function_name, args)?;
+            let response = self
+                .submit_operation(invoke_operation, |builder| builder.set_sequence(sequence))
+                .await?;
+            responses.push(response);
+            sequence = sequence.next();
+        }
+
+        Ok(())
+    }
+
+    /// Simulates a function invocation on the deployed contract and returns its result
+    ///
+    /// Nothing is signed nor submitted to the network, and no sequence number is
+    /// fetched, so this is the cheap way to call view functions. It also works
+    /// with read-only clients.
+    ///
+    /// # Parameters
+    ///
+    /// * `function_name` - The name of the function to invoke
+    /// * `args` - The arguments to pass to the function
+    ///
+    /// # Returns
+    ///
+    /// The value returned by the function
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract has not been deployed or if the simulation fails
+    pub async fn invoke_read_only(
+        &self,
+        function_name: &str,
+        args: Vec<ScVal>,
+    ) -> Result<ScVal, SorobanHelperError> {
+        let client_configs = self
+            .client_configs
+            .as_ref()
+            .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?;
+        let source = client_configs
+            .source_account
+            .as_ref()
+            .map(|account| account.account_id());
+
+        client_configs
+            .env
+            .simulate_invoke_unsigned(&client_configs.contract_id, source, function_name, args)
+            .await
+    }
+
+    /// Extends the time to live of the deployed contract instance
+    ///
+    /// The instance entry is set as the transaction footprint, and the
+    /// simulation of the transaction computes the resources and fees needed to
+    /// extend it.
+    ///
+    /// # Parameters
+    ///
+    /// * `ledgers` - The number of ledgers, from the current one, the instance must live for
+    ///
+    /// # Returns
+    ///
+    /// The transaction response from the network
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract has not been deployed, there is no
+    /// source account, or the transaction fails
+    pub async fn extend_ttl(
+        &mut self,
+        ledgers: u32,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let instance_key = self.instance_ledger_key()?;
+        let footprint = LedgerFootprint {
+            read_only: vec![instance_key].try_into()?,
+            read_write: VecM::default(),
+        };
+
+        let extend_operation = Operations::extend_footprint_ttl(ledgers)?;
+        let soroban_data = Self::soroban_data(footprint);
+        self.submit_operation(extend_operation, |builder| {
+            builder.set_soroban_data(soroban_data)
+        })
+        .await
+    }
+
+    /// Restores the archived instance and code of the deployed contract
+    ///
+    /// The instance entry and, for a contract backed by WASM, the code entry
+    /// are set as the read-write transaction footprint, and the simulation of
+    /// the transaction computes the resources and fees needed to restore them.
+    ///
+    /// The code hash is the one of the WASM the contract was created with, or
+    /// is read from the instance entry for a contract created from its configs.
+    ///
+    /// # Returns
+    ///
+    /// The transaction response from the network
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract has not been deployed
+    /// (`SorobanHelperError::ContractDeployedConfigsNotSet`), there is no
+    /// source account, the instance entry can't be read, or the transaction fails
+    pub async fn restore(&mut self) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let mut keys = vec![self.instance_ledger_key()?];
+        if let Some(code_key) = self.code_ledger_key().await? {
+            keys.push(code_key);
+        }
+        let footprint = LedgerFootprint {
+            read_only: VecM::default(),
+            read_write: keys.try_into()?,
+        };
+
+        let restore_operation = Operations::restore_footprint()?;
+        let soroban_data = Self::soroban_data(footprint);
+        self.submit_operation(restore_operation, |builder| {
+            builder.set_soroban_data(soroban_data)
+        })
+        .await
+    }
+
+    /// Returns the ledger key of the deployed contract instance entry
+    fn instance_ledger_key(&self) -> Result<LedgerKey, SorobanHelperError> {
+        let contract_id = self
+            .contract_id()
+            .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?;
+
+        Ok(ContractDataKey::instance()
+            .to_ledger_key(&contract_id, ContractDataDurability::Persistent))
+    }
+
+    /// Returns the ledger key of the WASM code of the deployed contract, or `None`
+    /// for a Stellar asset contract
+    async fn code_ledger_key(&self) -> Result<Option<LedgerKey>, SorobanHelperError> {
+        let hash = if self.wasm_bytes.is_empty() {
+            let env = &self
+                .client_configs
+                .as_ref()
+                .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?
+                .env;
+            match self.on_chain_executable(env).await? {
+                ContractExecutable::Wasm(hash) => hash,
+                ContractExecutable::StellarAsset => return Ok(None),
+            }
+        } else {
+            self.wasm_hash.clone()
+        };
+
+        Ok(Some(LedgerKey::ContractCode(LedgerKeyContractCode {
+            hash,
+        })))
+    }
+
+    /// Builds the Soroban resources of a transaction acting on the given footprint,
+    /// the remaining resources being computed by simulation
+    fn soroban_data(footprint: LedgerFootprint) -> SorobanTransactionData {
+        SorobanTransactionData {
+            ext: SorobanTransactionDataExt::V0,
+            resources: SorobanResources {
+                footprint,
+                instructions: 0,
+                disk_read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 0,
+        }
+    }
+
+    /// Builds, signs and submits a transaction holding the given invocation
+    async fn submit_invocation(
+        &mut self,
+        invoke_operation: Operation,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        self.submit_operation(invoke_operation, |builder| builder)
+            .await
+    }
+
+    /// Builds, signs and submits a transaction holding the given operation
+    ///
+    /// `configure` can customize the transaction builder before simulation.
+    async fn submit_operation(
+        &mut self,
+        operation: Operation,
+        configure: impl FnOnce(TransactionBuilder) -> TransactionBuilder,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let client_configs = self
+            .client_configs
+            .as_mut()
+            .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?;
+        let env = client_configs.env.clone();
+        let source_account = client_configs
+            .source_account
+            .as_mut()
+            .ok_or(SorobanHelperError::SourceAccountNotSet)?;
+
+        let builder =
+            configure(TransactionBuilder::new(source_account, &env).add_operation(operation));
+
+        source_account.submit(&env, builder).await
+    }
+
+    /// Reads a value from the deployed contract's storage
+    ///
+    /// The entry is read directly from the ledger, without invoking the contract.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key the value is stored under
+    /// * `durability` - The durability of the storage entry (persistent or temporary)
+    ///
+    /// # Returns
+    ///
+    /// The stored value
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract has not been deployed, the entry
+    /// doesn't exist, or it can't be decoded
+    pub async fn read(
+        &self,
+        key: ContractDataKey,
+        durability: ContractDataDurability,
+    ) -> Result<ScVal, SorobanHelperError> {
+        Ok(self.read_data(key, durability).await?.value)
+    }
+
+    /// Reads a value from the deployed contract's storage, along with its time to live
+    ///
+    /// Knowing the last ledger an entry is live at tells when it must be
+    /// extended (see `extend_ttl`) to avoid its archival.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` - The key the value is stored under
+    /// * `durability` - The durability of the storage entry (persistent or temporary)
+    ///
+    /// # Returns
+    ///
+    /// The stored value and the last ledger it is live at
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract has not been deployed, the entry
+    /// doesn't exist, or it can't be decoded
+    pub async fn read_data(
+        &self,
+        key: ContractDataKey,
+        durability: ContractDataDurability,
+    ) -> Result<ContractDataResult, SorobanHelperError> {
+        let client_configs = self
+            .client_configs
+            .as_ref()
+            .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?;
+
+        Self::read_entry(
+            &client_configs.env,
+            &client_configs.contract_id,
+            key,
+            durability,
+        )
+        .await
+    }
+
+    /// Returns the hash of the WASM code the deployed contract is running
+    ///
+    /// The hash is read from the executable of the contract instance entry, so it
+    /// can be compared with `wasm_hash_of_file` to detect drift between the
+    /// source and the deployment.
+    ///
+    /// # Parameters
+    ///
+    /// * `env` - The environment to read the contract instance from
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contract has not been deployed, the instance
+    /// entry can't be read, or the contract isn't backed by WASM code
+    pub async fn on_chain_wasm_hash(&self, env: &Env) -> Result<Hash, SorobanHelperError> {
+        match self.on_chain_executable(env).await? {
+            ContractExecutable::Wasm(hash) => Ok(hash),
+            ContractExecutable::StellarAsset => Err(SorobanHelperError::ConversionError(
+                "Contract executable is a Stellar asset, not WASM".to_string(),
+            )),
+        }
+    }
+
+    /// Reads the executable of the deployed contract from its instance entry
+    async fn on_chain_executable(
+        &self,
+        env: &Env,
+    ) -> Result<ContractExecutable, SorobanHelperError> {
+        let contract_id = self
+            .contract_id()
+            .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?;
 
-        match env.send_transaction(&tx_envelope).await {
-            Ok(_) => Ok(()),
-            Err(e) => {
-                // If it failed because the code already exists, that's fine
-                if let SorobanHelperError::ContractCodeAlreadyExists = e {
-                    Ok(())
-                } else {
-                    Err(e)
-                }
-            }
+        let instance = Self::read_entry(
+            env,
+            &contract_id,
+            ContractDataKey::instance(),
+            ContractDataDurability::Persistent,
+        )
+        .await?;
+
+        match instance.value {
+            ScVal::ContractInstance(instance) => Ok(instance.executable),
+            _ => Err(SorobanHelperError::ConversionError(
+                "Ledger entry is not a contract instance".to_string(),
+            )),
         }
     }
 
-    /// Invokes a function on the deployed contract
+    /// Checks that the deployed contract runs the expected WASM code
+    ///
+    /// Useful before invoking a contract that could have been upgraded.
     ///
     /// # Parameters
     ///
-    /// * `function_name` - The name of the function to invoke
-    /// * `args` - The arguments to pass to the function
+    /// * `env` - The environment to read the contract instance from
+    /// * `expected_wasm` - The WASM bytecode the contract is expected to run
     ///
     /// # Returns
     ///
-    /// The transaction response from the network
+    /// `true` if the on-chain code hash matches the hash of `expected_wasm`
     ///
     /// # Errors
     ///
-    /// Returns an error if the contract has not been deployed or
-    /// if there's an issue with the invocation
-    pub async fn invoke(
-        &mut self,
-        function_name: &str,
-        args: Vec<ScVal>,
-    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
-        let client_configs = self
-            .client_configs
-            .as_mut()
-            .ok_or(SorobanHelperError::ContractDeployedConfigsNotSet)?;
-
-        let contract_id = client_configs.contract_id;
-        let env = client_configs.env.clone();
-
-        let invoke_operation = Operations::invoke_contract(&contract_id, function_name, args)?;
-
-        let builder = TransactionBuilder::new(&client_configs.source_account, &env)
-            .add_operation(invoke_operation);
-
-        let invoke_tx = builder
-            .simulate_and_build(&env, &client_configs.source_account)
-            .await?;
-
-        let tx_envelope = client_configs
-            .source_account
-            .sign_transaction(&invoke_tx, &env.network_id())?;
+    /// Returns an error if the on-chain code hash can't be read, see `on_chain_wasm_hash`
+    pub async fn verify_code(
+        &self,
+        env: &Env,
+        expected_wasm: &[u8],
+    ) -> Result<bool, SorobanHelperError> {
+        let on_chain_hash = self.on_chain_wasm_hash(env).await?;
+        Ok(on_chain_hash == crypto::sha256_hash(expected_wasm))
+    }
 
-        env.send_transaction(&tx_envelope).await
+    /// Reads a contract storage entry from the ledger
+    async fn read_entry(
+        env: &Env,
+        contract_id: &ContractId,
+        key: ContractDataKey,
+        durability: ContractDataDurability,
+    ) -> Result<ContractDataResult, SorobanHelperError> {
+        let ledger_key = key.to_ledger_key(contract_id, durability);
+        let entries = env.get_ledger_entries(vec![ledger_key]).await?;
+
+        let entry = entries.first().ok_or_else(|| {
+            SorobanHelperError::InvalidArgument(format!(
+                "No contract data found for key {:?}",
+                key.as_val()
+            ))
+        })?;
+
+        match LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none())? {
+            LedgerEntryData::ContractData(data) => Ok(ContractDataResult {
+                value: data.val,
+                live_until_ledger: entry.live_until_ledger_seq_ledger_seq,
+            }),
+            _ => Err(SorobanHelperError::ConversionError(
+                "Ledger entry is not contract data".to_string(),
+            )),
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::MAX_WASM_SIZE;
     use crate::{
         crypto,
         error::SorobanHelperError,
         mock::{
             fs::MockFileReader,
-            mock_account_entry, mock_contract_id, mock_env, mock_signer1,
-            mock_simulate_tx_response, mock_transaction_response,
+            mock_account_entry, mock_contract_id, mock_env, mock_env_with_rpc_client,
+            mock_ledger_entries_response, mock_signer1, mock_simulate_tx_response,
+            mock_simulate_tx_response_with_auth, mock_transaction_response,
             transaction::{create_contract_id_val, mock_transaction_response_with_return_value},
+            MockRpcClient,
         },
-        Account, ClientContractConfigs, Contract,
+        Account, AccountSequence, ClientContractConfigs, Contract, ContractDataKey,
+        ContractDataResult, ContractId, IntoScVal, SorobanTransactionResponse,
     };
     use std::io::Write;
+    use std::sync::Arc;
+    use stellar_rpc_client::{SimulateHostFunctionResultRaw, SimulateTransactionResponse};
+    use stellar_xdr::curr::{
+        ContractDataDurability, ContractDataEntry, ContractExecutable, ContractIdPreimage,
+        ContractIdPreimageFromAddress, CreateContractArgs, ExtensionPoint, Hash, HostFunction,
+        Int128Parts, LedgerEntryData, LedgerFootprint, LedgerKey, LedgerKeyContractCode, Limits,
+        OperationBody, ScAddress, ScContractInstance, ScVal, SorobanAddressCredentials,
+        SorobanAuthorizationEntry, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+        SorobanCredentials, TransactionEnvelope, TransactionExt, Uint256, VecM, WriteXdr,
+    };
     use tempfile::NamedTempFile;
 
     #[test]
@@ -347,7 +1062,7 @@ mod test {
         let client_configs = Some(ClientContractConfigs {
             contract_id: mock_contract_id(account.clone(), &env),
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         });
 
         let original_contract = Contract {
@@ -404,7 +1119,7 @@ mod test {
             client_configs: Some(ClientContractConfigs {
                 contract_id,
                 env: env.clone(),
-                source_account: account.clone(),
+                source_account: Some(account.clone()),
             }),
         };
 
@@ -438,7 +1153,7 @@ mod test {
         let client_configs = ClientContractConfigs {
             contract_id: mock_contract_id(account.clone(), &env),
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         };
         let file_reader = MockFileReader::new(Ok(b"mock wasm bytes".to_vec()));
         let contract =
@@ -447,6 +1162,22 @@ mod test {
         assert!(contract.upload_wasm(&mut account, &env).await.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_upload_wasm_too_large() {
+        let rpc_client = Arc::new(MockRpcClient::new(None, None, None));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut account = Account::single(mock_signer1());
+        let file_reader = MockFileReader::new(Ok(vec![0; MAX_WASM_SIZE + 1]));
+        let contract = Contract::new_with_reader("path/to/wasm", None, file_reader).unwrap();
+
+        let res = contract.deploy(&env, &mut account, None).await;
+        assert!(matches!(
+            res,
+            Err(SorobanHelperError::InvalidArgument(msg)) if msg.contains("maximum size")
+        ));
+        assert_eq!(rpc_client.send_transaction_calls(), 0);
+    }
+
     #[tokio::test]
     async fn test_upload_wasm_contract_code_already_exists() {
         let simulate_transaction_envelope_result = mock_simulate_tx_response(None);
@@ -466,7 +1197,7 @@ mod test {
         let client_configs = ClientContractConfigs {
             contract_id: mock_contract_id(account.clone(), &env),
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         };
         let file_reader = MockFileReader::new(Ok(b"mock wasm bytes".to_vec()));
         let contract =
@@ -495,7 +1226,7 @@ mod test {
         let client_configs = ClientContractConfigs {
             contract_id: mock_contract_id(account.clone(), &env),
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         };
         let file_reader = MockFileReader::new(Ok(b"mock wasm bytes".to_vec()));
         let mut contract =
@@ -509,6 +1240,439 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn test_contract_invoke_read_only() {
+        let simulation = SimulateTransactionResponse {
+            results: vec![SimulateHostFunctionResultRaw {
+                auth: vec![],
+                xdr: ScVal::I128(Int128Parts { hi: 0, lo: 1000 })
+                    .to_xdr_base64(Limits::none())
+                    .unwrap(),
+            }],
+            ..mock_simulate_tx_response(None)
+        };
+        // fetching the sequence number fails, so it must not be needed
+        let rpc_client = Arc::new(MockRpcClient::new(
+            Some(Err(SorobanHelperError::NetworkRequestFailed(
+                "account not available".to_string(),
+            ))),
+            Some(Ok(simulation)),
+            None,
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let account = Account::single(mock_signer1());
+        let contract = Contract::from_configs(ClientContractConfigs::new(
+            mock_contract_id(account.clone(), &env),
+            env,
+            account,
+        ));
+
+        let balance = contract.invoke_read_only("balance", vec![]).await.unwrap();
+        assert_eq!(balance, ScVal::I128(Int128Parts { hi: 0, lo: 1000 }));
+        assert_eq!(rpc_client.send_transaction_calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_contract_invoke_not_deployed() {
+        let file_reader = MockFileReader::new(Ok(b"mock wasm bytes".to_vec()));
+        let mut contract = Contract::new_with_reader("path/to/wasm", None, file_reader).unwrap();
+
+        let err = contract.invoke("function_name", vec![]).await.unwrap_err();
+        assert!(err.is_not_deployed());
+        assert!(err.to_string().contains("Contract::deploy"));
+        assert!(err.to_string().contains("ClientContractConfigs"));
+        assert!(!SorobanHelperError::SourceAccountNotSet.is_not_deployed());
+    }
+
+    /// Deploys a mock contract with an RPC client recording the sent envelopes
+    ///
+    /// Transactions are answered with `send_results` in order, then succeed.
+    fn mock_upgradable_contract(
+        send_results: Vec<Result<SorobanTransactionResponse, SorobanHelperError>>,
+    ) -> (Contract, Arc<MockRpcClient>) {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let rpc_client = Arc::new(
+            MockRpcClient::new(
+                Some(Ok(mock_account_entry(&signer_1_account_id))),
+                Some(Ok(mock_simulate_tx_response(None))),
+                Some(Ok(mock_transaction_response())),
+            )
+            .with_send_transaction_polling_queue(send_results),
+        );
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let account = Account::single(mock_signer1());
+        let client_configs = ClientContractConfigs {
+            contract_id: mock_contract_id(account.clone(), &env),
+            env,
+            source_account: Some(account),
+        };
+        let file_reader = MockFileReader::new(Ok(b"mock wasm bytes".to_vec()));
+        let contract =
+            Contract::new_with_reader("path/to/wasm", Some(client_configs), file_reader).unwrap();
+        (contract, rpc_client)
+    }
+
+    /// Returns the host functions of the sent envelopes, in order
+    fn sent_host_functions(rpc_client: &MockRpcClient) -> Vec<HostFunction> {
+        rpc_client
+            .sent_envelopes()
+            .into_iter()
+            .map(|envelope| {
+                let TransactionEnvelope::Tx(tx_v1) = envelope else {
+                    panic!("Expected a V1 envelope");
+                };
+                match &tx_v1.tx.operations[0].body {
+                    OperationBody::InvokeHostFunction(op) => op.host_function.clone(),
+                    body => panic!("Expected a host function, got {:?}", body),
+                }
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_contract_upgrade() {
+        let wasm_path = "fixtures/soroban-helpers-example.wasm";
+        let new_wasm = std::fs::read(wasm_path).unwrap();
+        let new_hash = crypto::sha256_hash(&new_wasm);
+        let (mut contract, rpc_client) = mock_upgradable_contract(vec![]);
+        let contract_id = contract.contract_id().unwrap();
+
+        let res = contract.upgrade(wasm_path).await;
+        assert!(res.is_ok());
+        assert_eq!(contract.wasm_hash, new_hash);
+        assert_eq!(contract.wasm_bytes, new_wasm);
+
+        let host_functions = sent_host_functions(&rpc_client);
+        assert_eq!(host_functions.len(), 2);
+        assert!(matches!(
+            &host_functions[0],
+            HostFunction::UploadContractWasm(code) if code.to_vec() == new_wasm
+        ));
+        let HostFunction::InvokeContract(invoke) = &host_functions[1] else {
+            panic!("Expected a contract invocation");
+        };
+        assert_eq!(
+            invoke.contract_address,
+            ScAddress::Contract(stellar_xdr::curr::ContractId(Hash(contract_id.0)))
+        );
+        assert_eq!(invoke.function_name.to_string(), "upgrade");
+        assert_eq!(invoke.args.to_vec(), vec![new_hash.0.into_val()]);
+    }
+
+    #[tokio::test]
+    async fn test_contract_upgrade_with_function() {
+        let (mut contract, rpc_client) = mock_upgradable_contract(vec![]);
+
+        let res = contract
+            .upgrade_with_function("fixtures/soroban-helpers-example.wasm", "update_code")
+            .await;
+        assert!(res.is_ok());
+
+        let host_functions = sent_host_functions(&rpc_client);
+        assert!(matches!(
+            host_functions.last(),
+            Some(HostFunction::InvokeContract(invoke)) if invoke.function_name.to_string() == "update_code"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_contract_upgrade_not_deployed() {
+        let file_reader = MockFileReader::new(Ok(b"mock wasm bytes".to_vec()));
+        let mut contract = Contract::new_with_reader("path/to/wasm", None, file_reader).unwrap();
+
+        let err = contract
+            .upgrade("fixtures/soroban-helpers-example.wasm")
+            .await
+            .unwrap_err();
+        assert!(err.is_not_deployed());
+        assert_eq!(contract.wasm_bytes, b"mock wasm bytes".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_contract_upgrade_upload_failure() {
+        let (mut contract, rpc_client) = mock_upgradable_contract(vec![Err(
+            SorobanHelperError::TransactionFailed("upload rejected".to_string()),
+        )]);
+
+        let res = contract
+            .upgrade("fixtures/soroban-helpers-example.wasm")
+            .await;
+        assert!(res.unwrap_err().to_string().contains("upload rejected"));
+        assert_eq!(contract.wasm_bytes, b"mock wasm bytes".to_vec());
+        assert_eq!(rpc_client.sent_envelopes().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_contract_invoke_sequence() {
+        let rpc_client = Arc::new(MockRpcClient::new(
+            None,
+            Some(Ok(mock_simulate_tx_response(None))),
+            None,
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let account = Account::single(mock_signer1());
+        let mut contract = Contract::from_configs(ClientContractConfigs::new(
+            mock_contract_id(account.clone(), &env),
+            env,
+            account,
+        ));
+
+        let calls = vec![
+            ("mint".to_string(), vec![ScVal::U32(1)]),
+            ("mint".to_string(), vec![ScVal::U32(2)]),
+            ("burn".to_string(), vec![]),
+        ];
+        let responses = contract.invoke_sequence(calls).await.unwrap();
+        assert_eq!(responses.len(), 3);
+
+        // the sequence is fetched once, then incremented locally
+        assert_eq!(rpc_client.get_account_calls(), 1);
+        let envelopes = rpc_client.sent_envelopes();
+        let invocations: Vec<(i64, String)> = envelopes
+            .iter()
+            .map(|envelope| {
+                let TransactionEnvelope::Tx(tx_v1) = envelope else {
+                    panic!("Expected TransactionEnvelope::Tx");
+                };
+                let OperationBody::InvokeHostFunction(op) = &tx_v1.tx.operations[0].body else {
+                    panic!("Expected OperationBody::InvokeHostFunction");
+                };
+                let HostFunction::InvokeContract(args) = &op.host_function else {
+                    panic!("Expected HostFunction::InvokeContract");
+                };
+                (tx_v1.tx.seq_num.0, args.function_name.to_string())
+            })
+            .collect();
+        assert_eq!(
+            invocations,
+            vec![
+                (1, "mint".to_string()),
+                (2, "mint".to_string()),
+                (3, "burn".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_contract_invoke_sequence_partial_failure() {
+        let rpc_client = Arc::new(
+            MockRpcClient::new(None, Some(Ok(mock_simulate_tx_response(None))), None)
+                .with_send_transaction_polling_queue(vec![
+                    Ok(mock_transaction_response()),
+                    Err(SorobanHelperError::TransactionFailed(
+                        "mint rejected".to_string(),
+                    )),
+                ]),
+        );
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let account = Account::single(mock_signer1());
+        let mut contract = Contract::from_configs(ClientContractConfigs::new(
+            mock_contract_id(account.clone(), &env),
+            env,
+            account,
+        ));
+
+        let calls = vec![
+            ("mint".to_string(), vec![ScVal::U32(1)]),
+            ("mint".to_string(), vec![ScVal::U32(2)]),
+            ("burn".to_string(), vec![]),
+        ];
+        let error = contract.invoke_sequence(calls).await.unwrap_err();
+
+        // the first response is kept and the last call is never submitted
+        assert_eq!(error.responses.len(), 1);
+        assert_eq!(
+            error.error,
+            SorobanHelperError::TransactionFailed("mint rejected".to_string())
+        );
+        assert_eq!(rpc_client.send_transaction_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_contract_invoke_sequence_with_sequence_cache() {
+        let rpc_client = Arc::new(MockRpcClient::new(
+            None,
+            Some(Ok(mock_simulate_tx_response(None))),
+            None,
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut account = Account::single(mock_signer1());
+        account.use_sequence_cache(true);
+        account.set_cached_sequence(AccountSequence::new(10));
+        let mut contract = Contract::from_configs(ClientContractConfigs::new(
+            mock_contract_id(account.clone(), &env),
+            env,
+            account.clone(),
+        ));
+
+        let calls = vec![
+            ("mint".to_string(), vec![ScVal::U32(1)]),
+            ("burn".to_string(), vec![]),
+        ];
+        let responses = contract.invoke_sequence(calls).await.unwrap();
+        assert_eq!(responses.len(), 2);
+
+        // the cached sequence is used and advanced, without fetching it
+        assert_eq!(rpc_client.get_account_calls(), 0);
+        let sequences: Vec<i64> = rpc_client
+            .sent_envelopes()
+            .iter()
+            .map(|envelope| match envelope {
+                TransactionEnvelope::Tx(tx_v1) => tx_v1.tx.seq_num.0,
+                _ => panic!("Expected TransactionEnvelope::Tx"),
+            })
+            .collect();
+        assert_eq!(sequences, vec![11, 12]);
+        assert_eq!(account.cached_sequence(), Some(AccountSequence::new(12)));
+    }
+
+    #[tokio::test]
+    async fn test_contract_extend_ttl() {
+        let rpc_client = Arc::new(MockRpcClient::new(
+            None,
+            Some(Ok(mock_simulate_tx_response(None))),
+            None,
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let account = Account::single(mock_signer1());
+        let contract_id = mock_contract_id(account.clone(), &env);
+        let mut contract =
+            Contract::from_configs(ClientContractConfigs::new(contract_id, env, account));
+
+        contract.extend_ttl(17_280).await.unwrap();
+
+        let envelopes = rpc_client.sent_envelopes();
+        assert_eq!(envelopes.len(), 1);
+        let TransactionEnvelope::Tx(tx_v1) = &envelopes[0] else {
+            panic!("Expected TransactionEnvelope::Tx");
+        };
+        match &tx_v1.tx.operations[0].body {
+            OperationBody::ExtendFootprintTtl(op) => assert_eq!(op.extend_to, 17_280),
+            other => panic!(
+                "Expected OperationBody::ExtendFootprintTtl, got {:?}",
+                other
+            ),
+        }
+        let TransactionExt::V1(soroban_data) = &tx_v1.tx.ext else {
+            panic!("Expected TransactionExt::V1");
+        };
+        assert_eq!(
+            soroban_data.resources.footprint.read_only.to_vec(),
+            vec![ContractDataKey::instance()
+                .to_ledger_key(&contract_id, ContractDataDurability::Persistent)]
+        );
+        assert!(soroban_data.resources.footprint.read_write.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_contract_extend_ttl_not_deployed() {
+        let mut contract = Contract::new_with_reader(
+            "path/to/wasm",
+            None,
+            MockFileReader::new(Ok(b"mock wasm bytes".to_vec())),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            contract.extend_ttl(100).await,
+            Err(SorobanHelperError::ContractDeployedConfigsNotSet)
+        ));
+    }
+
+    fn sent_restore_footprint(rpc_client: &MockRpcClient) -> LedgerFootprint {
+        let envelopes = rpc_client.sent_envelopes();
+        assert_eq!(envelopes.len(), 1);
+        let TransactionEnvelope::Tx(tx_v1) = &envelopes[0] else {
+            panic!("Expected TransactionEnvelope::Tx");
+        };
+        assert!(matches!(
+            tx_v1.tx.operations[0].body,
+            OperationBody::RestoreFootprint(_)
+        ));
+        let TransactionExt::V1(soroban_data) = &tx_v1.tx.ext else {
+            panic!("Expected TransactionExt::V1");
+        };
+        soroban_data.resources.footprint.clone()
+    }
+
+    #[tokio::test]
+    async fn test_contract_restore() {
+        let rpc_client = Arc::new(MockRpcClient::new(
+            None,
+            Some(Ok(mock_simulate_tx_response(None))),
+            None,
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let account = Account::single(mock_signer1());
+        let contract_id = mock_contract_id(account.clone(), &env);
+        let wasm_bytes = b"mock wasm bytes".to_vec();
+        let mut contract = Contract::new_with_reader(
+            "path/to/wasm",
+            Some(ClientContractConfigs::new(contract_id, env, account)),
+            MockFileReader::new(Ok(wasm_bytes.clone())),
+        )
+        .unwrap();
+
+        contract.restore().await.unwrap();
+
+        let footprint = sent_restore_footprint(&rpc_client);
+        assert!(footprint.read_only.is_empty());
+        assert_eq!(
+            footprint.read_write.to_vec(),
+            vec![
+                ContractDataKey::instance()
+                    .to_ledger_key(&contract_id, ContractDataDurability::Persistent),
+                LedgerKey::ContractCode(LedgerKeyContractCode {
+                    hash: crypto::sha256_hash(&wasm_bytes),
+                }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_contract_restore_from_configs() {
+        let account = Account::single(mock_signer1());
+        let contract_id = mock_contract_id(account.clone(), &mock_env(None, None, None));
+        let wasm_hash = Hash([3; 32]);
+        let rpc_client = Arc::new(
+            mock_instance_rpc_client(&contract_id, wasm_hash.clone())
+                .with_simulate_transaction_envelope_queue(vec![Ok(mock_simulate_tx_response(
+                    None,
+                ))]),
+        );
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut contract =
+            Contract::from_configs(ClientContractConfigs::new(contract_id, env, account));
+
+        contract.restore().await.unwrap();
+
+        // the code hash is read from the instance entry
+        let footprint = sent_restore_footprint(&rpc_client);
+        assert_eq!(
+            footprint.read_write.to_vec(),
+            vec![
+                ContractDataKey::instance()
+                    .to_ledger_key(&contract_id, ContractDataDurability::Persistent),
+                LedgerKey::ContractCode(LedgerKeyContractCode { hash: wasm_hash }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_contract_restore_not_deployed() {
+        let mut contract = Contract::new_with_reader(
+            "path/to/wasm",
+            None,
+            MockFileReader::new(Ok(b"mock wasm bytes".to_vec())),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            contract.restore().await,
+            Err(SorobanHelperError::ContractDeployedConfigsNotSet)
+        ));
+    }
+
     #[tokio::test]
     async fn test_contract_deploy() {
         let simulate_transaction_envelope_result = mock_simulate_tx_response(None);
@@ -529,7 +1693,7 @@ mod test {
         let client_configs = ClientContractConfigs {
             contract_id: mock_contract_id(account.clone(), &env),
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         };
         let file_reader = MockFileReader::new(Ok(b"mock wasm bytes".to_vec()));
 
@@ -541,6 +1705,159 @@ mod test {
         assert_eq!(res.unwrap().wasm_hash, wasm_hash);
     }
 
+    #[tokio::test]
+    async fn test_contract_deploy_protocol_version_mismatch() {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        // the mock transaction metadata is in the V3 format, which protocol 23 replaced
+        let rpc_client = MockRpcClient::new(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response_with_return_value(
+                create_contract_id_val(),
+            ))),
+        )
+        .with_protocol_version(23);
+        let env = mock_env_with_rpc_client(rpc_client);
+        let mut account = Account::single(mock_signer1());
+        let file_reader = MockFileReader::new(Ok(b"mock wasm bytes".to_vec()));
+        let contract = Contract::new_with_reader("path/to/wasm", None, file_reader).unwrap();
+
+        let res = contract.deploy(&env, &mut account, None).await;
+        assert!(matches!(res, Err(SorobanHelperError::ConversionError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_from_address() {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let env = mock_env(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response_with_return_value(
+                create_contract_id_val(),
+            ))),
+        );
+        let mut account = Account::single(mock_signer1());
+        let factory = ScAddress::Contract(stellar_xdr::curr::ContractId(Hash([5; 32])));
+        let salt = Uint256([6; 32]);
+
+        let file_reader = MockFileReader::new(Ok(b"mock wasm bytes".to_vec()));
+        let contract = Contract::new_with_reader("path/to/wasm", None, file_reader).unwrap();
+
+        let operation = contract
+            .create_operation(factory.clone(), salt.clone(), None)
+            .unwrap();
+        match operation.body {
+            OperationBody::InvokeHostFunction(op) => match op.host_function {
+                HostFunction::CreateContract(args) => assert_eq!(
+                    args.contract_id_preimage,
+                    ContractIdPreimage::Address(ContractIdPreimageFromAddress {
+                        address: factory.clone(),
+                        salt: salt.clone(),
+                    })
+                ),
+                other => panic!("Expected HostFunction::CreateContract, got {:?}", other),
+            },
+            other => panic!("Expected InvokeHostFunction, got {:?}", other),
+        }
+
+        // only the source account can deploy without authorization entries
+        let result = contract
+            .clone()
+            .deploy_from_address(&env, &mut account, factory, salt.clone(), None)
+            .await;
+        assert!(matches!(result, Err(SorobanHelperError::Unauthorized(_))));
+
+        let deployer = ScAddress::Account(account.account_id());
+        let deployed = contract
+            .deploy_from_address(&env, &mut account, deployer, salt, None)
+            .await
+            .unwrap();
+        assert!(deployed.contract_id().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_from_address_with_auth_entries() {
+        let factory = ScAddress::Contract(stellar_xdr::curr::ContractId(Hash([5; 32])));
+        let salt = Uint256([6; 32]);
+        let file_reader = || MockFileReader::new(Ok(b"mock wasm bytes".to_vec()));
+        let factory_auth = SorobanAuthorizationEntry {
+            credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+                address: factory.clone(),
+                nonce: 1,
+                signature_expiration_ledger: 100,
+                signature: ScVal::Void,
+            }),
+            root_invocation: SorobanAuthorizedInvocation {
+                function: SorobanAuthorizedFunction::CreateContractHostFn(CreateContractArgs {
+                    contract_id_preimage: ContractIdPreimage::Address(
+                        ContractIdPreimageFromAddress {
+                            address: factory.clone(),
+                            salt: salt.clone(),
+                        },
+                    ),
+                    executable: ContractExecutable::Wasm(crypto::sha256_hash(b"mock wasm bytes")),
+                }),
+                sub_invocations: VecM::default(),
+            },
+        };
+        // the upload doesn't require any authorization, the deployment requires the factory's
+        let rpc_client = || {
+            MockRpcClient::new(
+                None,
+                None,
+                Some(Ok(mock_transaction_response_with_return_value(
+                    create_contract_id_val(),
+                ))),
+            )
+            .with_simulate_transaction_envelope_queue(vec![
+                Ok(mock_simulate_tx_response(None)),
+                Ok(mock_simulate_tx_response_with_auth(vec![
+                    factory_auth.clone()
+                ])),
+            ])
+        };
+        let mut account = Account::single(mock_signer1());
+
+        let env = mock_env_with_rpc_client(rpc_client());
+        let contract = Contract::new_with_reader("path/to/wasm", None, file_reader()).unwrap();
+        let result = contract
+            .deploy_from_address_with_auth_entries(
+                &env,
+                &mut account,
+                factory.clone(),
+                salt.clone(),
+                None,
+                vec![],
+            )
+            .await;
+        assert!(matches!(result, Err(SorobanHelperError::Unauthorized(_))));
+
+        let rpc_client = Arc::new(rpc_client());
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let contract = Contract::new_with_reader("path/to/wasm", None, file_reader()).unwrap();
+        let deployed = contract
+            .deploy_from_address_with_auth_entries(
+                &env,
+                &mut account,
+                factory,
+                salt,
+                None,
+                vec![factory_auth.clone()],
+            )
+            .await
+            .unwrap();
+        assert!(deployed.contract_id().is_some());
+
+        let envelopes = rpc_client.sent_envelopes();
+        let TransactionEnvelope::Tx(tx_v1) = &envelopes[1] else {
+            panic!("Expected TransactionEnvelope::Tx");
+        };
+        let OperationBody::InvokeHostFunction(invoke) = &tx_v1.tx.operations[0].body else {
+            panic!("Expected InvokeHostFunction");
+        };
+        assert_eq!(invoke.auth.to_vec(), vec![factory_auth]);
+    }
+
     #[test]
     fn test_set_client_configs() {
         let wasm_bytes = b"mock wasm bytes".to_vec();
@@ -557,7 +1874,7 @@ mod test {
         let configs = ClientContractConfigs {
             contract_id,
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         };
 
         contract.set_client_configs(configs.clone());
@@ -576,7 +1893,7 @@ mod test {
         let client_configs = ClientContractConfigs {
             contract_id,
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         };
         let contract = Contract::from_configs(client_configs.clone());
 
@@ -588,4 +1905,175 @@ mod test {
         assert!(contract.wasm_bytes.is_empty());
         assert_eq!(contract.wasm_hash, crypto::sha256_hash(&[]));
     }
+
+    #[tokio::test]
+    async fn test_read() {
+        let account = Account::single(mock_signer1());
+        let contract_id = mock_contract_id(account.clone(), &mock_env(None, None, None));
+        let key = ContractDataKey::symbol("ADMIN").unwrap();
+        let ledger_key = key.to_ledger_key(&contract_id, ContractDataDurability::Persistent);
+        let LedgerKey::ContractData(key_data) = ledger_key.clone() else {
+            panic!("Expected LedgerKey::ContractData");
+        };
+        let entry = LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: key_data.contract,
+            key: key_data.key,
+            durability: ContractDataDurability::Persistent,
+            val: ScVal::U32(42),
+        });
+
+        let rpc_client = MockRpcClient::new(None, None, None).with_get_ledger_entries_result(Ok(
+            mock_ledger_entries_response(vec![(ledger_key, entry)]),
+        ));
+        let env = mock_env_with_rpc_client(rpc_client);
+        let contract = Contract::from_configs(ClientContractConfigs {
+            contract_id,
+            env,
+            source_account: Some(account),
+        });
+
+        let val = contract
+            .read(key, ContractDataDurability::Persistent)
+            .await
+            .unwrap();
+        assert_eq!(val, ScVal::U32(42));
+    }
+
+    #[tokio::test]
+    async fn test_read_data() {
+        let account = Account::single(mock_signer1());
+        let contract_id = mock_contract_id(account.clone(), &mock_env(None, None, None));
+        let key = ContractDataKey::symbol("ADMIN").unwrap();
+        let ledger_key = key.to_ledger_key(&contract_id, ContractDataDurability::Persistent);
+        let LedgerKey::ContractData(key_data) = ledger_key.clone() else {
+            panic!("Expected LedgerKey::ContractData");
+        };
+        let entry = LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: key_data.contract,
+            key: key_data.key,
+            durability: ContractDataDurability::Persistent,
+            val: ScVal::U32(42),
+        });
+        let mut response = mock_ledger_entries_response(vec![(ledger_key, entry)]);
+        response.entries.as_mut().unwrap()[0].live_until_ledger_seq_ledger_seq = Some(1_000);
+
+        let rpc_client =
+            MockRpcClient::new(None, None, None).with_get_ledger_entries_result(Ok(response));
+        let env = mock_env_with_rpc_client(rpc_client);
+        let contract =
+            Contract::from_configs(ClientContractConfigs::new(contract_id, env, account));
+
+        let data = contract
+            .read_data(key, ContractDataDurability::Persistent)
+            .await
+            .unwrap();
+        assert_eq!(
+            data,
+            ContractDataResult {
+                value: ScVal::U32(42),
+                live_until_ledger: Some(1_000),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_not_found() {
+        let env = mock_env(None, None, None);
+        let account = Account::single(mock_signer1());
+        let contract = Contract::from_configs(ClientContractConfigs {
+            contract_id: mock_contract_id(account.clone(), &env),
+            env,
+            source_account: Some(account),
+        });
+
+        let res = contract
+            .read(
+                ContractDataKey::instance(),
+                ContractDataDurability::Persistent,
+            )
+            .await;
+        assert!(matches!(res, Err(SorobanHelperError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_wasm_hash_of_file() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let wasm_bytes = b"test wasm bytes";
+        temp_file.write_all(wasm_bytes).unwrap();
+
+        let wasm_path = temp_file.path().to_str().unwrap();
+        let hash = Contract::wasm_hash_of_file(wasm_path).unwrap();
+
+        assert_eq!(hash, crypto::sha256_hash(wasm_bytes));
+        assert_eq!(hash, Contract::new(wasm_path, None).unwrap().wasm_hash);
+    }
+
+    #[test]
+    fn test_wasm_hash_of_file_not_found() {
+        let res = Contract::wasm_hash_of_file("nonexistent_file.wasm");
+        assert!(matches!(res, Err(SorobanHelperError::FileReadError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_on_chain_wasm_hash() {
+        let account = Account::single(mock_signer1());
+        let contract_id = mock_contract_id(account.clone(), &mock_env(None, None, None));
+        let wasm_hash = Hash([3; 32]);
+        let env =
+            mock_env_with_rpc_client(mock_instance_rpc_client(&contract_id, wasm_hash.clone()));
+        let contract = Contract::from_configs(ClientContractConfigs {
+            contract_id,
+            env: env.clone(),
+            source_account: Some(account),
+        });
+
+        assert_eq!(contract.on_chain_wasm_hash(&env).await.unwrap(), wasm_hash);
+    }
+
+    #[tokio::test]
+    async fn test_verify_code() {
+        let account = Account::single(mock_signer1());
+        let contract_id = mock_contract_id(account.clone(), &mock_env(None, None, None));
+        let wasm = b"deployed wasm bytes";
+        let env = mock_env_with_rpc_client(mock_instance_rpc_client(
+            &contract_id,
+            crypto::sha256_hash(wasm),
+        ));
+        let contract = Contract::from_configs(ClientContractConfigs::new(
+            contract_id,
+            env.clone(),
+            account,
+        ));
+
+        assert!(contract.verify_code(&env, wasm).await.unwrap());
+        assert!(!contract
+            .verify_code(&env, b"other wasm bytes")
+            .await
+            .unwrap());
+    }
+
+    /// Mocks an RPC client returning the instance entry of a contract running `wasm_hash`
+    fn mock_instance_rpc_client(contract_id: &ContractId, wasm_hash: Hash) -> MockRpcClient {
+        let ledger_key = ContractDataKey::instance()
+            .to_ledger_key(contract_id, ContractDataDurability::Persistent);
+        let LedgerKey::ContractData(key_data) = ledger_key.clone() else {
+            panic!("Expected LedgerKey::ContractData");
+        };
+        let entry = LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: key_data.contract,
+            key: key_data.key,
+            durability: ContractDataDurability::Persistent,
+            val: ScVal::ContractInstance(ScContractInstance {
+                executable: ContractExecutable::Wasm(wasm_hash),
+                storage: None,
+            }),
+        });
+
+        MockRpcClient::new(None, None, None).with_get_ledger_entries_result(Ok(
+            mock_ledger_entries_response(vec![(ledger_key, entry)]),
+        ))
+    }
 }
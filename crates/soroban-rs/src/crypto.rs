@@ -1,12 +1,17 @@
 //! # Soroban Cryptography Utilities
 //!
 //! This module provides cryptographic functions for Soroban contracts, including
-//! hashing, random salt generation, and contract ID calculation.
+//! hashing (SHA-256 and Keccak-256), random salt generation, and contract ID calculation.
 use crate::error::SorobanHelperError;
-use sha2::{Digest, Sha256};
+use ed25519_dalek::{Signature, VerifyingKey};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, Sha512};
+use sha3::Keccak256;
+use stellar_strkey::ed25519::PublicKey;
 use stellar_xdr::curr::{
-    ContractIdPreimage, ContractIdPreimageFromAddress, Hash, HashIdPreimage,
-    HashIdPreimageContractId, Limits, ScAddress, Uint256, WriteXdr,
+    Asset, ContractIdPreimage, ContractIdPreimageFromAddress, Hash, HashIdPreimage,
+    HashIdPreimageContractId, Limits, ScAddress, Transaction, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, Uint256, WriteXdr,
 };
 
 /// Computes the SHA-256 hash of the provided data.
@@ -23,6 +28,156 @@ pub fn sha256_hash(data: &[u8]) -> Hash {
     Hash(hash_bytes)
 }
 
+/// Verifies an ed25519 signature against a Stellar public key.
+///
+/// Verification is strict, rejecting the malleable signatures and weak keys
+/// accepted by plain ed25519 verification.
+///
+/// # Parameters
+///
+/// * `public_key` - The public key of the expected signer
+/// * `message` - The signed bytes
+/// * `signature` - The raw signature
+///
+/// # Returns
+///
+/// Whether the signature is valid for the message and public key
+pub fn verify_signature(public_key: &PublicKey, message: &[u8], signature: &[u8; 64]) -> bool {
+    VerifyingKey::from_bytes(&public_key.0)
+        .map(|key| {
+            key.verify_strict(message, &Signature::from_bytes(signature))
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// Number of PBKDF2 iterations used to derive a BIP-39 seed.
+const BIP39_PBKDF2_ROUNDS: u32 = 2048;
+
+/// Computes HMAC-SHA512 of the given data.
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for chunk in data {
+        mac.update(chunk);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// Computes the BIP-39 seed of a mnemonic phrase.
+///
+/// The seed is derived with PBKDF2-HMAC-SHA512 over the phrase, salted with
+/// `"mnemonic"` followed by the optional passphrase. Words are expected to be
+/// separated by single spaces; the phrase is not checked against a wordlist.
+///
+/// # Parameters
+///
+/// * `phrase` - The mnemonic phrase
+/// * `passphrase` - The optional passphrase, empty if none
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> [u8; 64] {
+    // The seed is exactly one HMAC-SHA512 block long, so PBKDF2 has a single block
+    let salt = format!("mnemonic{}", passphrase);
+    let mut block = hmac_sha512(phrase.as_bytes(), &[salt.as_bytes(), &1u32.to_be_bytes()]);
+    let mut seed = block;
+    for _ in 1..BIP39_PBKDF2_ROUNDS {
+        block = hmac_sha512(phrase.as_bytes(), &[&block]);
+        seed.iter_mut().zip(block).for_each(|(s, b)| *s ^= b);
+    }
+    seed
+}
+
+/// Derives an ed25519 private key from a seed following SLIP-0010.
+///
+/// Only hardened derivation is defined for ed25519, so every index of the
+/// path is hardened.
+///
+/// # Parameters
+///
+/// * `seed` - The master seed, e.g. computed by `mnemonic_to_seed`
+/// * `path` - The derivation path indexes, without the hardened bit
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::InvalidArgument` if an index is greater than `2^31 - 1`
+pub fn derive_ed25519_key(seed: &[u8], path: &[u32]) -> Result<[u8; 32], SorobanHelperError> {
+    let mut node = hmac_sha512(b"ed25519 seed", &[seed]);
+    for index in path {
+        if *index >= 0x8000_0000 {
+            return Err(SorobanHelperError::InvalidArgument(format!(
+                "Derivation index {} is out of range",
+                index
+            )));
+        }
+        let (key, chain_code) = node.split_at(32);
+        node = hmac_sha512(
+            chain_code,
+            &[&[0], key, &(index | 0x8000_0000).to_be_bytes()],
+        );
+    }
+    let mut key = [0; 32];
+    key.copy_from_slice(&node[..32]);
+    Ok(key)
+}
+
+/// Computes the Keccak-256 hash of the provided data.
+///
+/// This is the original Keccak variant used by Ethereum, which differs from
+/// the standardized SHA3-256 by its padding.
+///
+/// # Parameters
+///
+/// * `data` - The byte slice to hash
+///
+/// # Returns
+///
+/// The Keccak-256 hash
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Computes the hash of a transaction, which identifies it on the network
+/// and is the payload signed by its signers.
+///
+/// # Parameters
+///
+/// * `tx` - The transaction to hash
+/// * `network_id` - The network ID hash
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::XdrEncodingFailed` if the signature payload
+/// cannot be encoded to XDR format
+pub fn transaction_hash(
+    tx: &Transaction,
+    network_id: &Hash,
+) -> Result<[u8; 32], SorobanHelperError> {
+    signature_payload_hash(
+        TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+        network_id,
+    )
+}
+
+/// Computes the SHA-256 hash of the signature payload of a transaction.
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::XdrEncodingFailed` if the signature payload
+/// cannot be encoded to XDR format
+pub fn signature_payload_hash(
+    tagged_transaction: TransactionSignaturePayloadTaggedTransaction,
+    network_id: &Hash,
+) -> Result<[u8; 32], SorobanHelperError> {
+    let signature_payload = TransactionSignaturePayload {
+        network_id: network_id.clone(),
+        tagged_transaction,
+    };
+
+    let payload_xdr = signature_payload
+        .to_xdr(Limits::none())
+        .map_err(|e| SorobanHelperError::XdrEncodingFailed(e.to_string()))?;
+
+    Ok(Sha256::digest(payload_xdr).into())
+}
+
 /// Generates a random salt.
 ///
 /// # Returns
@@ -73,9 +228,115 @@ pub fn calculate_contract_id(
     Ok(contract_id)
 }
 
+/// Calculates the ID of the Stellar Asset Contract (SAC) of a classic asset.
+///
+/// The ID only depends on the asset and the network, so it can be computed
+/// whether or not the contract has been deployed yet.
+///
+/// # Parameters
+///
+/// * `asset` - The classic asset wrapped by the contract
+/// * `network_id` - The network ID hash
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::XdrEncodingFailed` if the HashIdPreimage
+/// cannot be encoded to XDR format
+pub fn contract_id_from_asset(
+    asset: &Asset,
+    network_id: &Hash,
+) -> Result<stellar_strkey::Contract, SorobanHelperError> {
+    let preimage = HashIdPreimage::ContractId(HashIdPreimageContractId {
+        network_id: network_id.clone(),
+        contract_id_preimage: ContractIdPreimage::Asset(asset.clone()),
+    });
+
+    let preimage_xdr = preimage
+        .to_xdr(Limits::none())
+        .map_err(|e| SorobanHelperError::XdrEncodingFailed(e.to_string()))?;
+
+    Ok(stellar_strkey::Contract(
+        Sha256::digest(preimage_xdr).into(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock::{mock_signer1, mock_signer2, mock_transaction};
+    use stellar_xdr::curr::{AccountId, AlphaNum4, AssetCode4};
+
+    #[test]
+    fn test_keccak256() {
+        assert_eq!(
+            hex::encode(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex::encode(keccak256(b"abc")),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let signer = mock_signer1();
+        let message = b"signed off-chain";
+        let signature = signer.sign_payload(message).unwrap().to_bytes();
+
+        assert!(verify_signature(&signer.public_key(), message, &signature));
+        // wrong key
+        assert!(!verify_signature(
+            &mock_signer2().public_key(),
+            message,
+            &signature
+        ));
+        // wrong message
+        assert!(!verify_signature(
+            &signer.public_key(),
+            b"signed off-chain!",
+            &signature
+        ));
+        // not a curve point
+        assert!(!verify_signature(
+            &PublicKey([0xff; 32]),
+            message,
+            &signature
+        ));
+    }
+
+    #[test]
+    fn test_mnemonic_to_seed() {
+        // SEP-0005 test case 1
+        let seed = mnemonic_to_seed(
+            "illness spike retreat truth genius clock brain pass fit cave bargain toe",
+            "",
+        );
+        assert_eq!(
+            hex::encode(seed),
+            "e4a5a632e70943ae7f07659df1332160937fad82587216a4c64315a0fb39497e\
+             e4a01f76ddab4cba68147977f3a147b6ad584c41808e8238a07f6cc4b582f186"
+        );
+    }
+
+    #[test]
+    fn test_derive_ed25519_key() {
+        // SLIP-0010 test vector 1 for ed25519
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        assert_eq!(
+            hex::encode(derive_ed25519_key(&seed, &[]).unwrap()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            hex::encode(derive_ed25519_key(&seed, &[0]).unwrap()),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+
+        assert!(matches!(
+            derive_ed25519_key(&seed, &[0x8000_0000]),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+    }
 
     #[test]
     fn test_sha256_hash() {
@@ -86,6 +347,29 @@ mod tests {
         assert_eq!(hash.to_string(), expected_hash);
     }
 
+    #[test]
+    fn test_transaction_hash() {
+        let signer = mock_signer1();
+        let tx = mock_transaction(signer.account_id(), vec![]);
+        let network_id = Hash([0; 32]);
+
+        let hash = transaction_hash(&tx, &network_id).unwrap();
+        assert_eq!(
+            hex::encode(hash),
+            "da02e7d2855f2276f6d07fd0481aa36d13728f6d67d85a087c778aaa76c8f45a"
+        );
+
+        // the hash is the payload signed by the signers
+        let signature = signer.sign_transaction(&tx, &network_id).unwrap();
+        assert_eq!(
+            signature.signature.0.to_vec(),
+            signer.sign_payload(&hash).unwrap().to_vec()
+        );
+
+        // and depends on the network
+        assert_ne!(transaction_hash(&tx, &Hash([1; 32])).unwrap(), hash);
+    }
+
     #[test]
     fn test_generate_salt_chi_squared() {
         // Testing Random Number Generators
@@ -147,4 +431,40 @@ mod tests {
         assert_eq!(contract_id.0.len(), 32);
         assert!(contract_id.0.iter().any(|&x| x != 0));
     }
+
+    #[test]
+    fn test_contract_id_from_asset() {
+        let testnet = sha256_hash(b"Test SDF Network ; September 2015");
+        let mainnet = sha256_hash(b"Public Global Stellar Network ; September 2015");
+
+        // native asset
+        assert_eq!(
+            contract_id_from_asset(&Asset::Native, &testnet)
+                .unwrap()
+                .to_string(),
+            "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+        );
+        assert_eq!(
+            contract_id_from_asset(&Asset::Native, &mainnet)
+                .unwrap()
+                .to_string(),
+            "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA"
+        );
+
+        // testnet USDC
+        let issuer = stellar_strkey::ed25519::PublicKey::from_string(
+            "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5",
+        )
+        .unwrap();
+        let usdc = Asset::CreditAlphanum4(AlphaNum4 {
+            asset_code: AssetCode4(*b"USDC"),
+            issuer: AccountId(stellar_xdr::curr::PublicKey::PublicKeyTypeEd25519(Uint256(
+                issuer.0,
+            ))),
+        });
+        assert_eq!(
+            contract_id_from_asset(&usdc, &testnet).unwrap().to_string(),
+            "CBIELTK6YBZJU5UP2WWQEUCYKLPU6AUNZ2BQ4WWFEIE3USCIHMXQDAMA"
+        );
+    }
 }
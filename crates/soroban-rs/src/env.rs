@@ -18,10 +18,13 @@
 //!
 //! async fn example() {
 //!     // Create a new environment for the Stellar testnet
-//!     let env = Env::new(EnvConfigs {
-//!         rpc_url: "https://soroban-testnet.stellar.org".to_string(),
-//!         network_passphrase: "Test SDF Network ; September 2015".to_string(),
-//!     }).unwrap();
+//!     let env = Env::testnet().unwrap();
+//!
+//!     // or for a custom endpoint
+//!     let env = Env::new(EnvConfigs::new(
+//!         "https://soroban-testnet.stellar.org",
+//!         "Test SDF Network ; September 2015",
+//!     )).unwrap();
 //!
 //!     // Retrieve account information
 //!     let account = env.get_account("G..........").await.unwrap();
@@ -30,24 +33,177 @@
 //! ```
 use crate::{
     error::SorobanHelperError,
+    event::{EventFilter, EventInfo},
+    operation::Operations,
     rpc::{ExternalRpcClient, RpcClient},
-    SorobanTransactionResponse,
+    transaction::DEFAULT_TRANSACTION_FEES,
+    Account, BeginSubmission, SorobanTransactionResponse, SubmissionGuard, TransactionBuilder,
 };
 use sha2::{Digest, Sha256};
-use std::sync::Arc;
-use stellar_rpc_client::SimulateTransactionResponse;
-use stellar_xdr::curr::{AccountEntry, Hash, TransactionEnvelope};
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use stellar_rpc_client::{
+    GetHealthResponse, GetLatestLedgerResponse, LedgerEntryResult, SimulateTransactionResponse,
+};
+use stellar_strkey::Contract as ContractId;
+use stellar_xdr::curr::{
+    AccountEntry, AccountId, Hash, LedgerKey, Memo, MuxedAccount, Preconditions, ScVal,
+    SequenceNumber, Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope,
+    Uint256, VecM,
+};
+
+/// Retry policy applied to the RPC requests made by an `Env`.
+///
+/// Requests failing with `SorobanHelperError::TransportFailed`, i.e. that were
+/// not processed by the server, are retried up to `max_retries` times, waiting
+/// `base_delay * 2^n` before the n-th retry. Retries and timeouts are disabled
+/// by default.
+///
+/// With the `serde` feature, missing fields are deserialized to their default
+/// value and durations are represented as `{ "secs": .., "nanos": .. }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct RetryConfig {
+    /// Number of retries after the first failed attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each subsequent one
+    pub base_delay: Duration,
+    /// Maximum duration of a single attempt, unbounded if `None`
+    pub timeout: Option<Duration>,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            base_delay: Duration::from_millis(500),
+            timeout: None,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Returns the delay to wait before the given retry, starting at 0.
+    fn delay(&self, retry: u32) -> Duration {
+        self.base_delay.saturating_mul(2u32.saturating_pow(retry))
+    }
+}
 
 /// Configuration for a Soroban environment.
 ///
 /// Contains the necessary parameters to connect to a Soroban RPC server
 /// and identify the target network.
+///
+/// With the `serde` feature, it can be loaded from configuration files such as
+/// `{ "rpc_url": "...", "network_passphrase": "..." }`, where `retry` is optional.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnvConfigs {
     /// URL of the Soroban RPC server
     pub rpc_url: String,
     /// Network passphrase that identifies the Stellar network
     pub network_passphrase: String,
+    /// Timeout and retry policy of the RPC requests
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub retry: RetryConfig,
+}
+
+impl EnvConfigs {
+    /// Creates a configuration for a custom RPC endpoint.
+    ///
+    /// # Parameters
+    ///
+    /// * `rpc_url` - URL of the Soroban RPC server
+    /// * `network_passphrase` - Network passphrase that identifies the Stellar network
+    pub fn new(rpc_url: impl Into<String>, network_passphrase: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            network_passphrase: network_passphrase.into(),
+            retry: RetryConfig::default(),
+        }
+    }
+
+    /// Sets the timeout and retry policy of the RPC requests.
+    ///
+    /// # Parameters
+    ///
+    /// * `retry` - The policy applied to `get_account`, `simulate_transaction`
+    ///   and `send_transaction`
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Configuration for the Stellar testnet, using the RPC server run by SDF.
+    pub fn testnet() -> Self {
+        Self::new(
+            "https://soroban-testnet.stellar.org",
+            "Test SDF Network ; September 2015",
+        )
+    }
+
+    /// Configuration for the Stellar mainnet.
+    ///
+    /// SDF does not run a public mainnet RPC server, so this uses a community
+    /// provider. Production deployments should use `new` with their own endpoint.
+    pub fn mainnet() -> Self {
+        Self::new(
+            "https://mainnet.sorobanrpc.com",
+            "Public Global Stellar Network ; September 2015",
+        )
+    }
+
+    /// Configuration for futurenet, the network used to preview upcoming protocol changes.
+    pub fn futurenet() -> Self {
+        Self::new(
+            "https://rpc-futurenet.stellar.org",
+            "Test SDF Future Network ; October 2022",
+        )
+    }
+
+    /// Configuration for a standalone network, e.g. the `stellar/quickstart`
+    /// container started with `--local`.
+    pub fn local() -> Self {
+        Self::new(
+            "http://localhost:8000/rpc",
+            "Standalone Network ; February 2017",
+        )
+    }
+
+    /// Checks that the configuration can be used to reach a network.
+    ///
+    /// An empty passphrase would silently produce a wrong network id, so it is
+    /// rejected along with RPC URLs that are not absolute HTTP(S) URLs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::InvalidArgument` if the passphrase is empty
+    /// or the RPC URL is malformed
+    pub fn validate(&self) -> Result<(), SorobanHelperError> {
+        if self.network_passphrase.trim().is_empty() {
+            return Err(SorobanHelperError::InvalidArgument(
+                "Network passphrase is empty".to_string(),
+            ));
+        }
+
+        let host = self
+            .rpc_url
+            .strip_prefix("https://")
+            .or_else(|| self.rpc_url.strip_prefix("http://"))
+            .and_then(|rest| rest.split(['/', '?', '#']).next());
+        match host {
+            Some(host) if !host.is_empty() && !self.rpc_url.contains(char::is_whitespace) => Ok(()),
+            _ => Err(SorobanHelperError::InvalidArgument(format!(
+                "Malformed RPC URL: {}",
+                self.rpc_url
+            ))),
+        }
+    }
 }
 
 /// The environment for Soroban operations.
@@ -60,6 +216,12 @@ pub struct Env {
     pub(crate) rpc_client: Arc<dyn RpcClient + Send + Sync>,
     /// Configuration for this environment
     pub(crate) configs: EnvConfigs,
+    /// Envelopes submitted through this environment, shared across clones
+    pub(crate) submission_guard: Arc<SubmissionGuard>,
+    /// Network ID computed from the network passphrase
+    network_id: Hash,
+    /// Protocol version of the network, fetched once and shared across clones
+    protocol_version: Arc<OnceLock<u32>>,
 }
 
 impl Env {
@@ -75,13 +237,83 @@ impl Env {
     ///
     /// # Errors
     ///
-    /// Returns `SorobanHelperError` if the RPC client initialization fails
+    /// Returns:
+    /// - `SorobanHelperError::InvalidArgument` if the configuration is invalid, see `EnvConfigs::validate`
+    /// - `SorobanHelperError::NetworkRequestFailed` if the RPC client initialization fails
     pub fn new(configs: EnvConfigs) -> Result<Self, SorobanHelperError> {
+        configs.validate()?;
         let client = ExternalRpcClient::new(&configs.rpc_url)?;
-        Ok(Self {
-            rpc_client: Arc::new(client),
+        Ok(Self::with_rpc_client(configs, Arc::new(client)))
+    }
+
+    /// Creates an environment for the Stellar testnet, see `EnvConfigs::testnet`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::NetworkRequestFailed` if the RPC client initialization fails
+    pub fn testnet() -> Result<Self, SorobanHelperError> {
+        Self::new(EnvConfigs::testnet())
+    }
+
+    /// Creates an environment for the Stellar mainnet, see `EnvConfigs::mainnet`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::NetworkRequestFailed` if the RPC client initialization fails
+    pub fn mainnet() -> Result<Self, SorobanHelperError> {
+        Self::new(EnvConfigs::mainnet())
+    }
+
+    /// Creates an environment for futurenet, see `EnvConfigs::futurenet`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::NetworkRequestFailed` if the RPC client initialization fails
+    pub fn futurenet() -> Result<Self, SorobanHelperError> {
+        Self::new(EnvConfigs::futurenet())
+    }
+
+    /// Creates an environment for a local standalone network, see `EnvConfigs::local`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::NetworkRequestFailed` if the RPC client initialization fails
+    pub fn local() -> Result<Self, SorobanHelperError> {
+        Self::new(EnvConfigs::local())
+    }
+
+    /// Creates a new environment backed by the given RPC client.
+    ///
+    /// # Parameters
+    ///
+    /// * `configs` - The environment configuration including RPC URL and network passphrase
+    /// * `rpc_client` - The client used to interact with the network
+    pub(crate) fn with_rpc_client(
+        configs: EnvConfigs,
+        rpc_client: Arc<dyn RpcClient + Send + Sync>,
+    ) -> Self {
+        let network_id = Hash(Sha256::digest(configs.network_passphrase.as_bytes()).into());
+        Self {
+            rpc_client,
             configs,
-        })
+            submission_guard: Arc::new(SubmissionGuard::new()),
+            network_id,
+            protocol_version: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Sets the protocol version of the network, so it isn't fetched from the RPC server.
+    ///
+    /// # Parameters
+    ///
+    /// * `protocol_version` - The protocol version of the network
+    ///
+    /// # Returns
+    ///
+    /// The updated environment
+    pub fn with_protocol_version(mut self, protocol_version: u32) -> Self {
+        self.protocol_version = Arc::new(OnceLock::from(protocol_version));
+        self
     }
 
     /// Returns the network passphrase for this environment.
@@ -97,17 +329,76 @@ impl Env {
         &self.configs.network_passphrase
     }
 
-    /// Calculates the network ID hash from the network passphrase.
+    /// Returns the network ID hash of this environment.
     ///
     /// The network ID is the SHA-256 hash of the network passphrase and is used
     /// in various cryptographic operations, including transaction signing.
+    /// It is computed once when the environment is created.
     ///
     /// # Returns
     ///
     /// The SHA-256 hash of the network passphrase
     pub fn network_id(&self) -> Hash {
-        let network_pass_bytes = self.configs.network_passphrase.as_bytes();
-        Hash(Sha256::digest(network_pass_bytes).into())
+        self.network_id.clone()
+    }
+
+    /// Returns the protocol version of the network.
+    ///
+    /// The transaction metadata format depends on the protocol version: V3 up
+    /// to protocol 22 and V4 from protocol 23, see `Parser::with_protocol_version`.
+    /// Unless set with `with_protocol_version`, it is fetched from the latest
+    /// ledger on first use and cached for the lifetime of the environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::NetworkRequestFailed` if the RPC request fails
+    pub async fn protocol_version(&self) -> Result<u32, SorobanHelperError> {
+        if let Some(protocol_version) = self.protocol_version.get() {
+            return Ok(*protocol_version);
+        }
+
+        let latest_ledger = self.rpc_client.get_latest_ledger().await.map_err(|e| {
+            SorobanHelperError::NetworkRequestFailed(format!(
+                "Failed to get protocol version: {}",
+                e
+            ))
+        })?;
+        Ok(*self
+            .protocol_version
+            .get_or_init(|| latest_ledger.protocol_version))
+    }
+
+    /// Retrieves the latest ledger known by the RPC server.
+    ///
+    /// # Returns
+    ///
+    /// The latest ledger sequence, its hash and the current protocol version
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::NetworkRequestFailed` if the RPC request fails
+    pub async fn get_latest_ledger(&self) -> Result<GetLatestLedgerResponse, SorobanHelperError> {
+        self.rpc_client.get_latest_ledger().await.map_err(|e| {
+            SorobanHelperError::NetworkRequestFailed(format!("Failed to get latest ledger: {}", e))
+        })
+    }
+
+    /// Retrieves the health status of the RPC server.
+    ///
+    /// A node reporting a status other than `"healthy"` may be lagging behind
+    /// the network and should not be relied upon for submissions.
+    ///
+    /// # Returns
+    ///
+    /// The node status along with the oldest and latest ledgers it retains
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::NetworkRequestFailed` if the RPC request fails
+    pub async fn get_health(&self) -> Result<GetHealthResponse, SorobanHelperError> {
+        self.rpc_client.get_health().await.map_err(|e| {
+            SorobanHelperError::NetworkRequestFailed(format!("Failed to get health: {}", e))
+        })
     }
 
     /// Retrieves account information from the network.
@@ -124,12 +415,14 @@ impl Env {
     ///
     /// Returns `SorobanHelperError::NetworkRequestFailed` if the RPC request fails
     pub async fn get_account(&self, account_id: &str) -> Result<AccountEntry, SorobanHelperError> {
-        self.rpc_client.get_account(account_id).await.map_err(|e| {
-            SorobanHelperError::NetworkRequestFailed(format!(
-                "Failed to get account {}: {}",
-                account_id, e
-            ))
-        })
+        self.with_retry(|| self.rpc_client.get_account(account_id))
+            .await
+            .map_err(|e| {
+                SorobanHelperError::NetworkRequestFailed(format!(
+                    "Failed to get account {}: {}",
+                    account_id, e
+                ))
+            })
     }
 
     /// Simulates a transaction without submitting it to the network.
@@ -152,8 +445,7 @@ impl Env {
         &self,
         tx_envelope: &TransactionEnvelope,
     ) -> Result<SimulateTransactionResponse, SorobanHelperError> {
-        self.rpc_client
-            .simulate_transaction_envelope(tx_envelope)
+        self.with_retry(|| self.rpc_client.simulate_transaction_envelope(tx_envelope))
             .await
             .map_err(|e| {
                 SorobanHelperError::NetworkRequestFailed(format!(
@@ -163,8 +455,138 @@ impl Env {
             })
     }
 
+    /// Simulates a contract function invocation and returns its result.
+    ///
+    /// Nothing is submitted to the network, which makes this the simplest way
+    /// to call a view function and read contract state.
+    ///
+    /// # Parameters
+    ///
+    /// * `contract_id` - The contract to invoke
+    /// * `source` - The account used as the source of the simulated transaction
+    /// * `function_name` - The name of the function to invoke
+    /// * `args` - The arguments to pass to the function
+    ///
+    /// # Returns
+    ///
+    /// The value returned by the function
+    ///
+    /// # Errors
+    ///
+    /// Returns:
+    /// - `SorobanHelperError::NetworkRequestFailed` if the RPC request fails
+    /// - `SorobanHelperError::TransactionSimulationFailed` if the simulation fails or returns no result
+    pub async fn simulate_invoke(
+        &self,
+        contract_id: &ContractId,
+        source: &Account,
+        function_name: &str,
+        args: Vec<ScVal>,
+    ) -> Result<ScVal, SorobanHelperError> {
+        let operation = Operations::invoke_contract(contract_id, function_name, args)?;
+        let tx = TransactionBuilder::new(source, self)
+            .add_operation(operation)
+            .build()
+            .await?;
+        let tx_envelope = source.sign_transaction_unsafe(&tx, &self.network_id())?;
+
+        self.simulate_invocation(&tx_envelope).await
+    }
+
+    /// Simulates a contract function invocation with an unsigned transaction.
+    ///
+    /// Simulation doesn't check signatures nor sequence numbers, so the source
+    /// account's sequence number is never fetched. Without a source account, a
+    /// placeholder account is used.
+    ///
+    /// # Parameters
+    ///
+    /// * `contract_id` - The contract to invoke
+    /// * `source` - The source account of the simulated transaction, if any
+    /// * `function_name` - The name of the function to invoke
+    /// * `args` - The arguments to pass to the function
+    ///
+    /// # Returns
+    ///
+    /// The value returned by the function
+    pub(crate) async fn simulate_invoke_unsigned(
+        &self,
+        contract_id: &ContractId,
+        source: Option<AccountId>,
+        function_name: &str,
+        args: Vec<ScVal>,
+    ) -> Result<ScVal, SorobanHelperError> {
+        let tx_envelope =
+            Self::unsigned_invocation_envelope(contract_id, source, function_name, args)?;
+        self.simulate_invocation(&tx_envelope).await
+    }
+
+    /// Builds an unsigned transaction holding a contract function invocation.
+    fn unsigned_invocation_envelope(
+        contract_id: &ContractId,
+        source: Option<AccountId>,
+        function_name: &str,
+        args: Vec<ScVal>,
+    ) -> Result<TransactionEnvelope, SorobanHelperError> {
+        let operation = Operations::invoke_contract(contract_id, function_name, args)?;
+        let tx = Transaction {
+            fee: DEFAULT_TRANSACTION_FEES,
+            seq_num: SequenceNumber(1),
+            source_account: source
+                .map(MuxedAccount::from)
+                .unwrap_or(MuxedAccount::Ed25519(Uint256([0; 32]))),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![operation].try_into().map_err(|e| {
+                SorobanHelperError::XdrEncodingFailed(format!(
+                    "Failed to convert operations: {}",
+                    e
+                ))
+            })?,
+            ext: TransactionExt::V0,
+        };
+        Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: VecM::default(),
+        }))
+    }
+
+    /// Simulates an invocation transaction and decodes the value it returns.
+    async fn simulate_invocation(
+        &self,
+        tx_envelope: &TransactionEnvelope,
+    ) -> Result<ScVal, SorobanHelperError> {
+        let simulation = self.simulate_transaction(tx_envelope).await?;
+
+        if let Some(error) = simulation.error {
+            return Err(SorobanHelperError::TransactionSimulationFailed(error));
+        }
+
+        simulation
+            .results()
+            .map_err(|e| {
+                SorobanHelperError::XdrEncodingFailed(format!(
+                    "Failed to decode simulation results: {}",
+                    e
+                ))
+            })?
+            .into_iter()
+            .next()
+            .map(|result| result.xdr)
+            .ok_or_else(|| {
+                SorobanHelperError::TransactionSimulationFailed(
+                    "Simulation returned no result".to_string(),
+                )
+            })
+    }
+
     /// Submits a transaction to the network and waits for the result.
     ///
+    /// An envelope that was already successfully submitted through this
+    /// environment is not submitted again: the cached result is returned instead.
+    /// Dropping the returned future before it completes releases the envelope,
+    /// so it can be submitted again.
+    ///
     /// # Parameters
     ///
     /// * `tx_envelope` - The signed transaction envelope to submit
@@ -177,42 +599,172 @@ impl Env {
     ///
     /// Returns:
     /// - `SorobanHelperError::ContractCodeAlreadyExists` if the transaction failed because the contract code already exists
-    /// - `SorobanHelperError::NetworkRequestFailed` for other transaction failures
+    /// - `SorobanHelperError::DuplicateSubmission` if the same envelope is already being submitted
+    /// - `SorobanHelperError::TransactionFailedWithCode` if the transaction was rejected or failed
+    /// - `SorobanHelperError::NetworkRequestFailed` or `SorobanHelperError::TransportFailed`
+    ///   if the RPC request fails
     pub async fn send_transaction(
         &self,
         tx_envelope: &TransactionEnvelope,
     ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let hash = SubmissionGuard::envelope_hash(tx_envelope)?;
+        let ticket = match self.submission_guard.begin(hash)? {
+            BeginSubmission::Submitted(response) => return Ok(*response),
+            BeginSubmission::Ready(ticket) => ticket,
+        };
+
+        let result = self.submit_transaction(tx_envelope).await;
+        ticket.finish(&result);
+        result
+    }
+
+    /// Runs an RPC request according to the configured `RetryConfig`.
+    ///
+    /// Each attempt is bounded by the configured timeout, if any, and attempts
+    /// failing with `SorobanHelperError::TransportFailed` are retried with an
+    /// exponential backoff. Other errors are returned immediately.
+    async fn with_retry<T, F, Fut>(&self, mut request: F) -> Result<T, SorobanHelperError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, SorobanHelperError>>,
+    {
+        let retry = self.configs.retry;
+        let mut attempt = 0;
+        loop {
+            let result = match retry.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, request())
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(SorobanHelperError::TransportFailed(format!(
+                            "Request timed out after {:?}",
+                            timeout
+                        )))
+                    }),
+                None => request().await,
+            };
+            match result {
+                Err(SorobanHelperError::TransportFailed(_)) if attempt < retry.max_retries => {
+                    tokio::time::sleep(retry.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends a transaction and polls its result.
+    ///
+    /// Sending is only retried if the request did not reach the server. Once the
+    /// transaction is sent, failed polling requests poll it again by hash
+    /// instead of sending it again.
+    async fn submit_transaction(
+        &self,
+        tx_envelope: &TransactionEnvelope,
+    ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
+        let result = match self
+            .with_retry(|| self.rpc_client.send_transaction(tx_envelope))
+            .await
+        {
+            Ok(hash) => {
+                self.with_retry(|| self.rpc_client.get_transaction_polling(&hash))
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        result.map_err(|e| {
+            // Check if this is a "contract code already exists" error
+            if e.to_string()
+                .contains(&SorobanHelperError::ContractCodeAlreadyExists.to_string())
+            {
+                return SorobanHelperError::ContractCodeAlreadyExists;
+            }
+            e
+        })
+    }
+
+    /// Retrieves ledger entries, such as contract storage, directly from the network.
+    ///
+    /// # Parameters
+    ///
+    /// * `keys` - The ledger keys to look up
+    ///
+    /// # Returns
+    ///
+    /// The entries found for the given keys. Keys without an entry are omitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::NetworkRequestFailed` if the RPC request fails
+    pub async fn get_ledger_entries(
+        &self,
+        keys: Vec<LedgerKey>,
+    ) -> Result<Vec<LedgerEntryResult>, SorobanHelperError> {
         self.rpc_client
-            .send_transaction_polling(tx_envelope)
+            .get_ledger_entries(&keys)
             .await
+            .map(|response| response.entries.unwrap_or_default())
             .map_err(|e| {
-                // Check if this is a "contract code already exists" error
-                let error_string = e.to_string();
-                if error_string.contains(&SorobanHelperError::ContractCodeAlreadyExists.to_string())
-                {
-                    return SorobanHelperError::ContractCodeAlreadyExists;
-                }
-                // Otherwise, it's a general transaction failure
                 SorobanHelperError::NetworkRequestFailed(format!(
-                    "Failed to send transaction: {}",
+                    "Failed to get ledger entries: {}",
                     e
                 ))
             })
     }
+
+    /// Retrieves contract events emitted from the given ledger onwards.
+    ///
+    /// # Parameters
+    ///
+    /// * `start_ledger` - The first ledger to include in the search
+    /// * `filters` - The contracts and topics to match
+    ///
+    /// # Returns
+    ///
+    /// The matching events, with their topics and values decoded
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::NetworkRequestFailed` if the RPC request fails,
+    /// or `SorobanHelperError::XdrEncodingFailed` if an event cannot be decoded
+    pub async fn get_events(
+        &self,
+        start_ledger: u32,
+        filters: EventFilter,
+    ) -> Result<Vec<EventInfo>, SorobanHelperError> {
+        let response = self
+            .rpc_client
+            .get_events(start_ledger, &filters)
+            .await
+            .map_err(|e| {
+                SorobanHelperError::NetworkRequestFailed(format!("Failed to get events: {}", e))
+            })?;
+        response.events.iter().map(EventInfo::try_from).collect()
+    }
 }
 
 #[cfg(test)]
 pub mod test {
-    use crate::mock::{mock_env, mock_signer3, mock_transaction_envelope};
+    use crate::contract_data_key;
+    use crate::mock::{
+        mock_account_entry, mock_contract_id, mock_env, mock_env_with_retry,
+        mock_env_with_rpc_client, mock_ledger_entries_response, mock_signer1, mock_signer3,
+        mock_simulate_tx_response, mock_transaction_envelope, MockRpcClient,
+    };
+    use stellar_rpc_client::{Event, GetEventsResponse, SimulateHostFunctionResultRaw};
+    use stellar_xdr::curr::{
+        ContractDataDurability, ContractDataEntry, ExtensionPoint, LedgerEntryData, Limits,
+        ReadXdr, ScSymbol, WriteXdr,
+    };
 
     use super::*;
 
     #[test]
     fn test_new() {
-        let env = Env::new(EnvConfigs {
-            rpc_url: "https://soroban-testnet.stellar.org".to_string(),
-            network_passphrase: "Test SDF Network ; September 2015".to_string(),
-        })
+        let env = Env::new(EnvConfigs::new(
+            "https://soroban-testnet.stellar.org",
+            "Test SDF Network ; September 2015",
+        ))
         .unwrap();
 
         assert_eq!(env.configs.rpc_url, "https://soroban-testnet.stellar.org");
@@ -222,14 +774,110 @@ pub mod test {
         );
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_network_id() {
-        let env = Env::new(EnvConfigs {
-            rpc_url: "https://test.com".to_string(),
-            network_passphrase: "test".to_string(),
-        })
+    fn test_env_configs_serde_round_trip() {
+        let configs = EnvConfigs::testnet().with_retry(RetryConfig {
+            max_retries: 3,
+            base_delay: Duration::from_millis(250),
+            timeout: Some(Duration::from_secs(10)),
+        });
+
+        let json = serde_json::to_string(&configs).unwrap();
+        let decoded: EnvConfigs = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(decoded.rpc_url, configs.rpc_url);
+        assert_eq!(decoded.network_passphrase, configs.network_passphrase);
+        assert_eq!(decoded.retry, configs.retry);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_env_configs_deserialize_defaults_retry() {
+        let configs: EnvConfigs = serde_json::from_str(
+            r#"{ "rpc_url": "http://localhost:8000/rpc", "network_passphrase": "Standalone Network ; February 2017" }"#,
+        )
         .unwrap();
 
+        assert_eq!(configs.rpc_url, "http://localhost:8000/rpc");
+        assert_eq!(
+            configs.network_passphrase,
+            "Standalone Network ; February 2017"
+        );
+        assert_eq!(configs.retry, RetryConfig::default());
+    }
+
+    #[test]
+    fn test_network_presets() {
+        let cases = [
+            (
+                EnvConfigs::testnet(),
+                "https://soroban-testnet.stellar.org",
+                "Test SDF Network ; September 2015",
+            ),
+            (
+                EnvConfigs::mainnet(),
+                "https://mainnet.sorobanrpc.com",
+                "Public Global Stellar Network ; September 2015",
+            ),
+            (
+                EnvConfigs::futurenet(),
+                "https://rpc-futurenet.stellar.org",
+                "Test SDF Future Network ; October 2022",
+            ),
+            (
+                EnvConfigs::local(),
+                "http://localhost:8000/rpc",
+                "Standalone Network ; February 2017",
+            ),
+        ];
+        for (configs, rpc_url, network_passphrase) in cases {
+            assert_eq!(configs.rpc_url, rpc_url);
+            assert_eq!(configs.network_passphrase, network_passphrase);
+            assert!(configs.validate().is_ok());
+        }
+
+        let env = Env::testnet().unwrap();
+        assert_eq!(env.configs.rpc_url, "https://soroban-testnet.stellar.org");
+        let env = Env::local().unwrap();
+        assert_eq!(
+            env.network_passphrase(),
+            "Standalone Network ; February 2017"
+        );
+        assert!(Env::mainnet().is_ok());
+        assert!(Env::futurenet().is_ok());
+    }
+
+    #[test]
+    fn test_new_empty_passphrase() {
+        let res = Env::new(EnvConfigs::new("https://soroban-testnet.stellar.org", ""));
+        assert!(matches!(res, Err(SorobanHelperError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_new_bad_url() {
+        for rpc_url in [
+            "",
+            "soroban-testnet.stellar.org",
+            "https://",
+            "http://bad host",
+        ] {
+            let res = Env::new(EnvConfigs::new(
+                rpc_url.to_string(),
+                "Test SDF Network ; September 2015",
+            ));
+            assert!(
+                matches!(res, Err(SorobanHelperError::InvalidArgument(_))),
+                "{} should be rejected",
+                rpc_url
+            );
+        }
+    }
+
+    #[test]
+    fn test_network_id() {
+        let env = Env::new(EnvConfigs::new("https://test.com", "test")).unwrap();
+
         assert_eq!(
             env.network_id().0,
             [
@@ -239,6 +887,214 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn test_network_id_is_stable() {
+        let env = Env::new(EnvConfigs::new(
+            "https://soroban-testnet.stellar.org",
+            "Test SDF Network ; September 2015",
+        ))
+        .unwrap();
+
+        let expected = Hash(Sha256::digest("Test SDF Network ; September 2015".as_bytes()).into());
+        assert_eq!(env.network_id(), expected);
+        assert_eq!(env.network_id(), env.network_id());
+        assert_eq!(env.clone().network_id(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_protocol_version() {
+        let rpc_client = Arc::new(MockRpcClient::new(None, None, None).with_protocol_version(23));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+
+        // fetched once, then shared across clones
+        assert_eq!(env.protocol_version().await.unwrap(), 23);
+        assert_eq!(env.clone().protocol_version().await.unwrap(), 23);
+        assert_eq!(rpc_client.get_latest_ledger_calls(), 1);
+
+        // a pinned protocol version is never fetched
+        let env = env.with_protocol_version(22);
+        assert_eq!(env.protocol_version().await.unwrap(), 22);
+        assert_eq!(rpc_client.get_latest_ledger_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_events() {
+        let contract_id = ContractId([1; 32]);
+        let event = |id: &str, value: ScVal| Event {
+            event_type: "contract".to_string(),
+            ledger: 10,
+            ledger_closed_at: "2025-01-01T00:00:00Z".to_string(),
+            id: id.to_string(),
+            contract_id: contract_id.to_string(),
+            topic: vec![ScVal::U32(1).to_xdr_base64(Limits::none()).unwrap()],
+            value: value.to_xdr_base64(Limits::none()).unwrap(),
+        };
+        let response = GetEventsResponse {
+            events: vec![event("1", ScVal::I32(5)), event("2", ScVal::Bool(false))],
+            latest_ledger: 11,
+            latest_ledger_close_time: String::new(),
+            oldest_ledger: 1,
+            oldest_ledger_close_time: String::new(),
+            cursor: "2".to_string(),
+        };
+        let rpc_client =
+            Arc::new(MockRpcClient::new(None, None, None).with_get_events_result(Ok(response)));
+        let env = mock_env_with_rpc_client(rpc_client);
+
+        let events = env
+            .get_events(1, EventFilter::new().contract(contract_id))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id, "1");
+        assert_eq!(events[0].contract_id, Some(contract_id));
+        assert_eq!(events[0].topics, vec![ScVal::U32(1)]);
+        assert_eq!(events[0].value, ScVal::I32(5));
+        assert_eq!(events[1].value, ScVal::Bool(false));
+
+        // request failure
+        let rpc_client = Arc::new(MockRpcClient::new(None, None, None).with_get_events_result(
+            Err(SorobanHelperError::NetworkRequestFailed("down".to_string())),
+        ));
+        let env = mock_env_with_rpc_client(rpc_client);
+        assert!(matches!(
+            env.get_events(1, EventFilter::new()).await,
+            Err(SorobanHelperError::NetworkRequestFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_ledger() {
+        let rpc_client = Arc::new(
+            MockRpcClient::new(None, None, None)
+                .with_protocol_version(23)
+                .with_latest_ledger(42),
+        );
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+
+        let latest_ledger = env.get_latest_ledger().await.unwrap();
+        assert_eq!(latest_ledger.sequence, 42);
+        assert_eq!(latest_ledger.protocol_version, 23);
+        assert_eq!(rpc_client.get_latest_ledger_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_health() {
+        let env = mock_env(None, None, None);
+        let health = env.get_health().await.unwrap();
+        assert_eq!(health.status, "healthy");
+
+        let rpc_client = Arc::new(MockRpcClient::new(None, None, None).with_get_health_result(
+            Err(SorobanHelperError::NetworkRequestFailed("down".to_string())),
+        ));
+        let env = mock_env_with_rpc_client(rpc_client);
+        assert!(matches!(
+            env.get_health().await,
+            Err(SorobanHelperError::NetworkRequestFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_ledger_entries() {
+        let contract_id = ContractId([3; 32]);
+        let key = contract_data_key(
+            &contract_id,
+            ScVal::Symbol(ScSymbol("COUNTER".try_into().unwrap())),
+            ContractDataDurability::Persistent,
+        );
+        let LedgerKey::ContractData(key_data) = key.clone() else {
+            panic!("Expected LedgerKey::ContractData");
+        };
+        let entry = LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: key_data.contract,
+            key: key_data.key,
+            durability: key_data.durability,
+            val: ScVal::U32(9),
+        });
+        let rpc_client = MockRpcClient::new(None, None, None).with_get_ledger_entries_result(Ok(
+            mock_ledger_entries_response(vec![(key.clone(), entry)]),
+        ));
+        let env = mock_env_with_rpc_client(rpc_client);
+
+        let entries = env.get_ledger_entries(vec![key]).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        let data = LedgerEntryData::from_xdr_base64(&entries[0].xdr, Limits::none()).unwrap();
+        let LedgerEntryData::ContractData(data) = data else {
+            panic!("Expected LedgerEntryData::ContractData");
+        };
+        assert_eq!(data.val, ScVal::U32(9));
+
+        // keys without an entry are omitted
+        let env = mock_env(None, None, None);
+        let entries = env
+            .get_ledger_entries(vec![contract_data_key(
+                &contract_id,
+                ScVal::U32(0),
+                ContractDataDurability::Temporary,
+            )])
+            .await
+            .unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let retry = RetryConfig {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            timeout: Some(Duration::from_secs(1)),
+        };
+        assert_eq!(retry.delay(0), Duration::from_millis(100));
+        assert_eq!(retry.delay(2), Duration::from_millis(400));
+        // the multiplier saturates instead of overflowing
+        assert_eq!(retry.delay(40), Duration::from_millis(100) * u32::MAX);
+        assert_eq!(RetryConfig::default().max_retries, 0);
+        assert_eq!(RetryConfig::default().timeout, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_account_retry() {
+        let network_error = || Err(SorobanHelperError::TransportFailed("down".to_string()));
+        let account = || {
+            Some(Ok(mock_account_entry(
+                &mock_signer1().account_id().to_string(),
+            )))
+        };
+        let retry = RetryConfig {
+            max_retries: 2,
+            base_delay: Duration::from_millis(1),
+            timeout: Some(Duration::from_secs(1)),
+        };
+
+        // the last attempt succeeds
+        let rpc_client = Arc::new(
+            MockRpcClient::new(account(), None, None)
+                .with_get_account_queue(vec![network_error(), network_error()]),
+        );
+        let env = mock_env_with_retry(rpc_client.clone(), retry);
+        assert!(env.get_account("G...").await.is_ok());
+        assert_eq!(rpc_client.get_account_calls(), 3);
+
+        // retries are disabled by default
+        let rpc_client = Arc::new(
+            MockRpcClient::new(account(), None, None).with_get_account_queue(vec![network_error()]),
+        );
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        assert!(env.get_account("G...").await.is_err());
+        assert_eq!(rpc_client.get_account_calls(), 1);
+
+        // other errors are not retried
+        let rpc_client = Arc::new(MockRpcClient::new(None, None, None).with_get_account_queue(
+            vec![Err(SorobanHelperError::NetworkRequestFailed(
+                "account not found".to_string(),
+            ))],
+        ));
+        let env = mock_env_with_retry(rpc_client.clone(), retry);
+        assert!(env.get_account("G...").await.is_err());
+        assert_eq!(rpc_client.get_account_calls(), 1);
+    }
+
     #[tokio::test]
     async fn test_code_already_exists_error() {
         let send_transaction_polling_result = Err(SorobanHelperError::ContractCodeAlreadyExists);
@@ -253,6 +1109,89 @@ pub mod test {
         ));
     }
 
+    #[tokio::test]
+    async fn test_send_transaction_twice() {
+        let rpc_client = Arc::new(MockRpcClient::new(None, None, None));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let envelope = mock_transaction_envelope(mock_signer3().account_id());
+
+        let first = env.send_transaction(&envelope).await.unwrap();
+        let second = env.send_transaction(&envelope).await.unwrap();
+
+        assert_eq!(rpc_client.send_transaction_calls(), 1);
+        assert_eq!(first.response.status, second.response.status);
+        assert!(env.submission_guard.is_submitted(&envelope));
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_after_cancellation() {
+        let rpc_client = Arc::new(
+            MockRpcClient::new(None, None, None).with_send_delay(Duration::from_millis(100)),
+        );
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let envelope = mock_transaction_envelope(mock_signer3().account_id());
+
+        let cancelled =
+            tokio::time::timeout(Duration::from_millis(10), env.send_transaction(&envelope)).await;
+        assert!(cancelled.is_err());
+        assert!(!env.submission_guard.is_submitted(&envelope));
+
+        env.send_transaction(&envelope).await.unwrap();
+        assert_eq!(rpc_client.send_transaction_calls(), 2);
+        assert!(env.submission_guard.is_submitted(&envelope));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_invoke() {
+        let simulation = SimulateTransactionResponse {
+            results: vec![SimulateHostFunctionResultRaw {
+                auth: vec![],
+                xdr: ScVal::U32(42).to_xdr_base64(Limits::none()).unwrap(),
+            }],
+            ..mock_simulate_tx_response(None)
+        };
+        let env = mock_env(None, Some(Ok(simulation)), None);
+        let account = Account::single(mock_signer1());
+        let contract_id = mock_contract_id(account.clone(), &env);
+
+        let val = env
+            .simulate_invoke(&contract_id, &account, "get", vec![])
+            .await
+            .unwrap();
+        assert_eq!(val, ScVal::U32(42));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_invoke_error() {
+        let simulation = SimulateTransactionResponse {
+            error: Some("HostError".to_string()),
+            ..mock_simulate_tx_response(None)
+        };
+        let env = mock_env(None, Some(Ok(simulation)), None);
+        let account = Account::single(mock_signer1());
+        let contract_id = mock_contract_id(account.clone(), &env);
+
+        let res = env
+            .simulate_invoke(&contract_id, &account, "get", vec![])
+            .await;
+        assert_eq!(
+            res,
+            Err(SorobanHelperError::TransactionSimulationFailed(
+                "HostError".to_string()
+            ))
+        );
+
+        // no result
+        let env = mock_env(None, Some(Ok(mock_simulate_tx_response(None))), None);
+        let res = env
+            .simulate_invoke(&contract_id, &account, "get", vec![])
+            .await;
+        assert!(matches!(
+            res,
+            Err(SorobanHelperError::TransactionSimulationFailed(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_send_transaction_error() {
         let send_transaction_polling_result = Err(SorobanHelperError::NetworkRequestFailed(
@@ -268,4 +1207,55 @@ pub mod test {
             Err(SorobanHelperError::NetworkRequestFailed(_))
         ));
     }
+
+    #[tokio::test]
+    async fn test_failed_transaction_is_not_resent() {
+        let retry = RetryConfig {
+            max_retries: 3,
+            base_delay: Duration::from_millis(1),
+            timeout: None,
+        };
+        let rpc_client = Arc::new(MockRpcClient::new(
+            None,
+            None,
+            Some(Err(SorobanHelperError::TransactionFailedWithCode {
+                code: "txFailed".to_string(),
+                detail: String::new(),
+            })),
+        ));
+        let env = mock_env_with_retry(rpc_client.clone(), retry);
+
+        let result = env
+            .send_transaction(&mock_transaction_envelope(mock_signer3().account_id()))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(SorobanHelperError::TransactionFailedWithCode { code, .. }) if code == "txFailed"
+        ));
+        assert_eq!(rpc_client.send_transaction_calls(), 1);
+        assert_eq!(rpc_client.get_transaction_polling_calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_polls_again_by_hash() {
+        let retry = RetryConfig {
+            max_retries: 3,
+            base_delay: Duration::from_millis(1),
+            timeout: None,
+        };
+        let rpc_client = Arc::new(
+            MockRpcClient::new(None, None, None).with_get_transaction_polling_queue(vec![Err(
+                SorobanHelperError::TransportFailed("connection reset".to_string()),
+            )]),
+        );
+        let env = mock_env_with_retry(rpc_client.clone(), retry);
+
+        env.send_transaction(&mock_transaction_envelope(mock_signer3().account_id()))
+            .await
+            .unwrap();
+
+        assert_eq!(rpc_client.send_transaction_calls(), 1);
+        assert_eq!(rpc_client.get_transaction_polling_calls(), 2);
+    }
 }
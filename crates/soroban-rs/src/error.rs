@@ -3,7 +3,10 @@
 //! This module defines the error types used throughout the Soroban helpers library.
 //! It provides a unified error handling approach for all operations related to
 //! Soroban contract deployment, invocation, and transaction management.
-use std::{error::Error, fmt};
+use std::{error::Error, fmt, sync::Arc};
+use stellar_xdr::curr::TransactionResultResult;
+
+use crate::SorobanTransactionResponse;
 
 /// Errors that can occur when using the Soroban helpers library.
 ///
@@ -14,6 +17,13 @@ pub enum SorobanHelperError {
     /// Error when a transaction fails to execute successfully.
     TransactionFailed(String),
 
+    /// Error when a transaction is rejected with a result code, e.g. `txFailed`
+    /// or `txInsufficientFee`, along with the operation-level details.
+    TransactionFailedWithCode {
+        code: String,
+        detail: String,
+    },
+
     /// Error when a transaction simulation fails.
     TransactionSimulationFailed(String),
 
@@ -23,13 +33,21 @@ pub enum SorobanHelperError {
     /// Error when a network request to the Soroban RPC server fails.
     NetworkRequestFailed(String),
 
+    /// Error when a request was not processed by the Soroban RPC server, e.g. the
+    /// connection failed or timed out. The same request can be safely sent again.
+    TransportFailed(String),
+
     /// Error when a signing operation fails.
     SigningFailed(String),
 
     /// Error when XDR encoding or decoding fails.
+    ///
+    /// Errors converted from `stellar_xdr::curr::Error` use `Xdr` instead.
     XdrEncodingFailed(String),
 
     /// Error when an invalid argument is provided to a function.
+    ///
+    /// Errors converted from `std::io::Error` use `Io` instead.
     InvalidArgument(String),
 
     /// Error when building a transaction fails.
@@ -49,43 +67,221 @@ pub enum SorobanHelperError {
 
     // Some client operations taht it's still not supported
     NotSupported(String),
+
+    /// Error when submitting a transaction envelope that is already being submitted.
+    DuplicateSubmission(String),
+
+    /// Error when submitting a transaction through a client without a source account.
+    SourceAccountNotSet,
+
+    /// Error when an IO operation fails, e.g. reading a WASM file.
+    ///
+    /// The underlying `std::io::Error` is returned by `Error::source`. This is a
+    /// breaking change: these errors were `InvalidArgument` before, with the same
+    /// `Display` output, so code matching on `InvalidArgument` must also match `Io`.
+    Io(ErrorSource),
+
+    /// Error when XDR encoding or decoding fails in the `stellar_xdr` crate.
+    ///
+    /// The underlying `stellar_xdr::curr::Error` is returned by `Error::source`. This
+    /// is a breaking change: these errors were `XdrEncodingFailed` before, so code
+    /// matching on `XdrEncodingFailed` must also match `Xdr`.
+    Xdr(ErrorSource),
+}
+
+/// The original error wrapped by a `SorobanHelperError`.
+///
+/// The error is shared so that `SorobanHelperError` stays cheap to clone, and two
+/// sources are equal when they are the same error or have the same message.
+#[derive(Debug, Clone)]
+pub struct ErrorSource(Arc<dyn Error + Send + Sync>);
+
+impl ErrorSource {
+    /// Wraps an error as the source of a `SorobanHelperError`.
+    ///
+    /// # Parameters
+    ///
+    /// * `err` - The original error
+    pub fn new(err: impl Error + Send + Sync + 'static) -> Self {
+        Self(Arc::new(err))
+    }
+
+    /// Returns the original error.
+    pub fn get(&self) -> &(dyn Error + Send + Sync + 'static) {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Display for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for ErrorSource {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0.to_string() == other.0.to_string()
+    }
 }
 
+impl Eq for ErrorSource {}
+
 impl fmt::Display for SorobanHelperError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::TransactionFailed(msg) => write!(f, "Transaction failed: {}", msg),
+            Self::TransactionFailedWithCode { code, detail } if detail.is_empty() => {
+                write!(f, "Transaction failed with {}", code)
+            }
+            Self::TransactionFailedWithCode { code, detail } => {
+                write!(f, "Transaction failed with {}: {}", code, detail)
+            }
             Self::TransactionSimulationFailed(msg) => {
                 write!(f, "Transaction simulation failed: {}", msg)
             }
             Self::ContractCodeAlreadyExists => write!(f, "Contract code already exists"),
             Self::NetworkRequestFailed(msg) => write!(f, "Network request failed: {}", msg),
+            Self::TransportFailed(msg) => write!(f, "Transport failed: {}", msg),
             Self::SigningFailed(msg) => write!(f, "Signing operation failed: {}", msg),
             Self::XdrEncodingFailed(msg) => write!(f, "XDR encoding failed: {}", msg),
             Self::InvalidArgument(msg) => write!(f, "Invalid argument: {}", msg),
             Self::TransactionBuildFailed(msg) => write!(f, "Transaction build failed: {}", msg),
             Self::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
-            Self::ContractDeployedConfigsNotSet => write!(f, "Contract deployed configs not set"),
+            Self::ContractDeployedConfigsNotSet => write!(
+                f,
+                "Contract deployed configs not set: deploy the contract with `Contract::deploy` \
+                 or create it with `ClientContractConfigs` for an already deployed instance"
+            ),
             Self::FileReadError(msg) => write!(f, "File read error: {}", msg),
             Self::ConversionError(msg) => write!(f, "Conversion error: {}", msg),
             Self::NotSupported(msg) => write!(f, "Not supported: {}", msg),
+            Self::DuplicateSubmission(hash) => {
+                write!(f, "Duplicate submission of transaction envelope {}", hash)
+            }
+            Self::SourceAccountNotSet => write!(f, "Source account not set"),
+            Self::Io(err) => write!(f, "Invalid argument: File operation failed: {}", err),
+            Self::Xdr(err) => write!(f, "XDR encoding failed: {}", err),
         }
     }
 }
 
-impl Error for SorobanHelperError {}
+impl Error for SorobanHelperError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) | Self::Xdr(err) => Some(err.get()),
+            _ => None,
+        }
+    }
+}
+
+impl SorobanHelperError {
+    /// Returns whether the error was caused by using a contract that has no
+    /// deployed instance configured.
+    pub fn is_not_deployed(&self) -> bool {
+        matches!(self, Self::ContractDeployedConfigsNotSet)
+    }
+
+    /// Returns whether the operation that failed with this error may succeed if retried.
+    ///
+    /// Network failures and failed simulations, e.g. caused by a ledger that was
+    /// still being closed, are transient. Every other error is permanent, so
+    /// retrying the same operation would fail again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::NetworkRequestFailed(_)
+            | Self::TransportFailed(_)
+            | Self::TransactionSimulationFailed(_) => true,
+            Self::TransactionFailed(_)
+            | Self::TransactionFailedWithCode { .. }
+            | Self::ContractCodeAlreadyExists
+            | Self::SigningFailed(_)
+            | Self::XdrEncodingFailed(_)
+            | Self::InvalidArgument(_)
+            | Self::TransactionBuildFailed(_)
+            | Self::Unauthorized(_)
+            | Self::ContractDeployedConfigsNotSet
+            | Self::FileReadError(_)
+            | Self::ConversionError(_)
+            | Self::NotSupported(_)
+            | Self::DuplicateSubmission(_)
+            | Self::SourceAccountNotSet
+            | Self::Io(_)
+            | Self::Xdr(_) => false,
+        }
+    }
+
+    /// Creates the error of a transaction rejected with the given result.
+    ///
+    /// The code is the name of the result, e.g. `txBadSeq`, and the detail lists
+    /// the operation results of a `txFailed` transaction.
+    pub(crate) fn from_transaction_result(result: &TransactionResultResult) -> Self {
+        let detail = match result {
+            TransactionResultResult::TxFailed(results) => format!("{:?}", results.as_slice()),
+            TransactionResultResult::TxFeeBumpInnerFailed(inner) => {
+                format!("inner transaction {:?}", inner.result.result)
+            }
+            _ => String::new(),
+        };
+
+        let name = result.name();
+        let mut chars = name.chars();
+        let code = match chars.next() {
+            Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+            None => String::new(),
+        };
+
+        Self::TransactionFailedWithCode { code, detail }
+    }
+}
+
+/// Error returned by `Contract::invoke_sequence` when one of the invocations fails.
+///
+/// Holds the responses of the invocations submitted before the failure, as
+/// their transactions were applied and can't be rolled back.
+#[derive(Debug, Clone)]
+pub struct InvokeSequenceError {
+    /// The responses of the successful invocations, in order.
+    pub responses: Vec<SorobanTransactionResponse>,
+    /// The error of the failing invocation.
+    pub error: SorobanHelperError,
+}
+
+impl fmt::Display for InvokeSequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invocation {} of the sequence failed: {}",
+            self.responses.len() + 1,
+            self.error
+        )
+    }
+}
+
+impl Error for InvokeSequenceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Convert the error of a sequence into the error of the failing invocation,
+/// dropping the responses of the successful ones
+impl From<InvokeSequenceError> for SorobanHelperError {
+    fn from(err: InvokeSequenceError) -> Self {
+        err.error
+    }
+}
 
 /// Convert XDR errors into SorobanHelperError
 impl From<stellar_xdr::curr::Error> for SorobanHelperError {
     fn from(err: stellar_xdr::curr::Error) -> Self {
-        Self::XdrEncodingFailed(err.to_string())
+        Self::Xdr(ErrorSource::new(err))
     }
 }
 
 /// Convert IO errors into SorobanHelperError
 impl From<std::io::Error> for SorobanHelperError {
     fn from(err: std::io::Error) -> Self {
-        Self::InvalidArgument(format!("File operation failed: {}", err))
+        Self::Io(ErrorSource::new(err))
     }
 }
 #[cfg(test)]
@@ -100,6 +296,20 @@ mod tests {
                 SorobanHelperError::TransactionFailed("timeout".to_string()),
                 "Transaction failed: timeout",
             ),
+            (
+                SorobanHelperError::TransactionFailedWithCode {
+                    code: "txFailed".to_string(),
+                    detail: "op failed".to_string(),
+                },
+                "Transaction failed with txFailed: op failed",
+            ),
+            (
+                SorobanHelperError::TransactionFailedWithCode {
+                    code: "txTooLate".to_string(),
+                    detail: String::new(),
+                },
+                "Transaction failed with txTooLate",
+            ),
             (
                 SorobanHelperError::ContractCodeAlreadyExists,
                 "Contract code already exists",
@@ -108,6 +318,10 @@ mod tests {
                 SorobanHelperError::NetworkRequestFailed("connection refused".to_string()),
                 "Network request failed: connection refused",
             ),
+            (
+                SorobanHelperError::TransportFailed("request timeout".to_string()),
+                "Transport failed: request timeout",
+            ),
             (
                 SorobanHelperError::SigningFailed("invalid key".to_string()),
                 "Signing operation failed: invalid key",
@@ -130,7 +344,8 @@ mod tests {
             ),
             (
                 SorobanHelperError::ContractDeployedConfigsNotSet,
-                "Contract deployed configs not set",
+                "Contract deployed configs not set: deploy the contract with `Contract::deploy` \
+                 or create it with `ClientContractConfigs` for an already deployed instance",
             ),
             (
                 SorobanHelperError::FileReadError("file not found".to_string()),
@@ -148,6 +363,22 @@ mod tests {
                 SorobanHelperError::NotSupported("feature not implemented".to_string()),
                 "Not supported: feature not implemented",
             ),
+            (
+                SorobanHelperError::DuplicateSubmission("abcd".to_string()),
+                "Duplicate submission of transaction envelope abcd",
+            ),
+            (
+                SorobanHelperError::SourceAccountNotSet,
+                "Source account not set",
+            ),
+            (
+                SorobanHelperError::from(IoError::new(ErrorKind::NotFound, "file not found")),
+                "Invalid argument: File operation failed: file not found",
+            ),
+            (
+                SorobanHelperError::from(stellar_xdr::curr::Error::Invalid),
+                "XDR encoding failed: xdr value invalid",
+            ),
         ];
 
         for (error, expected_msg) in cases {
@@ -161,13 +392,19 @@ mod tests {
         let helper_error = SorobanHelperError::from(io_error);
 
         assert!(
-            matches!(helper_error, SorobanHelperError::InvalidArgument(_)),
-            "Expected InvalidArgument variant"
+            matches!(helper_error, SorobanHelperError::Io(_)),
+            "Expected Io variant"
         );
 
         let error_string = helper_error.to_string();
         assert!(error_string.contains("file not found"));
         assert!(error_string.contains("File operation failed"));
+
+        let source = helper_error
+            .source()
+            .expect("Expected the IO error as source");
+        let io_error = source.downcast_ref::<IoError>().unwrap();
+        assert_eq!(io_error.kind(), ErrorKind::NotFound);
     }
 
     #[test]
@@ -177,12 +414,78 @@ mod tests {
         let helper_error = SorobanHelperError::from(xdr_error);
 
         assert!(
-            matches!(helper_error, SorobanHelperError::XdrEncodingFailed(_)),
-            "Expected XdrEncodingFailed variant"
+            matches!(helper_error, SorobanHelperError::Xdr(_)),
+            "Expected Xdr variant"
         );
 
         let error_string = helper_error.to_string();
         assert!(error_string.contains("XDR encoding failed"));
+        assert!(helper_error
+            .source()
+            .and_then(|source| source.downcast_ref::<stellar_xdr::curr::Error>())
+            .is_some());
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let msg = || "msg".to_string();
+        let cases = [
+            (SorobanHelperError::NetworkRequestFailed(msg()), true),
+            (SorobanHelperError::TransportFailed(msg()), true),
+            (SorobanHelperError::TransactionSimulationFailed(msg()), true),
+            (SorobanHelperError::TransactionFailed(msg()), false),
+            (
+                SorobanHelperError::TransactionFailedWithCode {
+                    code: "txFailed".to_string(),
+                    detail: msg(),
+                },
+                false,
+            ),
+            (SorobanHelperError::ContractCodeAlreadyExists, false),
+            (SorobanHelperError::SigningFailed(msg()), false),
+            (SorobanHelperError::XdrEncodingFailed(msg()), false),
+            (SorobanHelperError::InvalidArgument(msg()), false),
+            (SorobanHelperError::TransactionBuildFailed(msg()), false),
+            (SorobanHelperError::Unauthorized(msg()), false),
+            (SorobanHelperError::ContractDeployedConfigsNotSet, false),
+            (SorobanHelperError::FileReadError(msg()), false),
+            (SorobanHelperError::ConversionError(msg()), false),
+            (SorobanHelperError::NotSupported(msg()), false),
+            (SorobanHelperError::DuplicateSubmission(msg()), false),
+            (SorobanHelperError::SourceAccountNotSet, false),
+            (
+                SorobanHelperError::from(IoError::new(ErrorKind::NotFound, "missing")),
+                false,
+            ),
+            (
+                SorobanHelperError::from(stellar_xdr::curr::Error::Invalid),
+                false,
+            ),
+        ];
+
+        for (error, retryable) in cases {
+            assert_eq!(error.is_retryable(), retryable, "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn test_source_only_set_for_wrapped_errors() {
+        let error = SorobanHelperError::InvalidArgument("test error".to_string());
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn test_wrapped_error_equality() {
+        let error = SorobanHelperError::from(IoError::new(ErrorKind::NotFound, "missing"));
+        assert_eq!(error, error.clone());
+        assert_eq!(
+            error,
+            SorobanHelperError::from(IoError::new(ErrorKind::NotFound, "missing"))
+        );
+        assert_ne!(
+            error,
+            SorobanHelperError::from(IoError::new(ErrorKind::NotFound, "other"))
+        );
     }
 
     #[test]
@@ -218,4 +521,21 @@ mod tests {
 
         assert_eq!(original, cloned);
     }
+
+    #[test]
+    fn test_invoke_sequence_error() {
+        let error = InvokeSequenceError {
+            responses: vec![],
+            error: SorobanHelperError::TransactionFailed("rejected".to_string()),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Invocation 1 of the sequence failed: Transaction failed: rejected"
+        );
+        assert!(error.source().is_some());
+        assert_eq!(
+            SorobanHelperError::from(error),
+            SorobanHelperError::TransactionFailed("rejected".to_string())
+        );
+    }
 }
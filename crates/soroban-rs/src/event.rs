@@ -0,0 +1,240 @@
+//! # Soroban Contract Events
+//!
+//! This module provides a decoded representation of the events emitted by
+//! contracts, convenient to log or to store in an indexer.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use soroban_rs::SorobanTransactionResponse;
+//!
+//! fn example(response: &SorobanTransactionResponse) {
+//!     for event in response.get_decoded_events().unwrap() {
+//!         println!("{}", event.to_json());
+//!     }
+//! }
+//! ```
+//!
+//! Past events can also be queried from the network with `Env::get_events`,
+//! narrowed down by an [`EventFilter`].
+use crate::error::SorobanHelperError;
+use serde_json::{json, Value};
+use stellar_rpc_client::Event;
+use stellar_strkey::Contract as ContractId;
+use stellar_xdr::curr::{ContractEvent, ContractEventBody, Limits, ReadXdr, ScVal};
+
+/// A contract event with its topics and data extracted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedEvent {
+    /// The contract that emitted the event, if any
+    pub contract_id: Option<ContractId>,
+    /// The event topics
+    pub topics: Vec<ScVal>,
+    /// The event data
+    pub data: ScVal,
+}
+
+impl From<&ContractEvent> for DecodedEvent {
+    fn from(event: &ContractEvent) -> Self {
+        let ContractEventBody::V0(body) = &event.body;
+        Self {
+            contract_id: event.contract_id.as_ref().map(|id| ContractId(id.0 .0)),
+            topics: body.topics.to_vec(),
+            data: body.data.clone(),
+        }
+    }
+}
+
+impl DecodedEvent {
+    /// Converts the event into a JSON document.
+    ///
+    /// The document has the form `{ "contract_id": ..., "topics": [...], "data": ... }`,
+    /// where the contract ID is a strkey (`C...`) or `null`, and values use the
+    /// JSON representation of `ScVal` defined by `stellar-xdr`.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "contract_id": self.contract_id.map(|id| id.to_string()),
+            "topics": self.topics.iter().map(scval_to_json).collect::<Vec<_>>(),
+            "data": scval_to_json(&self.data),
+        })
+    }
+}
+
+/// Criteria used to select the events returned by `Env::get_events`.
+///
+/// An empty filter matches every contract event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// Contracts whose events are returned, all contracts if empty
+    pub contract_ids: Vec<ContractId>,
+    /// Topic filters in the RPC format: comma-separated base64 `ScVal`
+    /// segments, where `*` matches any single segment
+    pub topics: Vec<String>,
+    /// Maximum number of events returned, the server default if `None`
+    pub limit: Option<usize>,
+}
+
+impl EventFilter {
+    /// Creates a filter matching every contract event.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the events to those emitted by the given contract.
+    ///
+    /// Can be called several times to match any of multiple contracts.
+    pub fn contract(mut self, contract_id: ContractId) -> Self {
+        self.contract_ids.push(contract_id);
+        self
+    }
+
+    /// Adds a topic filter, e.g. `"AAAADwAAAAh0cmFuc2Zlcg==,*,*"`.
+    pub fn topic(mut self, topic: impl Into<String>) -> Self {
+        self.topics.push(topic.into());
+        self
+    }
+
+    /// Sets the maximum number of events returned.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+/// An event retrieved from the network with `Env::get_events`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventInfo {
+    /// Unique identifier of the event, usable as a pagination cursor
+    pub id: String,
+    /// The ledger in which the event was emitted
+    pub ledger: u32,
+    /// The close time of that ledger, as an ISO 8601 string
+    pub ledger_closed_at: String,
+    /// The contract that emitted the event, if any
+    pub contract_id: Option<ContractId>,
+    /// The event topics
+    pub topics: Vec<ScVal>,
+    /// The event data
+    pub value: ScVal,
+}
+
+impl TryFrom<&Event> for EventInfo {
+    type Error = SorobanHelperError;
+
+    fn try_from(event: &Event) -> Result<Self, Self::Error> {
+        let contract_id = if event.contract_id.is_empty() {
+            None
+        } else {
+            Some(ContractId::from_string(&event.contract_id).map_err(|e| {
+                SorobanHelperError::ConversionError(format!(
+                    "Invalid event contract id {}: {}",
+                    event.contract_id, e
+                ))
+            })?)
+        };
+        let topics = event
+            .topic
+            .iter()
+            .map(|topic| ScVal::from_xdr_base64(topic, Limits::none()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            id: event.id.clone(),
+            ledger: event.ledger,
+            ledger_closed_at: event.ledger_closed_at.clone(),
+            contract_id,
+            topics,
+            value: ScVal::from_xdr_base64(&event.value, Limits::none())?,
+        })
+    }
+}
+
+/// Converts an `ScVal` into its JSON representation.
+fn scval_to_json(val: &ScVal) -> Value {
+    serde_json::to_value(val).expect("Failed to convert ScVal to JSON")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use stellar_xdr::curr::{
+        ContractEventType, ContractEventV0, ExtensionPoint, Hash, ScSymbol, WriteXdr,
+    };
+
+    #[test]
+    fn test_to_json() {
+        let event = ContractEvent {
+            ext: ExtensionPoint::V0,
+            contract_id: Some(stellar_xdr::curr::ContractId(Hash([1; 32]))),
+            type_: ContractEventType::Contract,
+            body: ContractEventBody::V0(ContractEventV0 {
+                topics: vec![
+                    ScVal::Symbol(ScSymbol("transfer".try_into().unwrap())),
+                    ScVal::U32(7),
+                ]
+                .try_into()
+                .unwrap(),
+                data: ScVal::I64(-100),
+            }),
+        };
+
+        let decoded = DecodedEvent::from(&event);
+        assert_eq!(decoded.topics.len(), 2);
+
+        let json = decoded.to_json();
+        assert_eq!(
+            json["contract_id"],
+            Value::String(ContractId([1; 32]).to_string())
+        );
+        assert_eq!(json["topics"][0], json!({ "symbol": "transfer" }));
+        assert_eq!(json["topics"][1], json!({ "u32": 7 }));
+        // 64-bit integers are encoded as strings
+        assert_eq!(json["data"], json!({ "i64": "-100" }));
+    }
+
+    fn rpc_event(contract_id: String) -> Event {
+        Event {
+            event_type: "contract".to_string(),
+            ledger: 12,
+            ledger_closed_at: "2025-01-01T00:00:00Z".to_string(),
+            id: "0000000051539611648-0000000001".to_string(),
+            contract_id,
+            topic: vec![ScVal::U32(7).to_xdr_base64(Limits::none()).unwrap()],
+            value: ScVal::Bool(true).to_xdr_base64(Limits::none()).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_event_info_from_rpc_event() {
+        let contract_id = ContractId([1; 32]);
+        let info = EventInfo::try_from(&rpc_event(contract_id.to_string())).unwrap();
+
+        assert_eq!(info.ledger, 12);
+        assert_eq!(info.contract_id, Some(contract_id));
+        assert_eq!(info.topics, vec![ScVal::U32(7)]);
+        assert_eq!(info.value, ScVal::Bool(true));
+
+        let system = EventInfo::try_from(&rpc_event(String::new())).unwrap();
+        assert_eq!(system.contract_id, None);
+
+        let invalid = EventInfo::try_from(&rpc_event("not-a-contract".to_string()));
+        assert!(matches!(
+            invalid,
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_event_filter_builder() {
+        let filter = EventFilter::new()
+            .contract(ContractId([1; 32]))
+            .contract(ContractId([2; 32]))
+            .topic("*")
+            .limit(5);
+
+        assert_eq!(filter.contract_ids.len(), 2);
+        assert_eq!(filter.topics, vec!["*".to_string()]);
+        assert_eq!(filter.limit, Some(5));
+        assert_eq!(EventFilter::new(), EventFilter::default());
+    }
+}
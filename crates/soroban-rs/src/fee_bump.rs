@@ -0,0 +1,215 @@
+//! # Soroban Fee Bump Transactions
+//!
+//! This module provides functionality for wrapping a signed transaction in a fee
+//! bump transaction, so a transaction stuck because of a too low fee can be
+//! resubmitted with a higher fee paid by another account, without signing it again.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use soroban_rs::{Account, Env, FeeBumpBuilder};
+//! use stellar_xdr::curr::TransactionEnvelope;
+//!
+//! async fn example(env: &Env, fee_source: &Account, stuck_tx: &TransactionEnvelope) {
+//!     let fee_bump = FeeBumpBuilder::new(fee_source, 10_000)
+//!         .sign(stuck_tx, &env.network_id())
+//!         .unwrap();
+//!     env.send_transaction(&fee_bump).await.unwrap();
+//! }
+//! ```
+use stellar_xdr::curr::{
+    DecoratedSignature, FeeBumpTransaction, FeeBumpTransactionEnvelope, FeeBumpTransactionExt,
+    FeeBumpTransactionInnerTx, Hash, TransactionEnvelope,
+    TransactionSignaturePayloadTaggedTransaction, VecM,
+};
+
+use crate::{signer, Account, SorobanHelperError};
+
+/// Builder for fee bump transactions.
+///
+/// Wraps a signed transaction envelope in a fee bump transaction whose fee is
+/// paid by the fee source account.
+#[derive(Clone)]
+pub struct FeeBumpBuilder {
+    /// Account paying the fee
+    pub fee_source: Account,
+    /// Total fee in stroops paid for the fee bump and the inner transaction
+    pub fee: u32,
+}
+
+impl FeeBumpBuilder {
+    /// Creates a new fee bump builder.
+    ///
+    /// # Parameters
+    ///
+    /// * `fee_source` - The account paying the fee
+    /// * `fee` - The total fee in stroops, higher than the fee of the inner transaction
+    ///
+    /// # Returns
+    ///
+    /// A new FeeBumpBuilder instance
+    pub fn new(fee_source: &Account, fee: u32) -> Self {
+        Self {
+            fee_source: fee_source.clone(),
+            fee,
+        }
+    }
+
+    /// Builds a fee bump transaction wrapping the given envelope.
+    ///
+    /// # Parameters
+    ///
+    /// * `inner` - The signed transaction envelope to wrap
+    ///
+    /// # Returns
+    ///
+    /// The unsigned fee bump transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::InvalidArgument` if the inner envelope is not
+    /// a V1 transaction, or the fee is not higher than the inner transaction fee
+    pub fn build(
+        &self,
+        inner: &TransactionEnvelope,
+    ) -> Result<FeeBumpTransaction, SorobanHelperError> {
+        let inner_tx = match inner {
+            TransactionEnvelope::Tx(tx_v1) => tx_v1,
+            _ => {
+                return Err(SorobanHelperError::InvalidArgument(
+                    "Only V1 transaction envelopes can be fee bumped".to_string(),
+                ))
+            }
+        };
+
+        if self.fee <= inner_tx.tx.fee {
+            return Err(SorobanHelperError::InvalidArgument(format!(
+                "Fee bump fee {} must be higher than the inner transaction fee {}",
+                self.fee, inner_tx.tx.fee
+            )));
+        }
+
+        Ok(FeeBumpTransaction {
+            fee_source: self.fee_source.account_id().into(),
+            fee: self.fee.into(),
+            inner_tx: FeeBumpTransactionInnerTx::Tx(inner_tx.clone()),
+            ext: FeeBumpTransactionExt::V0,
+        })
+    }
+
+    /// Builds a fee bump transaction wrapping the given envelope and signs it
+    /// with the fee source signers.
+    ///
+    /// # Parameters
+    ///
+    /// * `inner` - The signed transaction envelope to wrap
+    /// * `network_id` - The network ID hash
+    ///
+    /// # Returns
+    ///
+    /// The signed fee bump transaction envelope, ready to be submitted
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fee bump transaction can't be built (see `build`)
+    /// or signed
+    pub fn sign(
+        &self,
+        inner: &TransactionEnvelope,
+        network_id: &Hash,
+    ) -> Result<TransactionEnvelope, SorobanHelperError> {
+        let tx = self.build(inner)?;
+
+        let signatures: Vec<DecoratedSignature> = self
+            .fee_source
+            .signers()
+            .iter()
+            .map(|signer| {
+                signer::sign_tagged_transaction(
+                    signer.as_ref(),
+                    TransactionSignaturePayloadTaggedTransaction::TxFeeBump(tx.clone()),
+                    network_id,
+                )
+            })
+            .collect::<Result<_, _>>()?;
+        let signatures: VecM<DecoratedSignature, 20> = signatures.try_into().map_err(|_| {
+            SorobanHelperError::XdrEncodingFailed("Failed to convert signatures to XDR".to_string())
+        })?;
+
+        Ok(TransactionEnvelope::TxFeeBump(FeeBumpTransactionEnvelope {
+            tx,
+            signatures,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{mock_signer1, mock_signer3, mock_transaction_envelope};
+    use sha2::{Digest, Sha256};
+    use stellar_xdr::curr::{
+        Limits, TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction,
+        TransactionV0Envelope, WriteXdr,
+    };
+
+    #[test]
+    fn test_fee_bump() {
+        let network_id = Hash([1; 32]);
+        let mut inner_account = Account::single(mock_signer1());
+        let inner = mock_transaction_envelope(mock_signer1().account_id());
+        let inner = inner_account
+            .sign_transaction_envelope(&inner, &network_id)
+            .unwrap();
+        let fee_source = Account::single(mock_signer3());
+
+        let envelope = FeeBumpBuilder::new(&fee_source, 1_000)
+            .sign(&inner, &network_id)
+            .unwrap();
+
+        let TransactionEnvelope::TxFeeBump(fee_bump) = envelope else {
+            panic!("Expected TransactionEnvelope::TxFeeBump");
+        };
+        assert_eq!(fee_bump.tx.fee, 1_000);
+        assert_eq!(fee_bump.tx.fee_source, mock_signer3().account_id().into());
+        let TransactionEnvelope::Tx(inner_tx) = inner else {
+            panic!("Expected TransactionEnvelope::Tx");
+        };
+        assert_eq!(
+            fee_bump.tx.inner_tx,
+            FeeBumpTransactionInnerTx::Tx(inner_tx)
+        );
+
+        // signed by the fee source over the fee bump payload
+        assert_eq!(fee_bump.signatures.len(), 1);
+        let payload = TransactionSignaturePayload {
+            network_id: network_id.clone(),
+            tagged_transaction: TransactionSignaturePayloadTaggedTransaction::TxFeeBump(
+                fee_bump.tx.clone(),
+            ),
+        };
+        let hash: [u8; 32] = Sha256::digest(payload.to_xdr(Limits::none()).unwrap()).into();
+        assert_eq!(
+            fee_bump.signatures[0].signature.0.to_vec(),
+            mock_signer3().sign_payload(&hash).unwrap().to_vec()
+        );
+    }
+
+    #[test]
+    fn test_fee_bump_invalid() {
+        let fee_source = Account::single(mock_signer3());
+        let inner = mock_transaction_envelope(mock_signer1().account_id());
+
+        // the fee must be higher than the inner transaction fee (100)
+        assert!(matches!(
+            FeeBumpBuilder::new(&fee_source, 100).build(&inner),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+
+        let v0 = TransactionEnvelope::TxV0(TransactionV0Envelope::default());
+        assert!(matches!(
+            FeeBumpBuilder::new(&fee_source, 1_000).build(&v0),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+    }
+}
@@ -14,9 +14,12 @@
 //!     account.add_guard(guard);
 //! }
 //! ```
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
 use stellar_strkey::Contract as ContractId;
 use stellar_xdr::curr::{
-    OperationBody, SorobanAuthorizedFunction, SorobanAuthorizedInvocation, Transaction,
+    Asset, HostFunction, InvokeHostFunctionOp, OperationBody, Preconditions, ScSymbol,
+    SorobanAuthorizedFunction, SorobanAuthorizedInvocation, TimeBounds, Transaction,
 };
 
 use crate::SorobanHelperError;
@@ -27,20 +30,158 @@ pub enum Guard {
     /// The u16 value represents the remaining number of calls allowed.
     NumberOfAllowedCalls(u16),
     AuthorizedCallsFor(AuthorizedCallsForContract),
+    /// Only allows signing within a time window, as UNIX timestamps in seconds (inclusive).
+    ///
+    /// Transactions with time bounds pass if their bounds are within the window.
+    /// Transactions without time bounds pass if the current time is within the window.
+    TimeWindow {
+        not_before: u64,
+        not_after: u64,
+    },
+    /// Only allows transactions invoking the listed contract functions.
+    ///
+    /// Only the functions invoked by the transaction's `InvokeHostFunction`
+    /// operations are checked, other operations are not restricted.
+    FunctionAllowList(Vec<String>),
+    /// Limits the number of operations in a transaction.
+    MaxOperations(u32),
+    /// Limits the total amount of an asset sent by the payment operations of the transactions.
+    ///
+    /// `remaining` is the remaining budget, in the smallest unit of `asset`.
+    /// Payments of other assets are not limited, combine several limits with
+    /// `Guard::All` to limit them too.
+    PaymentLimit {
+        asset: Asset,
+        remaining: i64,
+    },
+    /// Passes if all of the guards pass.
+    All(Vec<Guard>),
+    /// Passes if any of the guards passes. Only the guards that pass are updated.
+    Any(Vec<Guard>),
     // ... other variants
 }
 
+/// Identifies a guard when reporting which guard blocked a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardLabel {
+    /// A `Guard::NumberOfAllowedCalls` guard.
+    NumberOfAllowedCalls,
+    /// A `Guard::AuthorizedCallsFor` guard for the given contract.
+    AuthorizedCallsFor(ContractId),
+    /// A `Guard::TimeWindow` guard.
+    TimeWindow,
+    /// A `Guard::FunctionAllowList` guard.
+    FunctionAllowList,
+    /// A `Guard::MaxOperations` guard.
+    MaxOperations,
+    /// A `Guard::PaymentLimit` guard.
+    PaymentLimit,
+    /// A `Guard::All` guard.
+    All,
+    /// A `Guard::Any` guard.
+    Any,
+}
+
+impl fmt::Display for GuardLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuardLabel::NumberOfAllowedCalls => write!(f, "number of allowed calls"),
+            GuardLabel::AuthorizedCallsFor(contract_id) => {
+                write!(f, "authorized calls for contract {}", contract_id)
+            }
+            GuardLabel::TimeWindow => write!(f, "time window"),
+            GuardLabel::FunctionAllowList => write!(f, "function allow list"),
+            GuardLabel::MaxOperations => write!(f, "max operations"),
+            GuardLabel::PaymentLimit => write!(f, "payment limit"),
+            GuardLabel::All => write!(f, "all of"),
+            GuardLabel::Any => write!(f, "any of"),
+        }
+    }
+}
+
 impl Guard {
+    /// Returns the label identifying this guard.
+    pub fn label(&self) -> GuardLabel {
+        match self {
+            Guard::NumberOfAllowedCalls(_) => GuardLabel::NumberOfAllowedCalls,
+            Guard::AuthorizedCallsFor(calls_for_contract) => {
+                GuardLabel::AuthorizedCallsFor(calls_for_contract.contract_id)
+            }
+            Guard::TimeWindow { .. } => GuardLabel::TimeWindow,
+            Guard::FunctionAllowList(_) => GuardLabel::FunctionAllowList,
+            Guard::MaxOperations(_) => GuardLabel::MaxOperations,
+            Guard::PaymentLimit { .. } => GuardLabel::PaymentLimit,
+            Guard::All(_) => GuardLabel::All,
+            Guard::Any(_) => GuardLabel::Any,
+        }
+    }
+
     /// Checks if the guard condition is satisfied.
     ///
     /// # Returns
     /// * `true` if the operation is allowed to proceed
     /// * `false` if the operation should be blocked
     pub fn check(&self, transaction: &Transaction) -> Result<bool, SorobanHelperError> {
+        self.check_at(transaction, unix_now()?)
+    }
+
+    /// Checks if the guard condition is satisfied at the given time, e.g. the
+    /// close time of the latest ledger.
+    ///
+    /// # Parameters
+    ///
+    /// * `transaction` - The transaction about to be signed
+    /// * `now` - The current time, as a UNIX timestamp in seconds
+    ///
+    /// # Returns
+    /// * `true` if the operation is allowed to proceed
+    /// * `false` if the operation should be blocked
+    pub fn check_at(
+        &self,
+        transaction: &Transaction,
+        now: u64,
+    ) -> Result<bool, SorobanHelperError> {
         match self {
             Guard::NumberOfAllowedCalls(remaining) => Ok(*remaining > 0),
             Guard::AuthorizedCallsFor(calls_for_contract) => {
                 Ok(calls_for_contract.check(transaction))
+            }
+            Guard::TimeWindow {
+                not_before,
+                not_after,
+            } => match time_bounds(transaction) {
+                // a max time of 0 means the transaction never expires
+                Some(bounds) => Ok(bounds.min_time.0 >= *not_before
+                    && bounds.max_time.0 != 0
+                    && bounds.max_time.0 <= *not_after),
+                None => Ok(*not_before <= now && now <= *not_after),
+            },
+            Guard::FunctionAllowList(allowed) => {
+                Ok(invoked_functions(transaction).all(|function_name| {
+                    allowed
+                        .iter()
+                        .any(|name| name.as_bytes() == function_name.as_slice())
+                }))
+            }
+            Guard::MaxOperations(max) => Ok(transaction.operations.len() <= *max as usize),
+            Guard::PaymentLimit { asset, remaining } => {
+                Ok(payments_total(transaction, asset).is_some_and(|total| total <= *remaining))
+            }
+            Guard::All(guards) => {
+                for guard in guards {
+                    if !guard.check_at(transaction, now)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Guard::Any(guards) => {
+                for guard in guards {
+                    if guard.check_at(transaction, now)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
             } // handle other variants
         }
     }
@@ -49,7 +190,25 @@ impl Guard {
     ///
     /// This method should be called after a successful operation to update
     /// the internal state of the guard (e.g., decrement remaining allowed calls).
+    /// `Guard::Any` only updates the guards that pass `check`.
     pub fn update(&mut self, transaction: &Transaction) -> Result<(), SorobanHelperError> {
+        self.update_at(transaction, unix_now()?)
+    }
+
+    /// Updates the guard state after an operation has been performed at the
+    /// given time, e.g. the time the guard was checked at with `check_at`.
+    ///
+    /// `Guard::Any` only updates the guards that pass `check_at` at that time.
+    ///
+    /// # Parameters
+    ///
+    /// * `transaction` - The transaction that was signed
+    /// * `now` - The current time, as a UNIX timestamp in seconds
+    pub fn update_at(
+        &mut self,
+        transaction: &Transaction,
+        now: u64,
+    ) -> Result<(), SorobanHelperError> {
         match self {
             Guard::NumberOfAllowedCalls(remaining) => {
                 if *remaining > 0 {
@@ -60,11 +219,86 @@ impl Guard {
             Guard::AuthorizedCallsFor(calls_for_contract) => {
                 calls_for_contract.update(transaction);
                 Ok(())
+            }
+            Guard::TimeWindow { .. } | Guard::FunctionAllowList(_) | Guard::MaxOperations(_) => {
+                Ok(())
+            }
+            Guard::PaymentLimit { asset, remaining } => {
+                if let Some(total) = payments_total(transaction, asset) {
+                    if total <= *remaining {
+                        *remaining -= total;
+                    }
+                }
+                Ok(())
+            }
+            Guard::All(guards) => {
+                for guard in guards {
+                    guard.update_at(transaction, now)?;
+                }
+                Ok(())
+            }
+            Guard::Any(guards) => {
+                // guards are checked before any update, so that updating a guard
+                // can't change which of the others pass
+                let passed = guards
+                    .iter()
+                    .map(|guard| guard.check_at(transaction, now))
+                    .collect::<Result<Vec<_>, _>>()?;
+                for (guard, passed) in guards.iter_mut().zip(passed) {
+                    if passed {
+                        guard.update_at(transaction, now)?;
+                    }
+                }
+                Ok(())
             } // handle other variants
         }
     }
 }
 
+/// Returns the current time, as a UNIX timestamp in seconds.
+fn unix_now() -> Result<u64, SorobanHelperError> {
+    Ok(SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| SorobanHelperError::InvalidArgument(format!("Invalid system time: {}", e)))?
+        .as_secs())
+}
+
+/// Returns the time bounds of a transaction, if any.
+fn time_bounds(transaction: &Transaction) -> Option<&TimeBounds> {
+    match &transaction.cond {
+        Preconditions::Time(bounds) => Some(bounds),
+        Preconditions::V2(preconditions) => preconditions.time_bounds.as_ref(),
+        Preconditions::None => None,
+    }
+}
+
+/// Returns the total amount of `asset` sent by the payment operations of a
+/// transaction, or `None` if it overflows.
+fn payments_total(transaction: &Transaction, asset: &Asset) -> Option<i64> {
+    transaction
+        .operations
+        .iter()
+        .filter_map(|op| match &op.body {
+            OperationBody::Payment(payment) if payment.asset == *asset => Some(payment.amount),
+            _ => None,
+        })
+        .try_fold(0i64, |total, amount| total.checked_add(amount))
+}
+
+/// Returns the names of the contract functions invoked by a transaction.
+fn invoked_functions(transaction: &Transaction) -> impl Iterator<Item = &ScSymbol> {
+    transaction
+        .operations
+        .iter()
+        .filter_map(|op| match &op.body {
+            OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function: HostFunction::InvokeContract(args),
+                ..
+            }) => Some(&args.function_name),
+            _ => None,
+        })
+}
+
 #[derive(Clone)]
 pub struct AuthorizedCallsForContract {
     pub contract_id: ContractId,
@@ -116,14 +350,15 @@ mod tests {
     use ed25519_dalek::SigningKey;
     use stellar_strkey::{ed25519::PublicKey, Contract as ContractId};
     use stellar_xdr::curr::{
-        AccountId, Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Operation,
-        OperationBody, ScAddress, ScSymbol, SorobanAuthorizationEntry, SorobanAuthorizedFunction,
-        SorobanAuthorizedInvocation, SorobanCredentials, VecM,
+        AccountId, AlphaNum4, Asset, AssetCode4, Hash, HostFunction, InvokeContractArgs,
+        InvokeHostFunctionOp, Operation, OperationBody, PaymentOp, Preconditions, ScAddress,
+        ScSymbol, SorobanAuthorizationEntry, SorobanAuthorizedFunction,
+        SorobanAuthorizedInvocation, SorobanCredentials, TimeBounds, TimePoint, VecM,
     };
 
     use crate::{
         mock::{mock_contract_id, mock_env, mock_transaction},
-        Account, AuthorizedCallsForContract, Signer,
+        Account, AuthorizedCallsForContract, Guard, GuardLabel, Signer,
     };
 
     fn create_invocation(
@@ -240,4 +475,274 @@ mod tests {
         assert_eq!(guard.extract_contract_calls(&transaction), 2);
         assert!(!guard.check(&transaction));
     }
+
+    #[test]
+    fn test_time_window() {
+        let account_id = AccountId(stellar_xdr::curr::PublicKey::PublicKeyTypeEd25519(
+            [1; 32].into(),
+        ));
+        let mut guard = Guard::TimeWindow {
+            not_before: 1_000,
+            not_after: 2_000,
+        };
+        let bounded = |min_time: u64, max_time: u64| {
+            let mut transaction = mock_transaction(account_id.clone(), vec![]);
+            transaction.cond = Preconditions::Time(TimeBounds {
+                min_time: TimePoint(min_time),
+                max_time: TimePoint(max_time),
+            });
+            transaction
+        };
+
+        // time bounds within the window
+        assert!(guard.check_at(&bounded(1_000, 2_000), 0).unwrap());
+        assert!(guard.check_at(&bounded(1_500, 1_600), 0).unwrap());
+
+        // time bounds outside the window
+        assert!(!guard.check_at(&bounded(500, 1_500), 1_200).unwrap());
+        assert!(!guard.check_at(&bounded(1_500, 2_500), 1_200).unwrap());
+        assert!(!guard.check_at(&bounded(1_500, 0), 1_200).unwrap());
+
+        // without time bounds, the current time is checked
+        let unbounded = mock_transaction(account_id.clone(), vec![]);
+        assert!(guard.check_at(&unbounded, 1_500).unwrap());
+        assert!(!guard.check_at(&unbounded, 999).unwrap());
+        assert!(!guard.check_at(&unbounded, 2_001).unwrap());
+        assert!(!guard.check(&unbounded).unwrap());
+
+        guard.update(&unbounded).unwrap();
+        assert!(matches!(
+            guard,
+            Guard::TimeWindow {
+                not_before: 1_000,
+                not_after: 2_000
+            }
+        ));
+    }
+
+    #[test]
+    fn test_function_allow_list() {
+        let account_id = AccountId(stellar_xdr::curr::PublicKey::PublicKeyTypeEd25519(
+            [1; 32].into(),
+        ));
+        let invoke = |function_name: &str| Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                    contract_address: ScAddress::Contract(stellar_xdr::curr::ContractId(Hash(
+                        [2; 32],
+                    ))),
+                    function_name: ScSymbol(function_name.try_into().unwrap()),
+                    args: VecM::default(),
+                }),
+                auth: VecM::default(),
+            }),
+        };
+        let mut guard = Guard::FunctionAllowList(vec!["transfer".to_string(), "mint".to_string()]);
+
+        let allowed = mock_transaction(account_id.clone(), vec![invoke("transfer")]);
+        assert!(guard.check(&allowed).unwrap());
+
+        let disallowed = mock_transaction(account_id.clone(), vec![invoke("burn")]);
+        assert!(!guard.check(&disallowed).unwrap());
+
+        let mixed = mock_transaction(account_id.clone(), vec![invoke("mint"), invoke("burn")]);
+        assert!(!guard.check(&mixed).unwrap());
+
+        guard.update(&mixed).unwrap();
+        assert!(guard.check(&allowed).unwrap());
+        assert_eq!(guard.label(), GuardLabel::FunctionAllowList);
+    }
+
+    #[test]
+    fn test_max_operations() {
+        let account_id = AccountId(stellar_xdr::curr::PublicKey::PublicKeyTypeEd25519(
+            [1; 32].into(),
+        ));
+        let with_operations = |count: usize| {
+            let op = Operation {
+                source_account: None,
+                body: OperationBody::Inflation,
+            };
+            mock_transaction(account_id.clone(), vec![op; count])
+        };
+        let mut guard = Guard::MaxOperations(2);
+
+        assert!(guard.check(&with_operations(1)).unwrap());
+        assert!(guard.check(&with_operations(2)).unwrap());
+        assert!(!guard.check(&with_operations(3)).unwrap());
+
+        guard.update(&with_operations(2)).unwrap();
+        assert!(guard.check(&with_operations(2)).unwrap());
+        assert_eq!(guard.label(), GuardLabel::MaxOperations);
+    }
+
+    #[test]
+    fn test_composite_guards() {
+        let account_id = AccountId(stellar_xdr::curr::PublicKey::PublicKeyTypeEd25519(
+            [1; 32].into(),
+        ));
+        let transaction = mock_transaction(account_id, vec![]);
+
+        let all = Guard::All(vec![
+            Guard::NumberOfAllowedCalls(1),
+            Guard::MaxOperations(1),
+        ]);
+        assert!(all.check(&transaction).unwrap());
+        let all = Guard::All(vec![
+            Guard::NumberOfAllowedCalls(1),
+            Guard::NumberOfAllowedCalls(0),
+        ]);
+        assert!(!all.check(&transaction).unwrap());
+
+        let any = Guard::Any(vec![
+            Guard::NumberOfAllowedCalls(0),
+            Guard::NumberOfAllowedCalls(1),
+        ]);
+        assert!(any.check(&transaction).unwrap());
+        assert!(!Guard::Any(vec![Guard::NumberOfAllowedCalls(0)])
+            .check(&transaction)
+            .unwrap());
+        assert!(!Guard::Any(vec![]).check(&transaction).unwrap());
+        assert!(Guard::All(vec![]).check(&transaction).unwrap());
+
+        // all(any(calls 0, calls 2), calls 1): only the guards that passed are updated
+        let mut guard = Guard::All(vec![
+            Guard::Any(vec![
+                Guard::NumberOfAllowedCalls(0),
+                Guard::NumberOfAllowedCalls(2),
+            ]),
+            Guard::NumberOfAllowedCalls(1),
+        ]);
+        assert!(guard.check(&transaction).unwrap());
+        guard.update(&transaction).unwrap();
+        let Guard::All(guards) = &guard else {
+            panic!("Expected Guard::All");
+        };
+        let Guard::Any(any_guards) = &guards[0] else {
+            panic!("Expected Guard::Any");
+        };
+        assert!(matches!(any_guards[0], Guard::NumberOfAllowedCalls(0)));
+        assert!(matches!(any_guards[1], Guard::NumberOfAllowedCalls(1)));
+        assert!(matches!(guards[1], Guard::NumberOfAllowedCalls(0)));
+
+        // the outer calls are used up
+        assert!(!guard.check(&transaction).unwrap());
+        assert_eq!(guard.label(), GuardLabel::All);
+    }
+
+    #[test]
+    fn test_any_update_at() {
+        let account_id = AccountId(stellar_xdr::curr::PublicKey::PublicKeyTypeEd25519(
+            [1; 32].into(),
+        ));
+        let transaction = mock_transaction(account_id, vec![]);
+        let windowed_calls = || {
+            Guard::Any(vec![
+                Guard::All(vec![
+                    Guard::TimeWindow {
+                        not_before: 1_000,
+                        not_after: 2_000,
+                    },
+                    Guard::NumberOfAllowedCalls(2),
+                ]),
+                Guard::NumberOfAllowedCalls(5),
+            ])
+        };
+        let remaining_calls = |guard: &Guard| {
+            let Guard::Any(guards) = guard else {
+                panic!("Expected Guard::Any");
+            };
+            let Guard::All(windowed) = &guards[0] else {
+                panic!("Expected Guard::All");
+            };
+            match (&windowed[1], &guards[1]) {
+                (Guard::NumberOfAllowedCalls(a), Guard::NumberOfAllowedCalls(b)) => (*a, *b),
+                _ => panic!("Expected Guard::NumberOfAllowedCalls"),
+            }
+        };
+
+        // the children are checked at the given time, not the current one
+        let mut guard = windowed_calls();
+        assert!(guard.check_at(&transaction, 2_000).unwrap());
+        guard.update_at(&transaction, 2_000).unwrap();
+        assert_eq!(remaining_calls(&guard), (1, 4));
+
+        let mut guard = windowed_calls();
+        guard.update_at(&transaction, 2_001).unwrap();
+        assert_eq!(remaining_calls(&guard), (2, 4));
+    }
+
+    #[test]
+    fn test_payment_limit() {
+        let account_id = AccountId(stellar_xdr::curr::PublicKey::PublicKeyTypeEd25519(
+            [1; 32].into(),
+        ));
+        let usdc = Asset::CreditAlphanum4(AlphaNum4 {
+            asset_code: AssetCode4(*b"USDC"),
+            issuer: account_id.clone(),
+        });
+        let payments = |payments: &[(&Asset, i64)]| {
+            let ops = payments
+                .iter()
+                .map(|(asset, amount)| Operation {
+                    source_account: None,
+                    body: OperationBody::Payment(PaymentOp {
+                        destination: account_id.clone().into(),
+                        asset: (*asset).clone(),
+                        amount: *amount,
+                    }),
+                })
+                .collect();
+            mock_transaction(account_id.clone(), ops)
+        };
+        let native = &Asset::Native;
+        let mut guard = Guard::PaymentLimit {
+            asset: Asset::Native,
+            remaining: 100,
+        };
+
+        assert!(guard.check(&payments(&[(native, 100)])).unwrap());
+        assert!(!guard.check(&payments(&[(native, 101)])).unwrap());
+        assert!(guard
+            .check(&payments(&[(native, 40), (native, 60)]))
+            .unwrap());
+        assert!(!guard
+            .check(&payments(&[(native, 40), (native, 61)]))
+            .unwrap());
+        assert!(!guard
+            .check(&payments(&[(native, i64::MAX), (native, 1)]))
+            .unwrap());
+
+        // the budget is decremented by the amounts sent
+        guard
+            .update(&payments(&[(native, 30), (native, 30)]))
+            .unwrap();
+        assert!(matches!(guard, Guard::PaymentLimit { remaining: 40, .. }));
+        assert!(guard.check(&payments(&[(native, 40)])).unwrap());
+        assert!(!guard.check(&payments(&[(native, 41)])).unwrap());
+        assert_eq!(guard.label(), GuardLabel::PaymentLimit);
+
+        // payments of other assets don't count against the budget
+        let mixed = payments(&[(native, 40), (&usdc, 1_000)]);
+        assert!(guard.check(&mixed).unwrap());
+        guard.update(&mixed).unwrap();
+        assert!(matches!(guard, Guard::PaymentLimit { remaining: 0, .. }));
+
+        // limits on each asset combine with `Guard::All`
+        let guard = Guard::All(vec![
+            Guard::PaymentLimit {
+                asset: Asset::Native,
+                remaining: 100,
+            },
+            Guard::PaymentLimit {
+                asset: usdc.clone(),
+                remaining: 10,
+            },
+        ]);
+        assert!(guard
+            .check(&payments(&[(native, 100), (&usdc, 10)]))
+            .unwrap());
+        assert!(!guard.check(&payments(&[(native, 1), (&usdc, 11)])).unwrap());
+    }
 }
@@ -1,8 +1,12 @@
 mod account;
+pub mod amount;
+mod auth;
 mod contract;
 mod crypto;
 mod env;
 mod error;
+mod event;
+mod fee_bump;
 mod fs;
 mod guard;
 pub mod macros;
@@ -11,19 +15,35 @@ mod operation;
 mod parser;
 mod response;
 mod rpc;
-mod scval;
+pub mod scval;
 mod signer;
+mod storage;
+mod submission;
 mod transaction;
+pub mod xdr_utils;
 
-pub use account::{Account, AccountConfig, MultisigAccount, SingleAccount};
-pub use contract::{ClientContractConfigs, Contract};
-pub use env::{Env, EnvConfigs};
-pub use error::SorobanHelperError;
-pub use guard::{AuthorizedCallsForContract, Guard};
+pub use account::{
+    sign_with_accounts, Account, AccountConfig, AccountSequence, MultisigAccount, SingleAccount,
+};
+pub use auth::{
+    verify_authorization_entry, AuthorizationBuilder, NonceProvider, RandomNonceProvider,
+    SequentialNonceProvider,
+};
+pub use contract::{ClientContractConfigs, Contract, MAX_WASM_SIZE, UPGRADE_FUNCTION_NAME};
+pub use crypto::{contract_id_from_asset, keccak256, verify_signature};
+pub use env::{Env, EnvConfigs, RetryConfig};
+pub use error::{ErrorSource, InvokeSequenceError, SorobanHelperError};
+pub use event::{DecodedEvent, EventFilter, EventInfo};
+pub use fee_bump::FeeBumpBuilder;
+pub use guard::{AuthorizedCallsForContract, Guard, GuardLabel};
 pub use operation::Operations;
 pub use parser::{ParseResult, Parser, ParserType};
 pub use response::SorobanTransactionResponse;
-pub use signer::Signer;
+pub use signer::{verify_payload, Signer, TransactionSigner};
+pub use storage::{contract_data_key, ContractDataKey, ContractDataResult};
+pub use submission::{
+    BeginSubmission, SubmissionGuard, SubmissionTicket, DEFAULT_SUBMISSION_CACHE_SIZE,
+};
 pub use transaction::TransactionBuilder;
 
 // Re-export mock utilities for testing
@@ -34,8 +54,9 @@ pub use stellar_rpc_client::GetTransactionResponse;
 pub use stellar_strkey::Contract as ContractId;
 
 // re-exports
+pub use async_trait::async_trait;
 pub use stellar_rpc_client;
 pub use stellar_xdr::curr as xdr;
 
 // traits
-pub use scval::IntoScVal;
+pub use scval::{ContractError, FromScVal, IntoScVal, VecElement};
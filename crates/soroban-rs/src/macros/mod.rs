@@ -1,19 +1,36 @@
-pub use soroban_rs_macros::soroban;
+pub use soroban_rs_macros::{soroban, soroban_spec};
 
 #[cfg(test)]
 pub mod test {
-    use stellar_xdr::curr::{ScAddress, ScVal};
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use stellar_rpc_client::{SimulateHostFunctionResultRaw, SimulateTransactionResponse};
+    use stellar_xdr::curr::{
+        HostFunction, Limits, OperationBody, ScAddress, ScError, ScVal, TransactionEnvelope,
+        UInt128Parts, WriteXdr,
+    };
 
     use super::*;
     use crate::{
         self as soroban_rs,
         mock::{
-            account::mock_signer2, mock_account_entry, mock_contract_id, mock_env, mock_signer1,
-            mock_simulate_tx_response, mock_transaction_response,
+            account::mock_signer2, create_contract_id_val, mock_account_entry, mock_contract_id,
+            mock_env, mock_env_with_rpc_client, mock_signer1, mock_simulate_tx_response,
+            mock_transaction_response, mock_transaction_response_with_return_value, MockRpcClient,
         },
-        Account, ClientContractConfigs,
+        scval::SymbolVal,
+        Account, ClientContractConfigs, ContractError, FromScVal, IntoScVal, SorobanHelperError,
     };
 
+    fn mock_return_value_vec() -> Vec<String> {
+        vec!["alice".to_string(), "bob".to_string()]
+    }
+
+    fn mock_return_value() -> ScVal {
+        mock_return_value_vec().into_val()
+    }
+
     soroban!(
         r#"
         pub struct Token;
@@ -39,7 +56,9 @@ pub mod test {
         let signer_1_account_id = mock_signer1().account_id().0.to_string();
         let get_account_result = mock_account_entry(&signer_1_account_id);
 
-        let send_transaction_result = Ok(mock_transaction_response());
+        let send_transaction_result = Ok(mock_transaction_response_with_return_value(
+            mock_return_value(),
+        ));
 
         let env = mock_env(
             Some(Ok(get_account_result)),
@@ -50,21 +69,69 @@ pub mod test {
         let client_configs = ClientContractConfigs {
             contract_id: mock_contract_id(account.clone(), &env),
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         };
 
         // uses autogenerated TokenClient
         let mut token_contract = TokenClient::new(&client_configs);
 
-        let alice = ScVal::Address(ScAddress::Account(mock_signer1().account_id()));
-        let bob = ScVal::Address(ScAddress::Account(mock_signer2().account_id()));
+        let alice = ScAddress::Account(mock_signer1().account_id());
+        let bob = ScAddress::Account(mock_signer2().account_id());
 
         let res = token_contract.send(alice, bob).await;
-        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), mock_return_value_vec());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_with_constructor() {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let rpc_client = Arc::new(MockRpcClient::new(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response_with_return_value(
+                create_contract_id_val(),
+            ))),
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let mut account = Account::single(mock_signer1());
+
+        // the constructor parameters of Token are taken by deploy
+        let token_contract: TokenClient = TokenClient::deploy(
+            &env,
+            &mut account,
+            "fixtures/soroban-helpers-example.wasm",
+            42,
+        )
+        .await
+        .unwrap();
+
+        let ScVal::Address(ScAddress::Contract(contract_id)) = create_contract_id_val() else {
+            panic!("Expected a contract address");
+        };
         assert_eq!(
-            res.unwrap().response.result_meta,
-            mock_transaction_response().response.result_meta
+            token_contract.client_configs.contract_id.0,
+            contract_id.0 .0
         );
+
+        let constructor_args = rpc_client
+            .sent_envelopes()
+            .iter()
+            .find_map(|envelope| {
+                let TransactionEnvelope::Tx(tx_v1) = envelope else {
+                    return None;
+                };
+                match &tx_v1.tx.operations[0].body {
+                    OperationBody::InvokeHostFunction(op) => match &op.host_function {
+                        HostFunction::CreateContractV2(args) => {
+                            Some(args.constructor_args.to_vec())
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            })
+            .expect("Expected a create contract operation");
+        assert_eq!(constructor_args, vec![ScVal::U32(42)]);
     }
 
     // Genarates Token2Client
@@ -90,7 +157,9 @@ pub mod test {
         let signer_1_account_id = mock_signer1().account_id().0.to_string();
         let get_account_result = mock_account_entry(&signer_1_account_id);
 
-        let send_transaction_result = Ok(mock_transaction_response());
+        let send_transaction_result = Ok(mock_transaction_response_with_return_value(
+            mock_return_value(),
+        ));
 
         let env = mock_env(
             Some(Ok(get_account_result)),
@@ -101,18 +170,14 @@ pub mod test {
         let client_configs = ClientContractConfigs {
             contract_id: mock_contract_id(account.clone(), &env),
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         };
 
         // uses autogenerated TokenClient
         let mut token_contract = Token2Client::new(&client_configs);
 
-        let res: Result<_, crate::SorobanHelperError> = token_contract.transfer().await;
-        assert!(res.is_ok());
-        assert_eq!(
-            res.unwrap().response.result_meta,
-            mock_transaction_response().response.result_meta
-        );
+        let res: Result<Vec<String>, crate::SorobanHelperError> = token_contract.transfer().await;
+        assert_eq!(res.unwrap(), mock_return_value_vec());
     }
 
     // Genarates TokenMockClient
@@ -125,7 +190,9 @@ pub mod test {
         let signer_1_account_id = mock_signer1().account_id().0.to_string();
         let get_account_result = mock_account_entry(&signer_1_account_id);
 
-        let send_transaction_result = Ok(mock_transaction_response());
+        let send_transaction_result = Ok(mock_transaction_response_with_return_value(
+            mock_return_value(),
+        ));
 
         let env = mock_env(
             Some(Ok(get_account_result)),
@@ -136,20 +203,551 @@ pub mod test {
         let client_configs = ClientContractConfigs {
             contract_id: mock_contract_id(account.clone(), &env),
             env: env.clone(),
-            source_account: account.clone(),
+            source_account: Some(account.clone()),
         };
 
         // uses autogenerated TokenMockClient
         let mut token_mock_contract = TokenMockClient::new(&client_configs);
 
-        let alice = ScVal::Address(ScAddress::Account(mock_signer1().account_id()));
-        let bob = ScVal::Address(ScAddress::Account(mock_signer2().account_id()));
+        let alice = ScAddress::Account(mock_signer1().account_id());
+        let bob = ScAddress::Account(mock_signer2().account_id());
 
         let res = token_mock_contract.send(alice, bob).await;
+        assert_eq!(res.unwrap(), mock_return_value_vec());
+    }
+
+    #[tokio::test]
+    async fn test_inline_and_file_clients_match() {
+        let alice = ScAddress::Account(mock_signer1().account_id());
+        let bob = ScAddress::Account(mock_signer2().account_id());
+
+        // TokenClient is generated from inline code and TokenMockClient from a
+        // file holding the same contract, both through the same implementation
+        let (client_configs, inline_rpc_client) = token_client_configs();
+        TokenClient::new(&client_configs)
+            .send(alice.clone(), bob.clone())
+            .await
+            .unwrap();
+
+        let (client_configs, file_rpc_client) = token_client_configs();
+        TokenMockClient::new(&client_configs)
+            .send(alice, bob)
+            .await
+            .unwrap();
+
+        assert_eq!(sent_args(&inline_rpc_client), sent_args(&file_rpc_client));
+    }
+
+    fn token_client_configs() -> (ClientContractConfigs, Arc<MockRpcClient>) {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let rpc_client = Arc::new(MockRpcClient::new(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response_with_return_value(
+                mock_return_value(),
+            ))),
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let account = Account::single(mock_signer1());
+        let client_configs = ClientContractConfigs {
+            contract_id: mock_contract_id(account.clone(), &env),
+            env,
+            source_account: Some(account),
+        };
+        (client_configs, rpc_client)
+    }
+
+    // Genarates Token3Client
+    soroban!(
+        r#"
+        pub struct Token3;
+
+        impl Token3 {
+            pub fn transfer(env: &Env, from: Address, to: Address, amount: u128) -> bool {
+                true
+            }
+        }
+    "#
+    );
+
+    #[tokio::test]
+    async fn test_token_contract_typed_parameters() {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let rpc_client = Arc::new(MockRpcClient::new(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response_with_return_value(
+                ScVal::Bool(true),
+            ))),
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let account = Account::single(mock_signer1());
+        let client_configs = ClientContractConfigs {
+            contract_id: mock_contract_id(account.clone(), &env),
+            env: env.clone(),
+            source_account: Some(account.clone()),
+        };
+
+        let mut token_contract = Token3Client::new(&client_configs);
+
+        let from = ScAddress::Account(mock_signer1().account_id());
+        let to = ScAddress::Account(mock_signer2().account_id());
+        let amount: u128 = (1 << 64) + 5;
+
+        let res = token_contract
+            .transfer(from.clone(), to.clone(), amount)
+            .await;
+        assert!(res.unwrap());
+
+        let envelopes = rpc_client.sent_envelopes();
+        let TransactionEnvelope::Tx(tx_v1) = &envelopes[0] else {
+            panic!("Expected TransactionEnvelope::Tx");
+        };
+        let OperationBody::InvokeHostFunction(op) = &tx_v1.tx.operations[0].body else {
+            panic!("Expected OperationBody::InvokeHostFunction");
+        };
+        let HostFunction::InvokeContract(args) = &op.host_function else {
+            panic!("Expected HostFunction::InvokeContract");
+        };
+        assert_eq!(args.function_name.to_string(), "transfer");
+        assert_eq!(
+            args.args.to_vec(),
+            vec![
+                ScVal::Address(from),
+                ScVal::Address(to),
+                ScVal::U128(UInt128Parts { hi: 1, lo: 5 }),
+            ]
+        );
+    }
+
+    // Genarates CounterClient
+    soroban!(
+        r#"
+        pub struct Counter;
+
+        impl Counter {
+            pub fn increment(env: &Env, amount: u32) -> u32 {
+                amount
+            }
+
+            pub fn reset(env: &Env) {}
+        }
+    "#
+    );
+
+    #[tokio::test]
+    async fn test_decoded_return_value() {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let env = mock_env(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response_with_return_value(ScVal::U32(
+                6,
+            )))),
+        );
+        let account = Account::single(mock_signer1());
+        let client_configs = ClientContractConfigs {
+            contract_id: mock_contract_id(account.clone(), &env),
+            env: env.clone(),
+            source_account: Some(account.clone()),
+        };
+
+        let mut counter = CounterClient::new(&client_configs);
+
+        let res: Result<u32, SorobanHelperError> = counter.increment(5).await;
+        assert_eq!(res.unwrap(), 6);
+
+        // methods without a return value return the transaction response
+        let res: Result<crate::SorobanTransactionResponse, SorobanHelperError> =
+            counter.reset().await;
         assert!(res.is_ok());
+    }
+
+    /// A mock of the generated client, through the generated `CounterClientApi` trait
+    struct MockCounter {
+        count: u32,
+    }
+
+    #[crate::async_trait]
+    impl CounterClientApi for MockCounter {
+        async fn increment(&mut self, amount: u32) -> Result<u32, SorobanHelperError> {
+            self.count += amount;
+            Ok(self.count)
+        }
+
+        async fn reset(&mut self) -> Result<crate::SorobanTransactionResponse, SorobanHelperError> {
+            self.count = 0;
+            Ok(mock_transaction_response())
+        }
+    }
+
+    async fn increment_twice(
+        counter: &mut impl CounterClientApi,
+    ) -> Result<u32, SorobanHelperError> {
+        counter.increment(1).await?;
+        counter.increment(2).await
+    }
+
+    #[tokio::test]
+    async fn test_client_api_trait() {
+        let mut mock_counter = MockCounter { count: 0 };
+        assert_eq!(increment_twice(&mut mock_counter).await.unwrap(), 3);
+        assert!(mock_counter.reset().await.is_ok());
+        assert_eq!(mock_counter.count, 0);
+
+        // the generated client implements the trait as well
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let env = mock_env(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response_with_return_value(ScVal::U32(
+                6,
+            )))),
+        );
+        let account = Account::single(mock_signer1());
+        let client_configs = ClientContractConfigs {
+            contract_id: mock_contract_id(account.clone(), &env),
+            env: env.clone(),
+            source_account: Some(account),
+        };
+        let mut counter = CounterClient::new(&client_configs);
+        assert_eq!(increment_twice(&mut counter).await.unwrap(), 6);
+        assert!(CounterClientApi::reset(&mut counter).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_decoded_return_value_mismatch() {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let env = mock_env(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response_with_return_value(
+                ScVal::Bool(true),
+            ))),
+        );
+        let account = Account::single(mock_signer1());
+        let client_configs = ClientContractConfigs {
+            contract_id: mock_contract_id(account.clone(), &env),
+            env: env.clone(),
+            source_account: Some(account.clone()),
+        };
+
+        let mut counter = CounterClient::new(&client_configs);
+
+        assert!(matches!(
+            counter.increment(5).await,
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_client() {
+        let simulation = SimulateTransactionResponse {
+            results: vec![SimulateHostFunctionResultRaw {
+                auth: vec![],
+                xdr: ScVal::U32(42).to_xdr_base64(Limits::none()).unwrap(),
+            }],
+            ..mock_simulate_tx_response(None)
+        };
+        let env = mock_env(
+            Some(Err(SorobanHelperError::NetworkRequestFailed(
+                "account not available".to_string(),
+            ))),
+            Some(Ok(simulation)),
+            None,
+        );
+        let contract_id = mock_contract_id(Account::single(mock_signer1()), &env);
+
+        let mut token_contract = Token2Client::read_only(contract_id, env);
+
+        let res = token_contract.invoke_read_only("transfer", vec![]).await;
+        assert_eq!(res.unwrap(), ScVal::U32(42));
+
+        let res = token_contract.transfer().await;
+        assert!(matches!(res, Err(SorobanHelperError::SourceAccountNotSet)));
+    }
+
+    // Genarates BalanceClient
+    soroban!(
+        r#"
+        pub struct Balance;
+
+        impl Balance {
+            #[view]
+            pub fn balance(env: &Env, id: Address) -> u32 {
+                42
+            }
+
+            pub fn burn(env: &Env, id: Address, amount: u32) {}
+        }
+    "#
+    );
+
+    #[tokio::test]
+    async fn test_view_method() {
+        let simulation = SimulateTransactionResponse {
+            results: vec![SimulateHostFunctionResultRaw {
+                auth: vec![],
+                xdr: ScVal::U32(42).to_xdr_base64(Limits::none()).unwrap(),
+            }],
+            ..mock_simulate_tx_response(None)
+        };
+        let rpc_client = Arc::new(MockRpcClient::new(
+            Some(Err(SorobanHelperError::NetworkRequestFailed(
+                "account not available".to_string(),
+            ))),
+            Some(Ok(simulation)),
+            None,
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let contract_id = mock_contract_id(Account::single(mock_signer1()), &env);
+
+        let mut balance = BalanceClient::read_only(contract_id, env);
+        let id = ScAddress::Account(mock_signer1().account_id());
+
+        // view methods only simulate, so no source account nor submission is needed
+        let res: Result<u32, SorobanHelperError> = balance.balance(id.clone()).await;
+        assert_eq!(res.unwrap(), 42);
+        assert!(rpc_client.sent_envelopes().is_empty());
+
+        let res = balance.burn(id, 1).await;
+        assert!(matches!(res, Err(SorobanHelperError::SourceAccountNotSet)));
+    }
+
+    // Genarates LookupClient
+    soroban!(
+        r#"
+        pub struct Lookup;
+
+        impl Lookup {
+            pub fn find(env: &Env, key: Symbol) -> Option<u32> {
+                None
+            }
+
+            pub fn name(env: &Env) -> Symbol {
+                symbol_short!("lookup")
+            }
+
+            pub fn hash(env: &Env) -> Bytes {
+                Bytes::new(&env)
+            }
+
+            pub fn all(env: &Env) -> Map<Symbol, u32> {
+                Map::new(&env)
+            }
+
+            pub fn flag(env: &Env) -> u8 {
+                1
+            }
+        }
+    "#
+    );
+
+    fn lookup_client(return_value: ScVal) -> LookupClient {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let env = mock_env(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response_with_return_value(
+                return_value,
+            ))),
+        );
+        let account = Account::single(mock_signer1());
+        let client_configs = ClientContractConfigs {
+            contract_id: mock_contract_id(account.clone(), &env),
+            env: env.clone(),
+            source_account: Some(account),
+        };
+        LookupClient::new(&client_configs)
+    }
+
+    #[tokio::test]
+    async fn test_decoded_return_types() {
+        let key = SymbolVal("balance".to_string());
+
+        let res: Result<Option<u32>, SorobanHelperError> =
+            lookup_client(ScVal::U32(7)).find(key.clone()).await;
+        assert_eq!(res.unwrap(), Some(7));
+        let res = lookup_client(ScVal::Void).find(key.clone()).await;
+        assert_eq!(res.unwrap(), None);
+
+        let res: Result<SymbolVal, SorobanHelperError> =
+            lookup_client(key.clone().into_val()).name().await;
+        assert_eq!(res.unwrap(), key);
+
+        let res: Result<Vec<u8>, SorobanHelperError> =
+            lookup_client(vec![1u8, 2].into_val()).hash().await;
+        assert_eq!(res.unwrap(), vec![1, 2]);
+
+        let map = BTreeMap::from([(key, 3u32)]);
+        let res: Result<BTreeMap<SymbolVal, u32>, SorobanHelperError> =
+            lookup_client(map.clone().into_val()).all().await;
+        assert_eq!(res.unwrap(), map);
+
+        // `u8` has no `FromScVal` implementation, so it is returned as is
+        let res: Result<ScVal, SorobanHelperError> = lookup_client(ScVal::U32(1)).flag().await;
+        assert_eq!(res.unwrap(), ScVal::U32(1));
+    }
+
+    // Genarates StoreClient
+    soroban!(
+        r#"
+        pub struct Store;
+
+        impl Store {
+            pub fn store(env: &Env, values: Vec<u8>, hash: Bytes) {}
+        }
+    "#
+    );
+
+    #[tokio::test]
+    async fn test_vec_u8_param() {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let rpc_client = Arc::new(MockRpcClient::new(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response())),
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let account = Account::single(mock_signer1());
+        let client_configs = ClientContractConfigs {
+            contract_id: mock_contract_id(account.clone(), &env),
+            env: env.clone(),
+            source_account: Some(account),
+        };
+        let mut store = StoreClient::new(&client_configs);
+
+        // `Vec<u8>` is a vector of `U32`, only `Bytes` is a byte vector
+        let values = vec![ScVal::U32(1), ScVal::U32(2)];
+        store.store(values.clone(), vec![3, 4]).await.unwrap();
+
         assert_eq!(
-            res.unwrap().response.result_meta,
-            mock_transaction_response().response.result_meta
+            sent_args(&rpc_client),
+            vec![values.into_val(), vec![3u8, 4].into_val()]
         );
     }
+
+    // Genarates RegistryClient along with the contract types, from the contract spec
+    soroban_spec!(Registry, "fixtures/registry.wasm");
+
+    fn registry_client(return_value: ScVal) -> (RegistryClient, Arc<MockRpcClient>) {
+        let signer_1_account_id = mock_signer1().account_id().0.to_string();
+        let rpc_client = Arc::new(MockRpcClient::new(
+            Some(Ok(mock_account_entry(&signer_1_account_id))),
+            Some(Ok(mock_simulate_tx_response(None))),
+            Some(Ok(mock_transaction_response_with_return_value(
+                return_value,
+            ))),
+        ));
+        let env = mock_env_with_rpc_client(rpc_client.clone());
+        let account = Account::single(mock_signer1());
+        let client_configs = ClientContractConfigs {
+            contract_id: mock_contract_id(account.clone(), &env),
+            env: env.clone(),
+            source_account: Some(account),
+        };
+        (RegistryClient::new(&client_configs), rpc_client)
+    }
+
+    fn sent_args(rpc_client: &MockRpcClient) -> Vec<ScVal> {
+        let envelopes = rpc_client.sent_envelopes();
+        let TransactionEnvelope::Tx(tx_v1) = &envelopes[0] else {
+            panic!("Expected TransactionEnvelope::Tx");
+        };
+        let OperationBody::InvokeHostFunction(op) = &tx_v1.tx.operations[0].body else {
+            panic!("Expected OperationBody::InvokeHostFunction");
+        };
+        let HostFunction::InvokeContract(args) = &op.host_function else {
+            panic!("Expected HostFunction::InvokeContract");
+        };
+        args.args.to_vec()
+    }
+
+    #[test]
+    fn test_spec_types() {
+        let alice = ScAddress::Account(mock_signer1().account_id());
+        let bob = ScAddress::Account(mock_signer2().account_id());
+
+        let allowance = Allowance {
+            amount: 100,
+            expiration_ledger: 50,
+        };
+        let val = allowance.try_into_val().unwrap();
+        assert_eq!(
+            crate::scval::decode_struct_fields(&val, &["amount", "expiration_ledger"]).unwrap(),
+            vec![100i128.into_val(), ScVal::U32(50)]
+        );
+        assert_eq!(Allowance::try_from_val(&val).unwrap(), allowance);
+
+        let key = DataKey::Allowance(alice.clone(), bob.clone());
+        let val = key.try_into_val().unwrap();
+        assert_eq!(
+            val,
+            crate::scval::data_variant(
+                "Allowance",
+                vec![ScVal::Address(alice), ScVal::Address(bob)]
+            )
+            .unwrap()
+        );
+        assert_eq!(DataKey::try_from_val(&val).unwrap(), key);
+        assert_eq!(
+            DataKey::try_from_val(&crate::scval::unit_variant("Admin").unwrap()).unwrap(),
+            DataKey::Admin
+        );
+        assert!(matches!(
+            DataKey::try_from_val(&crate::scval::unit_variant("Owner").unwrap()),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+
+        assert_eq!(Level::High.into_val(), ScVal::U32(2));
+        assert_eq!(Level::try_from_val(&ScVal::U32(1)).unwrap(), Level::Low);
+        assert!(matches!(
+            Level::try_from_val(&ScVal::U32(3)),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_spec_client() {
+        let alice = ScAddress::Account(mock_signer1().account_id());
+        let bob = ScAddress::Account(mock_signer2().account_id());
+        let allowance = Allowance {
+            amount: 100,
+            expiration_ledger: 50,
+        };
+
+        let (mut registry, rpc_client) = registry_client(allowance.clone().into_val());
+        let res: Result<Allowance, SorobanHelperError> =
+            registry.allowance(alice.clone(), bob.clone()).await;
+        assert_eq!(res.unwrap(), allowance);
+        assert_eq!(
+            sent_args(&rpc_client),
+            vec![ScVal::Address(alice.clone()), ScVal::Address(bob.clone())]
+        );
+
+        let (mut registry, rpc_client) = registry_client(ScVal::U32(2));
+        let res: Result<Level, SorobanHelperError> = registry
+            .set_level(DataKey::Balance(alice.clone()), Level::High)
+            .await;
+        assert_eq!(res.unwrap(), Level::High);
+        assert_eq!(
+            sent_args(&rpc_client),
+            vec![DataKey::Balance(alice.clone()).into_val(), ScVal::U32(2)]
+        );
+
+        // `Result<Option<Address>, Error>` is decoded along with the contract error
+        let (mut registry, _) = registry_client(ScVal::Void);
+        let res: Result<Result<Option<ScAddress>, ContractError>, SorobanHelperError> =
+            registry.admin().await;
+        assert_eq!(res.unwrap(), Ok(None));
+
+        let (mut registry, _) = registry_client(ScVal::Error(ScError::Contract(1)));
+        let res = registry.admin().await;
+        assert_eq!(res.unwrap(), Err(ContractError::Contract(1)));
+
+        let (mut registry, _) = registry_client(ScVal::Void);
+        let res: Result<crate::SorobanTransactionResponse, SorobanHelperError> =
+            registry.approve(alice, bob, allowance).await;
+        assert!(res.is_ok());
+    }
 }
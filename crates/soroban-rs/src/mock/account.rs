@@ -1,5 +1,5 @@
 use crate::error::SorobanHelperError;
-use crate::{crypto, Account, Env, EnvConfigs};
+use crate::{crypto, Account, Env, EnvConfigs, RetryConfig};
 use crate::{Signer, SorobanTransactionResponse};
 use ed25519_dalek::SigningKey;
 use std::default::Default;
@@ -30,20 +30,32 @@ pub fn mock_env(
     >,
     send_transaction_polling_result: Option<Result<SorobanTransactionResponse, SorobanHelperError>>,
 ) -> Env {
+    mock_env_with_rpc_client(MockRpcClient::new(
+        get_account_result,
+        simulate_transaction_envelope_result,
+        send_transaction_polling_result,
+    ))
+}
+
+/// Creates a mock environment backed by a preconfigured mock RPC client
+///
+/// Pass an `Arc` clone of the client to inspect its calls afterwards.
+#[allow(dead_code)]
+pub fn mock_env_with_rpc_client(rpc_client: impl Into<Arc<MockRpcClient>>) -> Env {
+    mock_env_with_retry(rpc_client, RetryConfig::default())
+}
+
+/// Creates a mock environment backed by a preconfigured mock RPC client,
+/// with the given retry policy
+#[allow(dead_code)]
+pub fn mock_env_with_retry(rpc_client: impl Into<Arc<MockRpcClient>>, retry: RetryConfig) -> Env {
     let random_id = rand::random::<u64>();
     let network_passphrase = format!("Mock Test Random Network {}", random_id);
 
-    Env {
-        configs: EnvConfigs {
-            rpc_url: "http://test.com".to_string(),
-            network_passphrase,
-        },
-        rpc_client: Arc::new(MockRpcClient::new(
-            get_account_result,
-            simulate_transaction_envelope_result,
-            send_transaction_polling_result,
-        )),
-    }
+    Env::with_rpc_client(
+        EnvConfigs::new("http://test.com", network_passphrase).with_retry(retry),
+        rpc_client.into(),
+    )
 }
 
 /// Returns a collection of mock signers for testing
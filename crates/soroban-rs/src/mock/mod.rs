@@ -1,14 +1,16 @@
 pub mod account;
 pub mod fs;
 pub mod rpc;
+pub mod signer;
 pub mod transaction;
 
 // Re-export transaction mock functions
 #[allow(unused_imports)]
 pub use transaction::{
     create_contract_id_val, create_mock_contract_event, create_mock_set_options_tx_envelope,
-    mock_simulate_tx_response, mock_transaction, mock_transaction_envelope,
-    mock_transaction_response, mock_transaction_response_with_account_entry,
+    mock_simulate_tx_response, mock_simulate_tx_response_with_auth, mock_transaction,
+    mock_transaction_envelope, mock_transaction_response,
+    mock_transaction_response_with_account_entry, mock_transaction_response_with_created_contract,
     mock_transaction_response_with_return_value, MockGetTransactionResponse, MockTransactionMeta,
     MockTransactionResult,
 };
@@ -16,6 +18,14 @@ pub use transaction::{
 // Re-export account mock functions
 #[allow(unused_imports)]
 pub use account::{
-    all_signers, mock_account_entry, mock_contract_id, mock_env, mock_signer1, mock_signer2,
-    mock_signer3,
+    all_signers, mock_account_entry, mock_contract_id, mock_env, mock_env_with_retry,
+    mock_env_with_rpc_client, mock_signer1, mock_signer2, mock_signer3,
 };
+
+// Re-export rpc mock client
+#[allow(unused_imports)]
+pub use rpc::{mock_ledger_entries_response, MockRpcClient};
+
+// Re-export transaction signer mock
+#[allow(unused_imports)]
+pub use signer::MockTransactionSigner;
@@ -1,19 +1,55 @@
+use crate::event::EventFilter;
 use crate::rpc::RpcClient;
 use crate::{error::SorobanHelperError, SorobanTransactionResponse};
 use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
 use std::default::Default;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::RwLock;
-use stellar_rpc_client::SimulateTransactionResponse;
-use stellar_xdr::curr::{AccountEntry, TransactionEnvelope};
+use std::time::Duration;
+use stellar_rpc_client::{
+    GetEventsResponse, GetHealthResponse, GetLatestLedgerResponse, GetLedgerEntriesResponse,
+    LedgerEntryResult, SimulateTransactionResponse,
+};
+use stellar_xdr::curr::{
+    AccountEntry, Hash, LedgerEntryData, LedgerKey, Limits, TransactionEnvelope, WriteXdr,
+};
 
 use super::{mock_account_entry, mock_transaction_response};
 
 pub struct MockRpcClient {
     get_account_result: RwLock<Option<Result<AccountEntry, SorobanHelperError>>>,
+    // Results returned (in order) before falling back to `get_account_result`
+    get_account_queue: RwLock<VecDeque<Result<AccountEntry, SorobanHelperError>>>,
     simulate_transaction_envelope_result:
         RwLock<Option<Result<SimulateTransactionResponse, SorobanHelperError>>>,
+    // Results returned (in order) before falling back to `simulate_transaction_envelope_result`
+    simulate_transaction_envelope_queue:
+        RwLock<VecDeque<Result<SimulateTransactionResponse, SorobanHelperError>>>,
     send_transaction_polling_result:
         RwLock<Option<Result<SorobanTransactionResponse, SorobanHelperError>>>,
+    // Results returned (in order) before falling back to `send_transaction_polling_result`
+    send_transaction_polling_queue:
+        RwLock<VecDeque<Result<SorobanTransactionResponse, SorobanHelperError>>>,
+    // Results of the sent transactions, returned by `get_transaction_polling`
+    pending_transactions: RwLock<HashMap<Hash, SorobanTransactionResponse>>,
+    // Results returned (in order) by `get_transaction_polling` before the sent ones
+    get_transaction_polling_queue:
+        RwLock<VecDeque<Result<SorobanTransactionResponse, SorobanHelperError>>>,
+    get_transaction_polling_calls: AtomicUsize,
+    get_ledger_entries_result: RwLock<Option<Result<GetLedgerEntriesResponse, SorobanHelperError>>>,
+    send_transaction_calls: AtomicUsize,
+    get_account_calls: AtomicUsize,
+    // Envelopes passed to `send_transaction`, in order
+    sent_envelopes: RwLock<Vec<TransactionEnvelope>>,
+    protocol_version: RwLock<u32>,
+    get_latest_ledger_calls: AtomicUsize,
+    get_events_result: RwLock<Option<Result<GetEventsResponse, SorobanHelperError>>>,
+    latest_ledger: RwLock<u32>,
+    get_health_result: RwLock<Option<Result<GetHealthResponse, SorobanHelperError>>>,
+    // Delay applied to every `send_transaction` call
+    send_delay: RwLock<Option<Duration>>,
 }
 impl MockRpcClient {
     pub fn new(
@@ -27,15 +63,184 @@ impl MockRpcClient {
     ) -> Self {
         Self {
             get_account_result: RwLock::new(get_account_result),
+            get_account_queue: RwLock::new(VecDeque::new()),
             simulate_transaction_envelope_result: RwLock::new(simulate_transaction_envelope_result),
+            simulate_transaction_envelope_queue: RwLock::new(VecDeque::new()),
             send_transaction_polling_result: RwLock::new(send_transaction_polling_result),
+            send_transaction_polling_queue: RwLock::new(VecDeque::new()),
+            pending_transactions: RwLock::new(HashMap::new()),
+            get_transaction_polling_queue: RwLock::new(VecDeque::new()),
+            get_transaction_polling_calls: AtomicUsize::new(0),
+            get_ledger_entries_result: RwLock::new(None),
+            send_transaction_calls: AtomicUsize::new(0),
+            get_account_calls: AtomicUsize::new(0),
+            sent_envelopes: RwLock::new(Vec::new()),
+            protocol_version: RwLock::new(22),
+            get_latest_ledger_calls: AtomicUsize::new(0),
+            get_events_result: RwLock::new(None),
+            latest_ledger: RwLock::new(1),
+            get_health_result: RwLock::new(None),
+            send_delay: RwLock::new(None),
         }
     }
+
+    /// Returns how many times `send_transaction` was called.
+    #[allow(dead_code)]
+    pub fn send_transaction_calls(&self) -> usize {
+        self.send_transaction_calls.load(Ordering::SeqCst)
+    }
+
+    /// Returns how many times `get_transaction_polling` was called.
+    #[allow(dead_code)]
+    pub fn get_transaction_polling_calls(&self) -> usize {
+        self.get_transaction_polling_calls.load(Ordering::SeqCst)
+    }
+
+    /// Returns how many times `get_account` was called.
+    #[allow(dead_code)]
+    pub fn get_account_calls(&self) -> usize {
+        self.get_account_calls.load(Ordering::SeqCst)
+    }
+
+    /// Returns the envelopes passed to `send_transaction`, in order.
+    #[allow(dead_code)]
+    pub fn sent_envelopes(&self) -> Vec<TransactionEnvelope> {
+        self.sent_envelopes.read().unwrap().clone()
+    }
+
+    /// Returns how many times `get_latest_ledger` was called.
+    #[allow(dead_code)]
+    pub fn get_latest_ledger_calls(&self) -> usize {
+        self.get_latest_ledger_calls.load(Ordering::SeqCst)
+    }
+
+    /// Sets the protocol version reported by `get_latest_ledger`.
+    #[allow(dead_code)]
+    pub fn with_protocol_version(self, protocol_version: u32) -> Self {
+        *self.protocol_version.write().unwrap() = protocol_version;
+        self
+    }
+
+    /// Queues `get_account` results to be returned one per call, in order.
+    /// Once the queue is drained, the default `get_account_result` is used.
+    #[allow(dead_code)]
+    pub fn with_get_account_queue(
+        self,
+        results: Vec<Result<AccountEntry, SorobanHelperError>>,
+    ) -> Self {
+        *self.get_account_queue.write().unwrap() = results.into();
+        self
+    }
+
+    /// Queues `simulate_transaction_envelope` results to be returned one per call, in order.
+    /// Once the queue is drained, the default `simulate_transaction_envelope_result` is used.
+    #[allow(dead_code)]
+    pub fn with_simulate_transaction_envelope_queue(
+        self,
+        results: Vec<Result<SimulateTransactionResponse, SorobanHelperError>>,
+    ) -> Self {
+        *self.simulate_transaction_envelope_queue.write().unwrap() = results.into();
+        self
+    }
+
+    /// Queues the results of the sent transactions, one per `send_transaction` call.
+    /// Once the queue is drained, the default `send_transaction_polling_result` is used.
+    ///
+    /// An error is returned by `send_transaction`, as for a rejected transaction,
+    /// while a response is returned by the following `get_transaction_polling`.
+    #[allow(dead_code)]
+    pub fn with_send_transaction_polling_queue(
+        self,
+        results: Vec<Result<SorobanTransactionResponse, SorobanHelperError>>,
+    ) -> Self {
+        *self.send_transaction_polling_queue.write().unwrap() = results.into();
+        self
+    }
+
+    /// Queues `get_transaction_polling` results to be returned one per call, in
+    /// order, before the results of the sent transactions.
+    #[allow(dead_code)]
+    pub fn with_get_transaction_polling_queue(
+        self,
+        results: Vec<Result<SorobanTransactionResponse, SorobanHelperError>>,
+    ) -> Self {
+        *self.get_transaction_polling_queue.write().unwrap() = results.into();
+        self
+    }
+
+    /// Sets the result returned by `get_ledger_entries`.
+    #[allow(dead_code)]
+    pub fn with_get_ledger_entries_result(
+        self,
+        result: Result<GetLedgerEntriesResponse, SorobanHelperError>,
+    ) -> Self {
+        *self.get_ledger_entries_result.write().unwrap() = Some(result);
+        self
+    }
+
+    /// Sets the result returned by `get_events`.
+    #[allow(dead_code)]
+    pub fn with_get_events_result(
+        self,
+        result: Result<GetEventsResponse, SorobanHelperError>,
+    ) -> Self {
+        *self.get_events_result.write().unwrap() = Some(result);
+        self
+    }
+
+    /// Sets the ledger sequence reported by `get_latest_ledger`.
+    #[allow(dead_code)]
+    pub fn with_latest_ledger(self, sequence: u32) -> Self {
+        *self.latest_ledger.write().unwrap() = sequence;
+        self
+    }
+
+    /// Makes every `send_transaction` call wait for `delay` before returning.
+    #[allow(dead_code)]
+    pub fn with_send_delay(self, delay: Duration) -> Self {
+        *self.send_delay.write().unwrap() = Some(delay);
+        self
+    }
+
+    /// Sets the result returned by `get_health`.
+    #[allow(dead_code)]
+    pub fn with_get_health_result(
+        self,
+        result: Result<GetHealthResponse, SorobanHelperError>,
+    ) -> Self {
+        *self.get_health_result.write().unwrap() = Some(result);
+        self
+    }
+}
+
+/// Creates a mock `getLedgerEntries` response holding the given entries
+#[allow(dead_code)]
+pub fn mock_ledger_entries_response(
+    entries: Vec<(LedgerKey, LedgerEntryData)>,
+) -> GetLedgerEntriesResponse {
+    let entries = entries
+        .into_iter()
+        .map(|(key, data)| LedgerEntryResult {
+            key: key.to_xdr_base64(Limits::none()).unwrap(),
+            xdr: data.to_xdr_base64(Limits::none()).unwrap(),
+            last_modified_ledger: 1,
+            live_until_ledger_seq_ledger_seq: None,
+        })
+        .collect();
+
+    GetLedgerEntriesResponse {
+        entries: Some(entries),
+        latest_ledger: 1,
+    }
 }
 
 #[async_trait]
 impl RpcClient for MockRpcClient {
     async fn get_account(&self, account_id: &str) -> Result<AccountEntry, SorobanHelperError> {
+        self.get_account_calls.fetch_add(1, Ordering::SeqCst);
+        if let Some(queued) = self.get_account_queue.write().unwrap().pop_front() {
+            return queued;
+        }
         let result = self.get_account_result.read().unwrap();
         match result.as_ref() {
             Some(res) => res.clone(),
@@ -47,6 +252,14 @@ impl RpcClient for MockRpcClient {
         &self,
         _tx_envelope: &TransactionEnvelope,
     ) -> Result<SimulateTransactionResponse, SorobanHelperError> {
+        if let Some(queued) = self
+            .simulate_transaction_envelope_queue
+            .write()
+            .unwrap()
+            .pop_front()
+        {
+            return queued;
+        }
         let result = self.simulate_transaction_envelope_result.read().unwrap();
         match result.as_ref() {
             Some(res) => res.clone(),
@@ -54,14 +267,122 @@ impl RpcClient for MockRpcClient {
         }
     }
 
-    async fn send_transaction_polling(
+    async fn send_transaction(
         &self,
-        _tx_envelope: &TransactionEnvelope,
+        tx_envelope: &TransactionEnvelope,
+    ) -> Result<Hash, SorobanHelperError> {
+        self.send_transaction_calls.fetch_add(1, Ordering::SeqCst);
+        self.sent_envelopes
+            .write()
+            .unwrap()
+            .push(tx_envelope.clone());
+        let delay = *self.send_delay.read().unwrap();
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+        let queued = self
+            .send_transaction_polling_queue
+            .write()
+            .unwrap()
+            .pop_front();
+        let result = queued.unwrap_or_else(|| {
+            match self
+                .send_transaction_polling_result
+                .read()
+                .unwrap()
+                .as_ref()
+            {
+                Some(res) => res.clone(),
+                None => Ok(mock_transaction_response()),
+            }
+        });
+
+        let hash = Hash(Sha256::digest(tx_envelope.to_xdr(Limits::none())?).into());
+        self.pending_transactions
+            .write()
+            .unwrap()
+            .insert(hash.clone(), result?);
+        Ok(hash)
+    }
+
+    async fn get_transaction_polling(
+        &self,
+        hash: &Hash,
     ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
-        let result = self.send_transaction_polling_result.read().unwrap();
+        self.get_transaction_polling_calls
+            .fetch_add(1, Ordering::SeqCst);
+        if let Some(queued) = self
+            .get_transaction_polling_queue
+            .write()
+            .unwrap()
+            .pop_front()
+        {
+            return queued;
+        }
+        match self.pending_transactions.read().unwrap().get(hash) {
+            Some(response) => Ok(response.clone()),
+            None => Err(SorobanHelperError::NetworkRequestFailed(format!(
+                "Error: transaction {} not found",
+                hash
+            ))),
+        }
+    }
+
+    async fn get_ledger_entries(
+        &self,
+        _keys: &[LedgerKey],
+    ) -> Result<GetLedgerEntriesResponse, SorobanHelperError> {
+        let result = self.get_ledger_entries_result.read().unwrap();
+        match result.as_ref() {
+            Some(res) => res.clone(),
+            None => Ok(GetLedgerEntriesResponse {
+                entries: None,
+                latest_ledger: 0,
+            }),
+        }
+    }
+
+    async fn get_latest_ledger(&self) -> Result<GetLatestLedgerResponse, SorobanHelperError> {
+        self.get_latest_ledger_calls.fetch_add(1, Ordering::SeqCst);
+        Ok(GetLatestLedgerResponse {
+            id: "mock".to_string(),
+            protocol_version: *self.protocol_version.read().unwrap(),
+            sequence: *self.latest_ledger.read().unwrap(),
+        })
+    }
+
+    async fn get_events(
+        &self,
+        _start_ledger: u32,
+        _filter: &EventFilter,
+    ) -> Result<GetEventsResponse, SorobanHelperError> {
+        let result = self.get_events_result.read().unwrap();
+        match result.as_ref() {
+            Some(res) => res.clone(),
+            None => Ok(GetEventsResponse {
+                events: Vec::new(),
+                latest_ledger: 0,
+                latest_ledger_close_time: String::new(),
+                oldest_ledger: 0,
+                oldest_ledger_close_time: String::new(),
+                cursor: String::new(),
+            }),
+        }
+    }
+
+    async fn get_health(&self) -> Result<GetHealthResponse, SorobanHelperError> {
+        let result = self.get_health_result.read().unwrap();
         match result.as_ref() {
             Some(res) => res.clone(),
-            None => Ok(mock_transaction_response()),
+            None => {
+                let latest_ledger = *self.latest_ledger.read().unwrap();
+                Ok(GetHealthResponse {
+                    status: "healthy".to_string(),
+                    latest_ledger,
+                    oldest_ledger: 1,
+                    ledger_retention_window: latest_ledger,
+                })
+            }
         }
     }
 }
@@ -0,0 +1,54 @@
+use crate::error::SorobanHelperError;
+use crate::{Signer, TransactionSigner};
+use std::sync::Mutex;
+use stellar_xdr::curr::{AccountId, DecoratedSignature};
+
+/// A transaction signer standing in for an external signing backend.
+///
+/// Delegates to an in-process `Signer` and records the hashes it was asked to sign.
+pub struct MockTransactionSigner {
+    inner: Signer,
+    fail: bool,
+    signed_hashes: Mutex<Vec<[u8; 32]>>,
+}
+
+impl MockTransactionSigner {
+    pub fn new(inner: Signer) -> Self {
+        Self {
+            inner,
+            fail: false,
+            signed_hashes: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Creates a signer rejecting every signing request, like a declined hardware prompt.
+    #[allow(dead_code)]
+    pub fn failing(inner: Signer) -> Self {
+        Self {
+            fail: true,
+            ..Self::new(inner)
+        }
+    }
+
+    /// Returns the hashes signed so far, in order.
+    #[allow(dead_code)]
+    pub fn signed_hashes(&self) -> Vec<[u8; 32]> {
+        self.signed_hashes.lock().unwrap().clone()
+    }
+}
+
+impl TransactionSigner for MockTransactionSigner {
+    fn account_id(&self) -> AccountId {
+        self.inner.account_id()
+    }
+
+    fn sign_hash(&self, hash: &[u8; 32]) -> Result<DecoratedSignature, SorobanHelperError> {
+        if self.fail {
+            return Err(SorobanHelperError::SigningFailed(
+                "Signing request rejected".to_string(),
+            ));
+        }
+        self.signed_hashes.lock().unwrap().push(*hash);
+        self.inner.sign_hash(hash)
+    }
+}
@@ -1,13 +1,16 @@
 use std::convert::TryInto;
-use stellar_rpc_client::{GetTransactionResponse, SimulateTransactionResponse};
+use stellar_rpc_client::{
+    GetTransactionResponse, SimulateHostFunctionResultRaw, SimulateTransactionResponse,
+};
 use stellar_xdr::curr::{
-    AccountEntry, AccountId, ContractEvent, ContractEventBody, ContractEventType, ContractEventV0,
-    ExtensionPoint, Hash, LedgerEntry, LedgerEntryChange, LedgerEntryData, LedgerEntryExt, Memo,
+    AccountEntry, AccountId, ContractDataDurability, ContractDataEntry, ContractEvent,
+    ContractEventBody, ContractEventType, ContractEventV0, ContractExecutable, ExtensionPoint,
+    Hash, LedgerEntry, LedgerEntryChange, LedgerEntryData, LedgerEntryExt, Limits, Memo,
     MuxedAccount, Operation, OperationBody, OperationMeta, OperationResult, Preconditions,
-    ScAddress, ScVal, SequenceNumber, SetOptionsOp, SorobanTransactionMeta,
-    SorobanTransactionMetaExt, Transaction, TransactionEnvelope, TransactionExt, TransactionMeta,
-    TransactionMetaV3, TransactionResult, TransactionResultExt, TransactionResultResult,
-    TransactionV1Envelope, Uint256, VecM,
+    ScAddress, ScContractInstance, ScVal, SequenceNumber, SetOptionsOp, SorobanAuthorizationEntry,
+    SorobanTransactionMeta, SorobanTransactionMetaExt, Transaction, TransactionEnvelope,
+    TransactionExt, TransactionMeta, TransactionMetaV3, TransactionResult, TransactionResultExt,
+    TransactionResultResult, TransactionV1Envelope, Uint256, VecM, WriteXdr,
 };
 
 use crate::SorobanTransactionResponse;
@@ -31,6 +34,7 @@ enum MockResponseType {
     Basic,
     WithReturnValue(ScVal),
     WithAccountEntry(AccountEntry),
+    WithCreatedContractInstance(Hash),
 }
 
 #[allow(dead_code)]
@@ -110,6 +114,23 @@ pub fn mock_simulate_tx_response(min_resource_fee: Option<u64>) -> SimulateTrans
     }
 }
 
+/// Creates a simulation response whose single result requests the given authorizations
+#[allow(dead_code)]
+pub fn mock_simulate_tx_response_with_auth(
+    auth: Vec<SorobanAuthorizationEntry>,
+) -> SimulateTransactionResponse {
+    SimulateTransactionResponse {
+        results: vec![SimulateHostFunctionResultRaw {
+            auth: auth
+                .iter()
+                .map(|entry| entry.to_xdr_base64(Limits::none()).unwrap())
+                .collect(),
+            xdr: ScVal::Void.to_xdr_base64(Limits::none()).unwrap(),
+        }],
+        ..mock_simulate_tx_response(None)
+    }
+}
+
 #[allow(dead_code)]
 fn mock_transaction_response_impl(response_type: MockResponseType) -> GetTransactionResponse {
     let mut response = GetTransactionResponse {
@@ -152,6 +173,39 @@ fn mock_transaction_response_impl(response_type: MockResponseType) -> GetTransac
                 operations,
             });
 
+            response.result_meta = Some(meta);
+        }
+        MockResponseType::WithCreatedContractInstance(contract_hash) => {
+            let ledger_entry = LedgerEntry {
+                last_modified_ledger_seq: 1,
+                data: LedgerEntryData::ContractData(ContractDataEntry {
+                    ext: ExtensionPoint::V0,
+                    contract: ScAddress::Contract(stellar_xdr::curr::ContractId(contract_hash)),
+                    key: ScVal::LedgerKeyContractInstance,
+                    durability: ContractDataDurability::Persistent,
+                    val: ScVal::ContractInstance(ScContractInstance {
+                        executable: ContractExecutable::Wasm(Hash([2; 32])),
+                        storage: None,
+                    }),
+                }),
+                ext: LedgerEntryExt::V0,
+            };
+
+            let change = LedgerEntryChange::Created(ledger_entry);
+            let changes = VecM::try_from(vec![change]).unwrap_or_default();
+            let op_meta = OperationMeta {
+                changes: stellar_xdr::curr::LedgerEntryChanges(changes),
+            };
+
+            let operations = VecM::try_from(vec![op_meta]).unwrap_or_default();
+            let meta = TransactionMeta::V3(TransactionMetaV3 {
+                ext: ExtensionPoint::V0,
+                soroban_meta: None,
+                tx_changes_before: Default::default(),
+                tx_changes_after: Default::default(),
+                operations,
+            });
+
             response.result_meta = Some(meta);
         }
     }
@@ -180,6 +234,17 @@ pub fn mock_transaction_response_with_account_entry(
     mock_transaction_response_impl(MockResponseType::WithAccountEntry(account))
 }
 
+/// Creates a response whose meta holds a newly created contract instance
+/// entry, without a return value
+#[allow(dead_code)]
+pub fn mock_transaction_response_with_created_contract(
+    contract_hash: Hash,
+) -> SorobanTransactionResponse {
+    SorobanTransactionResponse::from(mock_transaction_response_impl(
+        MockResponseType::WithCreatedContractInstance(contract_hash),
+    ))
+}
+
 #[allow(dead_code)]
 fn create_success_tx_result() -> TransactionResult {
     // Create empty operation results
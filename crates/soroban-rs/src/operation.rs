@@ -4,10 +4,13 @@
 //! These operations represent the fundamental actions that can be performed with Soroban,
 //! such as uploading contract code, deploying contracts, and invoking contract functions.
 use stellar_xdr::curr::{
-    AccountId, Asset, ContractExecutable, ContractIdPreimage, CreateContractArgs,
-    CreateContractArgsV2, Hash, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Operation,
-    OperationBody, PaymentOp, ScAddress, ScSymbol, ScVal, SorobanAuthorizationEntry,
-    SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials, VecM,
+    AccountId, AlphaNum12, AlphaNum4, Asset, AssetCode12, AssetCode4, ContractExecutable,
+    ContractIdPreimage, CreateAccountOp, CreateContractArgs, CreateContractArgsV2, DataValue,
+    ExtendFootprintTtlOp, ExtensionPoint, Hash, HostFunction, InvokeContractArgs,
+    InvokeHostFunctionOp, LiquidityPoolDepositOp, LiquidityPoolWithdrawOp, ManageDataOp, Operation,
+    OperationBody, PaymentOp, PoolId, Price, RestoreFootprintOp, ScAddress, ScSymbol, ScVal,
+    SorobanAuthorizationEntry, SorobanAuthorizedFunction, SorobanAuthorizedInvocation,
+    SorobanCredentials, String64, VecM,
 };
 
 use crate::error::SorobanHelperError;
@@ -225,6 +228,151 @@ impl Operations {
         })
     }
 
+    /// Creates an operation to invoke a function on a deployed contract, carrying
+    /// the given authorization entries.
+    ///
+    /// # Parameters
+    ///
+    /// * `contract_id` - The ID of the deployed contract
+    /// * `function_name` - The name of the function to invoke
+    /// * `args` - Arguments to pass to the function
+    /// * `auth_entries` - Authorization entries signed for this invocation
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to invoke the contract function
+    ///
+    /// # Errors
+    ///
+    /// Returns:
+    /// - `SorobanHelperError::InvalidArgument` if the function name is invalid
+    /// - `SorobanHelperError::XdrEncodingFailed` if the arguments or entries cannot be encoded
+    pub fn invoke_contract_with_auth(
+        contract_id: &stellar_strkey::Contract,
+        function_name: &str,
+        args: Vec<ScVal>,
+        auth_entries: Vec<SorobanAuthorizationEntry>,
+    ) -> Result<Operation, SorobanHelperError> {
+        let mut operation = Self::invoke_contract(contract_id, function_name, args)?;
+        if let OperationBody::InvokeHostFunction(op) = &mut operation.body {
+            op.auth = auth_entries.try_into().map_err(|e| {
+                SorobanHelperError::XdrEncodingFailed(format!(
+                    "Failed to encode auth entries: {}",
+                    e
+                ))
+            })?;
+        }
+        Ok(operation)
+    }
+
+    /// Creates an operation invoking an arbitrary host function.
+    ///
+    /// This is an escape hatch for host functions without a dedicated helper,
+    /// e.g. ones introduced by a newer protocol version.
+    ///
+    /// # Parameters
+    ///
+    /// * `host_function` - The host function to invoke
+    /// * `auth` - Authorization entries for the invocation
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to invoke the host function
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::XdrEncodingFailed` if the entries cannot be encoded
+    pub fn invoke_host_function(
+        host_function: HostFunction,
+        auth: Vec<SorobanAuthorizationEntry>,
+    ) -> Result<Operation, SorobanHelperError> {
+        Ok(Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function,
+                auth: auth.try_into().map_err(|e| {
+                    SorobanHelperError::XdrEncodingFailed(format!(
+                        "Failed to encode auth entries: {}",
+                        e
+                    ))
+                })?,
+            }),
+        })
+    }
+
+    /// Creates an operation to extend the time to live of ledger entries.
+    ///
+    /// The entries to extend are the read-only footprint of the transaction
+    /// holding the operation (see `TransactionBuilder::set_soroban_data`).
+    ///
+    /// # Parameters
+    ///
+    /// * `ledgers_to_live` - The number of ledgers, from the current one, the entries must live for
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to extend the entries TTL
+    pub fn extend_footprint_ttl(ledgers_to_live: u32) -> Result<Operation, SorobanHelperError> {
+        Ok(Operation {
+            source_account: None,
+            body: OperationBody::ExtendFootprintTtl(ExtendFootprintTtlOp {
+                ext: ExtensionPoint::V0,
+                extend_to: ledgers_to_live,
+            }),
+        })
+    }
+
+    /// Creates an operation to restore archived ledger entries.
+    ///
+    /// The entries to restore are the read-write footprint of the transaction
+    /// holding the operation (see `TransactionBuilder::set_soroban_data`).
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to restore the entries
+    pub fn restore_footprint() -> Result<Operation, SorobanHelperError> {
+        Ok(Operation {
+            source_account: None,
+            body: OperationBody::RestoreFootprint(RestoreFootprintOp {
+                ext: ExtensionPoint::V0,
+            }),
+        })
+    }
+
+    /// Creates an operation to create and fund a new account.
+    ///
+    /// # Parameters
+    ///
+    /// * `destination` - The account to create
+    /// * `starting_balance` - The initial balance of the account, in stroops
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to create the account
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::InvalidArgument` if the starting balance is not positive
+    pub fn create_account(
+        destination: AccountId,
+        starting_balance: i64,
+    ) -> Result<Operation, SorobanHelperError> {
+        if starting_balance <= 0 {
+            return Err(SorobanHelperError::InvalidArgument(format!(
+                "Starting balance must be positive, got {}",
+                starting_balance
+            )));
+        }
+
+        Ok(Operation {
+            source_account: None,
+            body: OperationBody::CreateAccount(CreateAccountOp {
+                destination,
+                starting_balance,
+            }),
+        })
+    }
+
     pub fn send_payment(
         to: AccountId,
         amount: i64,
@@ -239,12 +387,208 @@ impl Operations {
             }),
         })
     }
+
+    /// Creates a payment operation in native XLM.
+    ///
+    /// # Parameters
+    ///
+    /// * `to` - The destination account
+    /// * `stroops` - The amount to send, in stroops
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to send the payment
+    pub fn pay_native(to: AccountId, stroops: i64) -> Result<Operation, SorobanHelperError> {
+        Self::send_payment(to, stroops, Asset::Native)
+    }
+
+    /// Creates a payment operation in an issued asset.
+    ///
+    /// Codes of 1 to 4 characters are `AlphaNum4` assets, codes of 5 to 12
+    /// characters are `AlphaNum12` assets.
+    ///
+    /// # Parameters
+    ///
+    /// * `to` - The destination account
+    /// * `amount` - The amount to send, in the asset's smallest unit
+    /// * `code` - The asset code, 1 to 12 alphanumeric characters
+    /// * `issuer` - The account issuing the asset
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to send the payment
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::InvalidArgument` if the asset code is empty,
+    /// longer than 12 characters or not alphanumeric
+    pub fn pay_asset(
+        to: AccountId,
+        amount: i64,
+        code: &str,
+        issuer: AccountId,
+    ) -> Result<Operation, SorobanHelperError> {
+        if code.is_empty() || code.len() > 12 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(SorobanHelperError::InvalidArgument(format!(
+                "Invalid asset code '{}', expected 1 to 12 alphanumeric characters",
+                code
+            )));
+        }
+
+        let asset = if code.len() <= 4 {
+            let mut asset_code = [0u8; 4];
+            asset_code[..code.len()].copy_from_slice(code.as_bytes());
+            Asset::CreditAlphanum4(AlphaNum4 {
+                asset_code: AssetCode4(asset_code),
+                issuer,
+            })
+        } else {
+            let mut asset_code = [0u8; 12];
+            asset_code[..code.len()].copy_from_slice(code.as_bytes());
+            Asset::CreditAlphanum12(AlphaNum12 {
+                asset_code: AssetCode12(asset_code),
+                issuer,
+            })
+        };
+
+        Self::send_payment(to, amount, asset)
+    }
+
+    /// Creates an operation to merge the source account into another one.
+    ///
+    /// The source account is removed from the ledger and its remaining XLM
+    /// balance is transferred to the destination.
+    ///
+    /// # Parameters
+    ///
+    /// * `destination` - The account receiving the balance
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to merge the account
+    pub fn account_merge(destination: AccountId) -> Result<Operation, SorobanHelperError> {
+        Ok(Operation {
+            source_account: None,
+            body: OperationBody::AccountMerge(destination.into()),
+        })
+    }
+
+    /// Creates an operation to set or delete a data entry of the source account.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the entry, 1 to 64 bytes
+    /// * `value` - The value to store, at most 64 bytes, or `None` to delete the entry
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to manage the data entry
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::InvalidArgument` if the name is empty or
+    /// longer than 64 bytes, or the value is longer than 64 bytes
+    pub fn manage_data(
+        name: &str,
+        value: Option<Vec<u8>>,
+    ) -> Result<Operation, SorobanHelperError> {
+        if name.is_empty() || name.len() > 64 {
+            return Err(SorobanHelperError::InvalidArgument(format!(
+                "Data entry name must be 1 to 64 bytes, got {}",
+                name.len()
+            )));
+        }
+        let data_name = name.try_into().map_err(|e| {
+            SorobanHelperError::InvalidArgument(format!("Invalid data entry name: {}", e))
+        })?;
+
+        let data_value = value
+            .map(|value| {
+                let len = value.len();
+                value.try_into().map(DataValue).map_err(|_| {
+                    SorobanHelperError::InvalidArgument(format!(
+                        "Data entry value must be at most 64 bytes, got {}",
+                        len
+                    ))
+                })
+            })
+            .transpose()?;
+
+        Ok(Operation {
+            source_account: None,
+            body: OperationBody::ManageData(ManageDataOp {
+                data_name: String64(data_name),
+                data_value,
+            }),
+        })
+    }
+
+    /// Creates an operation to deposit assets into a liquidity pool.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool_id` - The liquidity pool to deposit into
+    /// * `max_amount_a` - The maximum amount of the pool's first asset to deposit
+    /// * `max_amount_b` - The maximum amount of the pool's second asset to deposit
+    /// * `min_price` - The minimum deposit price, as asset A over asset B
+    /// * `max_price` - The maximum deposit price, as asset A over asset B
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to deposit into the pool
+    pub fn liquidity_pool_deposit(
+        pool_id: PoolId,
+        max_amount_a: i64,
+        max_amount_b: i64,
+        min_price: Price,
+        max_price: Price,
+    ) -> Result<Operation, SorobanHelperError> {
+        Ok(Operation {
+            source_account: None,
+            body: OperationBody::LiquidityPoolDeposit(LiquidityPoolDepositOp {
+                liquidity_pool_id: pool_id,
+                max_amount_a,
+                max_amount_b,
+                min_price,
+                max_price,
+            }),
+        })
+    }
+
+    /// Creates an operation to withdraw assets from a liquidity pool.
+    ///
+    /// # Parameters
+    ///
+    /// * `pool_id` - The liquidity pool to withdraw from
+    /// * `amount` - The amount of pool shares to redeem
+    /// * `min_amount_a` - The minimum amount of the pool's first asset to receive
+    /// * `min_amount_b` - The minimum amount of the pool's second asset to receive
+    ///
+    /// # Returns
+    ///
+    /// An operation that can be added to a transaction to withdraw from the pool
+    pub fn liquidity_pool_withdraw(
+        pool_id: PoolId,
+        amount: i64,
+        min_amount_a: i64,
+        min_amount_b: i64,
+    ) -> Result<Operation, SorobanHelperError> {
+        Ok(Operation {
+            source_account: None,
+            body: OperationBody::LiquidityPoolWithdraw(LiquidityPoolWithdrawOp {
+                liquidity_pool_id: pool_id,
+                amount,
+                min_amount_a,
+                min_amount_b,
+            }),
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use stellar_xdr::curr::{ContractIdPreimageFromAddress, PublicKey, ScVal};
+    use stellar_xdr::curr::{ContractIdPreimageFromAddress, MuxedAccount, PublicKey, ScVal};
 
     #[test]
     fn test_upload_wasm() {
@@ -364,6 +708,54 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_invoke_contract_with_auth() {
+        let contract_id = stellar_strkey::Contract([3; 32]);
+        let auth_entry = SorobanAuthorizationEntry {
+            credentials: SorobanCredentials::SourceAccount,
+            root_invocation: SorobanAuthorizedInvocation {
+                function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+                    contract_address: ScAddress::Contract(stellar_xdr::curr::ContractId(Hash(
+                        [3; 32],
+                    ))),
+                    function_name: ScSymbol("test_function".try_into().unwrap()),
+                    args: VecM::default(),
+                }),
+                sub_invocations: VecM::default(),
+            },
+        };
+
+        let operation = Operations::invoke_contract_with_auth(
+            &contract_id,
+            "test_function",
+            vec![],
+            vec![auth_entry.clone()],
+        )
+        .unwrap();
+
+        if let OperationBody::InvokeHostFunction(op) = operation.body {
+            assert!(matches!(op.host_function, HostFunction::InvokeContract(_)));
+            assert_eq!(op.auth.to_vec(), vec![auth_entry]);
+        } else {
+            panic!("Expected InvokeHostFunction operation");
+        }
+    }
+
+    #[test]
+    fn test_invoke_host_function() {
+        let host_function = HostFunction::UploadContractWasm(vec![0, 1, 2].try_into().unwrap());
+
+        let operation = Operations::invoke_host_function(host_function.clone(), vec![]).unwrap();
+
+        assert!(operation.source_account.is_none());
+        if let OperationBody::InvokeHostFunction(op) = operation.body {
+            assert_eq!(op.host_function, host_function);
+            assert_eq!(op.auth.len(), 0);
+        } else {
+            panic!("Expected InvokeHostFunction operation");
+        }
+    }
+
     #[test]
     fn test_invoke_contract_invalid_function_name() {
         let contract_bytes = [3; 32];
@@ -380,4 +772,238 @@ mod test {
             Err(SorobanHelperError::InvalidArgument(_))
         ));
     }
+
+    #[test]
+    fn test_extend_footprint_ttl() {
+        let operation = Operations::extend_footprint_ttl(17_280).unwrap();
+
+        assert!(operation.source_account.is_none());
+        match operation.body {
+            OperationBody::ExtendFootprintTtl(op) => {
+                assert_eq!(op.extend_to, 17_280);
+                assert_eq!(op.ext, ExtensionPoint::V0);
+            }
+            other => panic!(
+                "Expected OperationBody::ExtendFootprintTtl, got {:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_restore_footprint() {
+        let operation = Operations::restore_footprint().unwrap();
+
+        assert!(operation.source_account.is_none());
+        assert!(matches!(
+            operation.body,
+            OperationBody::RestoreFootprint(RestoreFootprintOp {
+                ext: ExtensionPoint::V0
+            })
+        ));
+    }
+
+    #[test]
+    fn test_pay_native() {
+        let to = AccountId(PublicKey::PublicKeyTypeEd25519([1; 32].into()));
+        let operation = Operations::pay_native(to.clone(), 1_000).unwrap();
+
+        match operation.body {
+            OperationBody::Payment(payment) => {
+                assert_eq!(payment.destination, to.into());
+                assert_eq!(payment.amount, 1_000);
+                assert_eq!(payment.asset, Asset::Native);
+            }
+            other => panic!("Expected OperationBody::Payment, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pay_asset() {
+        let to = AccountId(PublicKey::PublicKeyTypeEd25519([1; 32].into()));
+        let issuer = AccountId(PublicKey::PublicKeyTypeEd25519([2; 32].into()));
+
+        let operation = Operations::pay_asset(to.clone(), 500, "USDC", issuer.clone()).unwrap();
+        match operation.body {
+            OperationBody::Payment(payment) => {
+                assert_eq!(payment.destination, to.clone().into());
+                assert_eq!(payment.amount, 500);
+                assert_eq!(
+                    payment.asset,
+                    Asset::CreditAlphanum4(AlphaNum4 {
+                        asset_code: AssetCode4(*b"USDC"),
+                        issuer: issuer.clone(),
+                    })
+                );
+            }
+            other => panic!("Expected OperationBody::Payment, got {:?}", other),
+        }
+
+        let operation = Operations::pay_asset(to.clone(), 500, "ABC", issuer.clone()).unwrap();
+        match operation.body {
+            OperationBody::Payment(payment) => assert_eq!(
+                payment.asset,
+                Asset::CreditAlphanum4(AlphaNum4 {
+                    asset_code: AssetCode4(*b"ABC\0"),
+                    issuer: issuer.clone(),
+                })
+            ),
+            other => panic!("Expected OperationBody::Payment, got {:?}", other),
+        }
+
+        let operation =
+            Operations::pay_asset(to.clone(), 500, "LONGASSET123", issuer.clone()).unwrap();
+        match operation.body {
+            OperationBody::Payment(payment) => assert_eq!(
+                payment.asset,
+                Asset::CreditAlphanum12(AlphaNum12 {
+                    asset_code: AssetCode12(*b"LONGASSET123"),
+                    issuer: issuer.clone(),
+                })
+            ),
+            other => panic!("Expected OperationBody::Payment, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pay_asset_invalid_code() {
+        let to = AccountId(PublicKey::PublicKeyTypeEd25519([1; 32].into()));
+        let issuer = AccountId(PublicKey::PublicKeyTypeEd25519([2; 32].into()));
+
+        for code in ["", "THIRTEENCHARS", "US-D"] {
+            assert!(
+                matches!(
+                    Operations::pay_asset(to.clone(), 1, code, issuer.clone()),
+                    Err(SorobanHelperError::InvalidArgument(_))
+                ),
+                "'{}' should be rejected",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_liquidity_pool_deposit() {
+        let pool_id = PoolId(Hash([3; 32]));
+        let min_price = Price { n: 1, d: 2 };
+        let max_price = Price { n: 2, d: 1 };
+        let operation = Operations::liquidity_pool_deposit(
+            pool_id.clone(),
+            1_000,
+            2_000,
+            min_price.clone(),
+            max_price.clone(),
+        )
+        .unwrap();
+
+        assert!(operation.source_account.is_none());
+        match operation.body {
+            OperationBody::LiquidityPoolDeposit(op) => {
+                assert_eq!(op.liquidity_pool_id, pool_id);
+                assert_eq!(op.max_amount_a, 1_000);
+                assert_eq!(op.max_amount_b, 2_000);
+                assert_eq!(op.min_price, min_price);
+                assert_eq!(op.max_price, max_price);
+            }
+            other => panic!(
+                "Expected OperationBody::LiquidityPoolDeposit, got {:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_liquidity_pool_withdraw() {
+        let pool_id = PoolId(Hash([3; 32]));
+        let operation =
+            Operations::liquidity_pool_withdraw(pool_id.clone(), 500, 100, 200).unwrap();
+
+        assert!(operation.source_account.is_none());
+        match operation.body {
+            OperationBody::LiquidityPoolWithdraw(op) => {
+                assert_eq!(op.liquidity_pool_id, pool_id);
+                assert_eq!(op.amount, 500);
+                assert_eq!(op.min_amount_a, 100);
+                assert_eq!(op.min_amount_b, 200);
+            }
+            other => panic!(
+                "Expected OperationBody::LiquidityPoolWithdraw, got {:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn test_create_account() {
+        let destination = AccountId(PublicKey::PublicKeyTypeEd25519([1; 32].into()));
+        let operation = Operations::create_account(destination.clone(), 10_000_000).unwrap();
+
+        assert!(operation.source_account.is_none());
+        match operation.body {
+            OperationBody::CreateAccount(op) => {
+                assert_eq!(op.destination, destination);
+                assert_eq!(op.starting_balance, 10_000_000);
+            }
+            other => panic!("Expected OperationBody::CreateAccount, got {:?}", other),
+        }
+
+        for starting_balance in [0, -1] {
+            assert!(matches!(
+                Operations::create_account(destination.clone(), starting_balance),
+                Err(SorobanHelperError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_manage_data() {
+        let operation = Operations::manage_data("app_version", Some(b"1.2.0".to_vec())).unwrap();
+
+        assert!(operation.source_account.is_none());
+        match operation.body {
+            OperationBody::ManageData(op) => {
+                assert_eq!(op.data_name.to_string(), "app_version");
+                assert_eq!(op.data_value.unwrap().to_vec(), b"1.2.0".to_vec());
+            }
+            other => panic!("Expected OperationBody::ManageData, got {:?}", other),
+        }
+
+        // no value deletes the entry
+        let operation = Operations::manage_data("app_version", None).unwrap();
+        match operation.body {
+            OperationBody::ManageData(op) => assert!(op.data_value.is_none()),
+            other => panic!("Expected OperationBody::ManageData, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_manage_data_invalid() {
+        assert!(Operations::manage_data(&"a".repeat(64), Some(vec![0; 64])).is_ok());
+
+        for (name, value) in [
+            (String::new(), None),
+            ("a".repeat(65), None),
+            ("name".to_string(), Some(vec![0; 65])),
+        ] {
+            assert!(matches!(
+                Operations::manage_data(&name, value),
+                Err(SorobanHelperError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_account_merge() {
+        let destination = AccountId(PublicKey::PublicKeyTypeEd25519([1; 32].into()));
+        let operation = Operations::account_merge(destination.clone()).unwrap();
+
+        assert!(operation.source_account.is_none());
+        match operation.body {
+            OperationBody::AccountMerge(muxed) => {
+                assert_eq!(muxed, MuxedAccount::Ed25519([1; 32].into()));
+                assert_eq!(muxed, destination.into());
+            }
+            other => panic!("Expected OperationBody::AccountMerge, got {:?}", other),
+        }
+    }
 }
@@ -1,9 +1,11 @@
 use crate::error::SorobanHelperError;
+use crate::{DecodedEvent, FromScVal, SorobanTransactionResponse};
+use std::fmt::Write;
 use stellar_rpc_client::GetTransactionResponse;
 use stellar_strkey::Contract as ContractId;
 use stellar_xdr::curr::{
-    AccountEntry, LedgerEntryChange, LedgerEntryData, OperationResult, ScAddress, ScVal,
-    TransactionMeta, TransactionResultResult,
+    AccountEntry, ContractEvent, LedgerEntryChange, LedgerEntryChanges, LedgerEntryData,
+    OperationResult, ScAddress, ScVal, TransactionMeta, TransactionResultResult,
 };
 
 #[derive(Debug)]
@@ -11,6 +13,7 @@ pub enum ParserType {
     AccountSetOptions,
     InvokeFunction,
     Deploy,
+    Events,
     // Add more parser types as needed
 }
 
@@ -19,16 +22,43 @@ pub enum ParseResult {
     AccountSetOptions(Option<AccountEntry>),
     InvokeFunction(Option<ScVal>),
     Deploy(Option<ContractId>),
+    Events(Vec<ContractEvent>),
     // Add more result types as needed
 }
 
+/// The first protocol version whose transaction metadata is in the V4 format.
+const META_V4_PROTOCOL_VERSION: u32 = 23;
+
 pub struct Parser {
     parser_type: ParserType,
+    include_diagnostics: bool,
+    protocol_version: Option<u32>,
 }
 
 impl Parser {
     pub fn new(parser_type: ParserType) -> Self {
-        Self { parser_type }
+        Self {
+            parser_type,
+            include_diagnostics: false,
+            protocol_version: None,
+        }
+    }
+
+    /// Parses the transaction metadata in the format of the given protocol
+    /// version, e.g. the one returned by `Env::protocol_version`.
+    ///
+    /// Metadata is in the V3 format up to protocol 22 and in the V4 format from
+    /// protocol 23, and metadata in the other format is rejected. Without a
+    /// protocol version, the format is detected from the metadata itself.
+    pub fn with_protocol_version(mut self, protocol_version: u32) -> Self {
+        self.protocol_version = Some(protocol_version);
+        self
+    }
+
+    /// Includes the diagnostic events of failed transactions in the parse errors.
+    pub fn with_diagnostics(mut self, include_diagnostics: bool) -> Self {
+        self.include_diagnostics = include_diagnostics;
+        self
     }
 
     pub fn parse(
@@ -37,23 +67,21 @@ impl Parser {
     ) -> Result<ParseResult, SorobanHelperError> {
         match self.parser_type {
             ParserType::AccountSetOptions => {
-                self.check_tx_success(&response.result)?;
+                self.check_tx_success(response)?;
 
                 // Extract account entry from transaction metadata
-                let result = response
-                    .result_meta
-                    .as_ref()
+                let result = self
+                    .result_meta(response)?
                     .and_then(|meta| self.extract_account_entry(meta));
 
                 Ok(ParseResult::AccountSetOptions(result))
             }
             ParserType::InvokeFunction => {
-                let op_results = self.check_tx_success(&response.result)?;
+                let op_results = self.check_tx_success(response)?;
 
                 // Try to extract return value from transaction metadata first
-                let result_from_meta = response
-                    .result_meta
-                    .as_ref()
+                let result_from_meta = self
+                    .result_meta(response)?
                     .and_then(|meta| self.extract_return_value(meta))
                     .map(|value| ParseResult::InvokeFunction(Some(value)));
                 if let Some(result) = result_from_meta {
@@ -72,68 +100,195 @@ impl Parser {
                 Ok(ParseResult::InvokeFunction(None))
             }
             ParserType::Deploy => {
-                self.check_tx_success(&response.result)?;
+                self.check_tx_success(response)?;
 
                 // Extract contract hash from transaction metadata
-                let result = response
-                    .result_meta
-                    .as_ref()
-                    .and_then(|meta| self.extract_return_value(meta))
-                    .and_then(|val| self.extract_contract_id(&val))
-                    .map(|contract_id| ParseResult::Deploy(Some(contract_id)));
+                let return_value = self
+                    .result_meta(response)?
+                    .and_then(|meta| self.extract_return_value(meta));
 
-                if let Some(result) = result {
-                    return Ok(result);
+                if let Some(val) = return_value {
+                    if let Some(contract_id) = self.extract_contract_id(&val) {
+                        return Ok(ParseResult::Deploy(Some(contract_id)));
+                    }
+                    // An address that isn't a contract means the deploy returned something unexpected
+                    if let ScVal::Address(address) = val {
+                        return Err(SorobanHelperError::ConversionError(format!(
+                            "Deploy returned a non-contract address {:?} instead of the deployed contract id",
+                            address
+                        )));
+                    }
+                }
+
+                // Some RPC providers don't populate the return value, fall back to
+                // the contract instance entry created by the deploy
+                let created = self
+                    .result_meta(response)?
+                    .and_then(|meta| self.extract_created_contract_id(meta));
+                if let Some(contract_id) = created {
+                    return Ok(ParseResult::Deploy(Some(contract_id)));
                 }
 
                 // If we couldn't extract a valid result but transaction succeeded
                 Ok(ParseResult::Deploy(None))
             }
+            ParserType::Events => {
+                self.check_tx_success(response)?;
+
+                let events = self
+                    .result_meta(response)?
+                    .map(|meta| self.extract_events(meta))
+                    .unwrap_or_default();
+
+                Ok(ParseResult::Events(events))
+            }
         }
     }
 
+    /// Parses the return value of a contract invocation and decodes it into `T`.
+    ///
+    /// Runs the `ParserType::InvokeFunction` parser, whatever the parser type.
+    ///
+    /// # Errors
+    ///
+    /// Returns:
+    /// - `SorobanHelperError::TransactionFailed` if the transaction failed
+    /// - `SorobanHelperError::ConversionError` if there is no return value or it is not a `T`
+    pub fn parse_return_as<T: FromScVal>(
+        response: &GetTransactionResponse,
+    ) -> Result<T, SorobanHelperError> {
+        match Parser::new(ParserType::InvokeFunction).parse(response)? {
+            ParseResult::InvokeFunction(Some(value)) => T::try_from_val(&value),
+            _ => Err(SorobanHelperError::ConversionError(
+                "Transaction has no return value".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the transaction metadata, checking it is in the format of the
+    /// protocol version if set.
+    fn result_meta<'a>(
+        &self,
+        response: &'a GetTransactionResponse,
+    ) -> Result<Option<&'a TransactionMeta>, SorobanHelperError> {
+        let (Some(meta), Some(protocol_version)) = (&response.result_meta, self.protocol_version)
+        else {
+            return Ok(response.result_meta.as_ref());
+        };
+
+        let expected = if protocol_version >= META_V4_PROTOCOL_VERSION {
+            "V4"
+        } else {
+            "V3"
+        };
+        let actual = match meta {
+            TransactionMeta::V3(_) => "V3",
+            TransactionMeta::V4(_) => "V4",
+            _ => "legacy",
+        };
+        if actual != expected {
+            return Err(SorobanHelperError::ConversionError(format!(
+                "Transaction metadata is in the {} format, expected {} for protocol {}",
+                actual, expected, protocol_version
+            )));
+        }
+        Ok(Some(meta))
+    }
+
     fn check_tx_success<'a>(
         &self,
-        tx_result: &'a Option<stellar_xdr::curr::TransactionResult>,
+        response: &'a GetTransactionResponse,
     ) -> Result<&'a [OperationResult], SorobanHelperError> {
-        let tx_result = tx_result.as_ref().ok_or_else(|| {
+        let tx_result = response.result.as_ref().ok_or_else(|| {
             SorobanHelperError::TransactionFailed("No transaction result available".to_string())
         })?;
 
-        match &tx_result.result {
-            TransactionResultResult::TxSuccess(results) => Ok(results.as_slice()),
-            _ => Err(SorobanHelperError::TransactionFailed(format!(
-                "Transaction failed: {:?}",
-                tx_result.result
-            ))),
+        if let TransactionResultResult::TxSuccess(results) = &tx_result.result {
+            return Ok(results.as_slice());
+        }
+
+        let mut error = SorobanHelperError::from_transaction_result(&tx_result.result);
+        if let SorobanHelperError::TransactionFailedWithCode { detail, .. } = &mut error {
+            detail.push_str(&self.format_diagnostics(response));
+        }
+        Err(error)
+    }
+
+    fn format_diagnostics(&self, response: &GetTransactionResponse) -> String {
+        if !self.include_diagnostics {
+            return String::new();
+        }
+        let events = SorobanTransactionResponse::new(response.clone())
+            .get_diagnostic_events()
+            .unwrap_or_default();
+
+        let mut diagnostics = String::new();
+        for diagnostic in &events {
+            let event = DecodedEvent::from(&diagnostic.event);
+            let _ = write!(
+                diagnostics,
+                "\n  diagnostic event: topics {:?}, data {:?}",
+                event.topics, event.data
+            );
         }
+        diagnostics
     }
 
     fn extract_account_entry(&self, meta: &TransactionMeta) -> Option<AccountEntry> {
         match meta {
-            TransactionMeta::V3(v3) => v3.operations.last().and_then(|op| {
-                op.changes.0.iter().rev().find_map(|change| match change {
-                    LedgerEntryChange::Updated(entry) => {
-                        if let LedgerEntryData::Account(account) = &entry.data {
-                            Some(account.clone())
-                        } else {
-                            None
-                        }
-                    }
-                    _ => None,
-                })
-            }),
+            TransactionMeta::V3(v3) => v3
+                .operations
+                .last()
+                .and_then(|op| Self::find_updated_account(&op.changes)),
+            TransactionMeta::V4(v4) => v4
+                .operations
+                .last()
+                .and_then(|op| Self::find_updated_account(&op.changes)),
             _ => None,
         }
     }
 
+    fn find_updated_account(changes: &LedgerEntryChanges) -> Option<AccountEntry> {
+        changes.0.iter().rev().find_map(|change| match change {
+            LedgerEntryChange::Updated(entry) => {
+                if let LedgerEntryData::Account(account) = &entry.data {
+                    Some(account.clone())
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+    }
+
     fn extract_return_value(&self, meta: &TransactionMeta) -> Option<ScVal> {
         match meta {
             TransactionMeta::V3(v3) => v3.soroban_meta.as_ref().map(|sm| sm.return_value.clone()),
+            TransactionMeta::V4(v4) => v4
+                .soroban_meta
+                .as_ref()
+                .and_then(|sm| sm.return_value.clone()),
             _ => None,
         }
     }
 
+    fn extract_events(&self, meta: &TransactionMeta) -> Vec<ContractEvent> {
+        match meta {
+            TransactionMeta::V3(v3) => v3
+                .soroban_meta
+                .as_ref()
+                .map(|sm| sm.events.to_vec())
+                .unwrap_or_default(),
+            // From protocol 23, contract events are reported per operation
+            TransactionMeta::V4(v4) => v4
+                .operations
+                .iter()
+                .flat_map(|op| op.events.iter().cloned())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
     fn extract_operation_result(&self, op_result: &OperationResult) -> Option<ScVal> {
         match op_result {
             OperationResult::OpInner(stellar_xdr::curr::OperationResultTr::InvokeHostFunction(
@@ -145,6 +300,34 @@ impl Parser {
         }
     }
 
+    fn extract_created_contract_id(&self, meta: &TransactionMeta) -> Option<ContractId> {
+        match meta {
+            TransactionMeta::V3(v3) => v3
+                .operations
+                .iter()
+                .find_map(|op| self.find_created_contract_id(&op.changes)),
+            TransactionMeta::V4(v4) => v4
+                .operations
+                .iter()
+                .find_map(|op| self.find_created_contract_id(&op.changes)),
+            _ => None,
+        }
+    }
+
+    fn find_created_contract_id(&self, changes: &LedgerEntryChanges) -> Option<ContractId> {
+        changes.0.iter().find_map(|change| match change {
+            LedgerEntryChange::Created(entry) => match &entry.data {
+                LedgerEntryData::ContractData(data)
+                    if data.key == ScVal::LedgerKeyContractInstance =>
+                {
+                    self.extract_contract_id(&ScVal::Address(data.contract.clone()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+
     fn extract_contract_id(&self, val: &ScVal) -> Option<ContractId> {
         match val {
             ScVal::Address(ScAddress::Contract(stellar_xdr::curr::ContractId(hash))) => {
@@ -159,16 +342,63 @@ impl Parser {
 mod tests {
     use crate::error::SorobanHelperError;
     use crate::mock::transaction::{
-        create_contract_id_val, mock_transaction_response_with_account_entry,
+        create_contract_id_val, create_mock_contract_event,
+        mock_transaction_response_with_account_entry,
+        mock_transaction_response_with_created_contract,
         mock_transaction_response_with_return_value,
     };
     use crate::parser::{ParseResult, Parser, ParserType};
     use stellar_rpc_client::GetTransactionResponse;
     use stellar_xdr::curr::{
-        AccountEntry, InvokeHostFunctionResult, OperationResult, OperationResultTr, ScVal,
-        TransactionResult, TransactionResultExt, TransactionResultResult,
+        AccountEntry, AccountId, ContractEventBody, ContractEventV0, DiagnosticEvent,
+        ExtensionPoint, Hash, InvokeHostFunctionResult, OperationMetaV2, OperationResult,
+        OperationResultTr, PublicKey, ScAddress, ScString, ScVal, SorobanTransactionMetaV2,
+        TransactionMeta, TransactionMetaV4, TransactionResult, TransactionResultExt,
+        TransactionResultResult, Uint256, VecM,
     };
 
+    /// Converts the V3 metadata of a mock response into the V4 format used from protocol 23
+    fn into_meta_v4(mut response: GetTransactionResponse) -> GetTransactionResponse {
+        let Some(TransactionMeta::V3(v3)) = response.result_meta.take() else {
+            panic!("Expected V3 transaction metadata");
+        };
+        let events = v3
+            .soroban_meta
+            .as_ref()
+            .map(|sm| sm.events.clone())
+            .unwrap_or_default();
+        let mut operations: Vec<OperationMetaV2> = v3
+            .operations
+            .iter()
+            .map(|op| OperationMetaV2 {
+                ext: ExtensionPoint::V0,
+                changes: op.changes.clone(),
+                events: VecM::default(),
+            })
+            .collect();
+        if !events.is_empty() {
+            operations.push(OperationMetaV2 {
+                ext: ExtensionPoint::V0,
+                changes: Default::default(),
+                events,
+            });
+        }
+
+        response.result_meta = Some(TransactionMeta::V4(TransactionMetaV4 {
+            ext: ExtensionPoint::V0,
+            tx_changes_before: v3.tx_changes_before,
+            operations: operations.try_into().unwrap(),
+            tx_changes_after: v3.tx_changes_after,
+            soroban_meta: v3.soroban_meta.map(|sm| SorobanTransactionMetaV2 {
+                ext: sm.ext,
+                return_value: Some(sm.return_value),
+            }),
+            events: VecM::default(),
+            diagnostic_events: VecM::default(),
+        }));
+        response
+    }
+
     #[test]
     fn test_new_parser() {
         let parser = Parser::new(ParserType::InvokeFunction);
@@ -346,6 +576,34 @@ mod tests {
         assert!(parser.extract_contract_id(&non_contract_val).is_none());
     }
 
+    #[test]
+    fn test_deploy_parser_account_address() {
+        let parser = Parser::new(ParserType::Deploy);
+
+        let account_val = ScVal::Address(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256([1; 32])),
+        )));
+        let res = mock_transaction_response_with_return_value(account_val);
+
+        let result = parser.parse(&res.response);
+        assert!(matches!(
+            result,
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_deploy_parser_from_created_instance() {
+        let parser = Parser::new(ParserType::Deploy);
+
+        let res = mock_transaction_response_with_created_contract(Hash([7; 32]));
+        let result = parser.parse(&res.response);
+        match result {
+            Ok(ParseResult::Deploy(Some(contract_id))) => assert_eq!(contract_id.0, [7; 32]),
+            _ => panic!("Expected Deploy(Some(_)), got {:?}", result),
+        }
+    }
+
     #[test]
     fn test_deploy_parser_fallback() {
         let parser = Parser::new(ParserType::Deploy);
@@ -376,4 +634,230 @@ mod tests {
         let result = parser.parse(&response_no_meta);
         assert!(matches!(result, Ok(ParseResult::Deploy(None))));
     }
+
+    #[test]
+    fn test_events_parser() {
+        let parser = Parser::new(ParserType::Events);
+
+        let event1 = create_mock_contract_event();
+        let mut event2 = create_mock_contract_event();
+        event2.contract_id = Some(stellar_xdr::curr::ContractId(Hash([2; 32])));
+
+        let mut res = mock_transaction_response_with_return_value(ScVal::Void);
+        if let Some(TransactionMeta::V3(meta)) = res.response.result_meta.as_mut() {
+            if let Some(soroban_meta) = meta.soroban_meta.as_mut() {
+                soroban_meta.events = vec![event1.clone(), event2.clone()].try_into().unwrap();
+            }
+        }
+
+        let result = parser.parse(&res.response);
+        assert!(matches!(result, Ok(ParseResult::Events(_))));
+        if let Ok(ParseResult::Events(events)) = result {
+            assert_eq!(events, vec![event1, event2]);
+        }
+
+        // a transaction without events
+        let res = mock_transaction_response_with_return_value(ScVal::Void);
+        assert!(matches!(
+            parser.parse(&res.response),
+            Ok(ParseResult::Events(events)) if events.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_parse_return_as() {
+        let res = mock_transaction_response_with_return_value(ScVal::U32(42));
+        assert_eq!(Parser::parse_return_as::<u32>(&res.response).unwrap(), 42);
+
+        assert!(matches!(
+            Parser::parse_return_as::<bool>(&res.response),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_failed_transaction_diagnostics() {
+        let mut res = mock_transaction_response_with_return_value(ScVal::Void);
+        res.response.result = Some(TransactionResult {
+            fee_charged: 100,
+            result: TransactionResultResult::TxFailed(VecM::default()),
+            ext: TransactionResultExt::V0,
+        });
+        let mut event = create_mock_contract_event();
+        event.body = ContractEventBody::V0(ContractEventV0 {
+            topics: vec![ScVal::Symbol("error".try_into().unwrap())]
+                .try_into()
+                .unwrap(),
+            data: ScVal::String(ScString("insufficient balance".try_into().unwrap())),
+        });
+        if let Some(TransactionMeta::V3(meta)) = res.response.result_meta.as_mut() {
+            meta.soroban_meta.as_mut().unwrap().diagnostic_events = vec![DiagnosticEvent {
+                in_successful_contract_call: false,
+                event,
+            }]
+            .try_into()
+            .unwrap();
+        }
+
+        let Err(SorobanHelperError::TransactionFailedWithCode { detail, .. }) =
+            Parser::new(ParserType::InvokeFunction).parse(&res.response)
+        else {
+            panic!("Expected SorobanHelperError::TransactionFailedWithCode");
+        };
+        assert!(!detail.contains("insufficient balance"));
+
+        let Err(SorobanHelperError::TransactionFailedWithCode { detail, .. }) =
+            Parser::new(ParserType::InvokeFunction)
+                .with_diagnostics(true)
+                .parse(&res.response)
+        else {
+            panic!("Expected SorobanHelperError::TransactionFailedWithCode");
+        };
+        assert!(detail.contains("diagnostic event"));
+        assert!(detail.contains("insufficient balance"));
+    }
+
+    #[test]
+    fn test_failed_transaction_code() {
+        let failed_with = |result: TransactionResultResult| {
+            let mut res = mock_transaction_response_with_return_value(ScVal::Void);
+            res.response.result = Some(TransactionResult {
+                fee_charged: 100,
+                result,
+                ext: TransactionResultExt::V0,
+            });
+            Parser::new(ParserType::InvokeFunction)
+                .parse(&res.response)
+                .unwrap_err()
+        };
+
+        let op_result = OperationResult::OpInner(OperationResultTr::InvokeHostFunction(
+            InvokeHostFunctionResult::Trapped,
+        ));
+        let error = failed_with(TransactionResultResult::TxFailed(
+            vec![op_result].try_into().unwrap(),
+        ));
+        assert!(matches!(
+            &error,
+            SorobanHelperError::TransactionFailedWithCode { code, detail }
+                if code == "txFailed" && detail.contains("Trapped")
+        ));
+
+        let error = failed_with(TransactionResultResult::TxInsufficientFee);
+        assert_eq!(
+            error,
+            SorobanHelperError::TransactionFailedWithCode {
+                code: "txInsufficientFee".to_string(),
+                detail: String::new(),
+            }
+        );
+
+        let error = failed_with(TransactionResultResult::TxBadSeq);
+        assert!(matches!(
+            error,
+            SorobanHelperError::TransactionFailedWithCode { code, .. } if code == "txBadSeq"
+        ));
+    }
+
+    #[test]
+    fn test_invoke_function_parser_meta_v4() {
+        let res = mock_transaction_response_with_return_value(ScVal::I32(42));
+        let response = into_meta_v4(res.response);
+
+        let result = Parser::new(ParserType::InvokeFunction).parse(&response);
+        assert!(matches!(
+            result,
+            Ok(ParseResult::InvokeFunction(Some(ScVal::I32(42))))
+        ));
+    }
+
+    #[test]
+    fn test_deploy_parser_meta_v4() {
+        let parser = Parser::new(ParserType::Deploy);
+
+        let res = mock_transaction_response_with_return_value(create_contract_id_val());
+        let result = parser.parse(&into_meta_v4(res.response));
+        assert!(matches!(result, Ok(ParseResult::Deploy(Some(_)))));
+
+        let res = mock_transaction_response_with_created_contract(Hash([7; 32]));
+        let result = parser.parse(&into_meta_v4(res.response));
+        match result {
+            Ok(ParseResult::Deploy(Some(contract_id))) => assert_eq!(contract_id.0, [7; 32]),
+            _ => panic!("Expected Deploy(Some(_)), got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_account_set_options_parser_meta_v4() {
+        let account_entry = AccountEntry {
+            account_id: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([0; 32]))),
+            balance: 1000,
+            seq_num: 123.into(),
+            num_sub_entries: 0,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: stellar_xdr::curr::String32(vec![].try_into().unwrap()),
+            thresholds: stellar_xdr::curr::Thresholds([0, 0, 0, 0]),
+            signers: VecM::default(),
+            ext: stellar_xdr::curr::AccountEntryExt::V0,
+        };
+        let response = into_meta_v4(mock_transaction_response_with_account_entry(
+            account_entry.clone(),
+        ));
+
+        let result = Parser::new(ParserType::AccountSetOptions).parse(&response);
+        assert!(matches!(
+            result,
+            Ok(ParseResult::AccountSetOptions(Some(acct))) if acct == account_entry
+        ));
+    }
+
+    #[test]
+    fn test_parser_with_protocol_version() {
+        let res = mock_transaction_response_with_return_value(ScVal::I32(42));
+        let meta_v3 = res.response.clone();
+        let meta_v4 = into_meta_v4(res.response);
+
+        let parser = Parser::new(ParserType::InvokeFunction).with_protocol_version(23);
+        assert!(matches!(
+            parser.parse(&meta_v4),
+            Ok(ParseResult::InvokeFunction(Some(ScVal::I32(42))))
+        ));
+        assert!(matches!(
+            parser.parse(&meta_v3),
+            Err(SorobanHelperError::ConversionError(msg)) if msg.contains("expected V4 for protocol 23")
+        ));
+
+        let parser = Parser::new(ParserType::InvokeFunction).with_protocol_version(22);
+        assert!(matches!(
+            parser.parse(&meta_v3),
+            Ok(ParseResult::InvokeFunction(Some(ScVal::I32(42))))
+        ));
+        assert!(matches!(
+            parser.parse(&meta_v4),
+            Err(SorobanHelperError::ConversionError(msg)) if msg.contains("expected V3 for protocol 22")
+        ));
+
+        let parser = Parser::new(ParserType::InvokeFunction);
+        assert!(parser.parse(&meta_v3).is_ok());
+        assert!(parser.parse(&meta_v4).is_ok());
+    }
+
+    #[test]
+    fn test_events_parser_meta_v4() {
+        let event = create_mock_contract_event();
+        let mut res = mock_transaction_response_with_return_value(ScVal::Void);
+        if let Some(TransactionMeta::V3(meta)) = res.response.result_meta.as_mut() {
+            if let Some(soroban_meta) = meta.soroban_meta.as_mut() {
+                soroban_meta.events = vec![event.clone()].try_into().unwrap();
+            }
+        }
+        let response = into_meta_v4(res.response);
+
+        let result = Parser::new(ParserType::Events).parse(&response);
+        assert!(matches!(
+            result,
+            Ok(ParseResult::Events(events)) if events == vec![event]
+        ));
+    }
 }
@@ -1,7 +1,10 @@
 use stellar_rpc_client::GetTransactionResponse;
-use stellar_xdr::curr::{ScVal, SorobanTransactionMeta, TransactionMeta, TransactionMetaV3};
+use stellar_xdr::curr::{
+    DiagnosticEvent, ScVal, SorobanTransactionMeta, TransactionMeta, TransactionMetaV3,
+    TransactionMetaV4,
+};
 
-use crate::SorobanHelperError;
+use crate::{DecodedEvent, FromScVal, SorobanHelperError};
 
 /// Extended transaction response with methods to extract Soroban-specific data
 #[derive(Debug, Clone)]
@@ -29,7 +32,7 @@ impl SorobanTransactionResponse {
     /// The Soroban return value as an ScVal or an error if:
     /// - The transaction result is not available
     /// - The transaction metadata is not available
-    /// - The transaction metadata is not in V3 format
+    /// - The transaction metadata is not in V3 (protocol 22) or V4 (protocol 23+) format
     /// - The Soroban metadata is not available
     pub fn get_return_value(&self) -> Result<ScVal, SorobanHelperError> {
         // Check if result_meta exists
@@ -40,12 +43,25 @@ impl SorobanTransactionResponse {
         // Extract the Soroban metadata from the transaction metadata
         match result_meta {
             TransactionMeta::V3(meta_v3) => self.extract_soroban_return_value(meta_v3),
+            TransactionMeta::V4(meta_v4) => self.extract_soroban_return_value_v4(meta_v4),
             _ => Err(SorobanHelperError::InvalidArgument(
-                "Transaction metadata is not in V3 format (not a Soroban transaction)".to_string(),
+                "Transaction metadata is not in V3 or V4 format (not a Soroban transaction)"
+                    .to_string(),
             )),
         }
     }
 
+    /// Extracts the Soroban transaction return value and decodes it into a native type
+    ///
+    /// # Returns
+    ///
+    /// The decoded return value or an error if:
+    /// - The return value can't be extracted, see `get_return_value`
+    /// - The return value is not of the requested type (`SorobanHelperError::ConversionError`)
+    pub fn get_return_value_as<T: FromScVal>(&self) -> Result<T, SorobanHelperError> {
+        T::try_from_val(&self.get_return_value()?)
+    }
+
     /// Extracts the Soroban transaction events from the transaction metadata
     ///
     /// # Returns
@@ -53,7 +69,7 @@ impl SorobanTransactionResponse {
     /// A vector of contract events or an error if:
     /// - The transaction result is not available
     /// - The transaction metadata is not available
-    /// - The transaction metadata is not in V3 format
+    /// - The transaction metadata is not in V3 (protocol 22) or V4 (protocol 23+) format
     /// - The Soroban metadata is not available
     pub fn get_events(&self) -> Result<Vec<stellar_xdr::curr::ContractEvent>, SorobanHelperError> {
         // Check if result_meta exists
@@ -64,12 +80,63 @@ impl SorobanTransactionResponse {
         // Extract the Soroban metadata from the transaction metadata
         match result_meta {
             TransactionMeta::V3(meta_v3) => self.extract_soroban_events(meta_v3),
+            TransactionMeta::V4(meta_v4) => self.extract_soroban_events_v4(meta_v4),
             _ => Err(SorobanHelperError::InvalidArgument(
-                "Transaction metadata is not in V3 format (not a Soroban transaction)".to_string(),
+                "Transaction metadata is not in V3 or V4 format (not a Soroban transaction)"
+                    .to_string(),
             )),
         }
     }
 
+    /// Extracts the Soroban diagnostic events from the transaction metadata
+    ///
+    /// Diagnostic events hold debugging information, such as the reason a contract
+    /// call failed. They are only recorded when enabled on the network's nodes.
+    ///
+    /// # Returns
+    ///
+    /// A vector of diagnostic events or an error if:
+    /// - The transaction metadata is not available
+    /// - The transaction metadata is not in V3 (protocol 22) or V4 (protocol 23+) format
+    /// - The Soroban metadata is not available
+    pub fn get_diagnostic_events(&self) -> Result<Vec<DiagnosticEvent>, SorobanHelperError> {
+        let result_meta = self.response.result_meta.as_ref().ok_or_else(|| {
+            SorobanHelperError::InvalidArgument("Transaction metadata not available".to_string())
+        })?;
+
+        let soroban_meta_missing =
+            || SorobanHelperError::InvalidArgument("Soroban metadata not available".to_string());
+        match result_meta {
+            TransactionMeta::V3(meta_v3) => Ok(meta_v3
+                .soroban_meta
+                .as_ref()
+                .ok_or_else(soroban_meta_missing)?
+                .diagnostic_events
+                .to_vec()),
+            // From protocol 23, diagnostic events are reported for the whole transaction
+            TransactionMeta::V4(meta_v4) => {
+                meta_v4
+                    .soroban_meta
+                    .as_ref()
+                    .ok_or_else(soroban_meta_missing)?;
+                Ok(meta_v4.diagnostic_events.to_vec())
+            }
+            _ => Err(SorobanHelperError::InvalidArgument(
+                "Transaction metadata is not in V3 or V4 format (not a Soroban transaction)"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Extracts the Soroban transaction events, with their topics and data decoded
+    ///
+    /// # Returns
+    ///
+    /// The decoded contract events or an error if they can't be extracted, see `get_events`
+    pub fn get_decoded_events(&self) -> Result<Vec<DecodedEvent>, SorobanHelperError> {
+        Ok(self.get_events()?.iter().map(DecodedEvent::from).collect())
+    }
+
     /// Helper method to extract the Soroban return value from a TransactionMetaV3
     fn extract_soroban_return_value(
         &self,
@@ -99,8 +166,47 @@ impl SorobanTransactionResponse {
         Ok(events)
     }
 
+    /// Helper method to extract the Soroban return value from a TransactionMetaV4
+    fn extract_soroban_return_value_v4(
+        &self,
+        meta_v4: &TransactionMetaV4,
+    ) -> Result<ScVal, SorobanHelperError> {
+        meta_v4
+            .soroban_meta
+            .as_ref()
+            .and_then(|soroban_meta| soroban_meta.return_value.clone())
+            .ok_or_else(|| {
+                SorobanHelperError::InvalidArgument(
+                    "Soroban return value not available".to_string(),
+                )
+            })
+    }
+
+    /// Helper method to extract the Soroban events from a TransactionMetaV4
+    ///
+    /// From protocol 23, contract events are reported per operation.
+    fn extract_soroban_events_v4(
+        &self,
+        meta_v4: &TransactionMetaV4,
+    ) -> Result<Vec<stellar_xdr::curr::ContractEvent>, SorobanHelperError> {
+        if meta_v4.soroban_meta.is_none() {
+            return Err(SorobanHelperError::InvalidArgument(
+                "Soroban metadata not available".to_string(),
+            ));
+        }
+
+        Ok(meta_v4
+            .operations
+            .iter()
+            .flat_map(|op| op.events.iter().cloned())
+            .collect())
+    }
+
     /// Extracts the full Soroban transaction metadata
     ///
+    /// Only available for V3 metadata, as V4 metadata splits it between the
+    /// transaction and its operations.
+    ///
     /// # Returns
     ///
     /// The Soroban transaction metadata or an error if:
@@ -140,8 +246,9 @@ mod tests {
 
     use super::*;
     use stellar_xdr::curr::{
-        ContractEvent, ExtensionPoint, LedgerEntryChanges, SorobanTransactionMetaExt,
-        TransactionResult, TransactionResultExt, TransactionResultResult, VecM,
+        ContractEvent, ExtensionPoint, LedgerEntryChanges, OperationMetaV2,
+        SorobanTransactionMetaExt, SorobanTransactionMetaV2, TransactionResult,
+        TransactionResultExt, TransactionResultResult, VecM,
     };
 
     #[test]
@@ -155,6 +262,18 @@ mod tests {
         assert_eq!(return_value, ScVal::U32(42));
     }
 
+    #[test]
+    fn test_get_return_value_as() {
+        let response = create_mock_response(Some(ScVal::U32(42)));
+        let soroban_response = SorobanTransactionResponse::new(response);
+
+        assert_eq!(soroban_response.get_return_value_as::<u32>().unwrap(), 42);
+        assert!(matches!(
+            soroban_response.get_return_value_as::<bool>(),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
     #[test]
     fn test_get_return_value_no_meta() {
         // Create a mock GetTransactionResponse with no transaction meta
@@ -177,6 +296,35 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_meta_v4() {
+        let event = create_mock_contract_event();
+        let meta_v4 = TransactionMetaV4 {
+            ext: ExtensionPoint::V0,
+            tx_changes_before: LedgerEntryChanges::default(),
+            operations: vec![OperationMetaV2 {
+                ext: ExtensionPoint::V0,
+                changes: LedgerEntryChanges::default(),
+                events: vec![event.clone()].try_into().unwrap(),
+            }]
+            .try_into()
+            .unwrap(),
+            tx_changes_after: LedgerEntryChanges::default(),
+            soroban_meta: Some(SorobanTransactionMetaV2 {
+                ext: SorobanTransactionMetaExt::V0,
+                return_value: Some(ScVal::U32(42)),
+            }),
+            events: VecM::default(),
+            diagnostic_events: VecM::default(),
+        };
+        let mut response = create_mock_response(None);
+        response.result_meta = Some(TransactionMeta::V4(meta_v4));
+        let soroban_response = SorobanTransactionResponse::new(response);
+
+        assert_eq!(soroban_response.get_return_value().unwrap(), ScVal::U32(42));
+        assert_eq!(soroban_response.get_events().unwrap(), vec![event]);
+    }
+
     // Helper function to create a mock GetTransactionResponse
     fn create_mock_response(return_value: Option<ScVal>) -> GetTransactionResponse {
         // Create a mock Soroban transaction meta
@@ -217,6 +365,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_diagnostic_events() {
+        let diagnostic_event = DiagnosticEvent {
+            in_successful_contract_call: false,
+            event: create_mock_contract_event(),
+        };
+        let mut response = create_mock_response(None);
+        if let Some(TransactionMeta::V3(meta_v3)) = response.result_meta.as_mut() {
+            meta_v3.soroban_meta.as_mut().unwrap().diagnostic_events =
+                vec![diagnostic_event.clone()].try_into().unwrap();
+        }
+        let soroban_response = SorobanTransactionResponse::new(response);
+
+        assert_eq!(
+            soroban_response.get_diagnostic_events().unwrap(),
+            vec![diagnostic_event]
+        );
+
+        let mut response = create_mock_response(None);
+        response.result_meta = None;
+        assert!(SorobanTransactionResponse::new(response)
+            .get_diagnostic_events()
+            .is_err());
+    }
+
     #[test]
     fn test_get_events_success() {
         // Create a mock ContractEvent
@@ -233,6 +406,10 @@ mod tests {
         // Test extracting the events
         let extracted_events = soroban_response.get_events().unwrap();
         assert_eq!(extracted_events.len(), 2);
+
+        let decoded_events = soroban_response.get_decoded_events().unwrap();
+        assert_eq!(decoded_events.len(), 2);
+        assert_eq!(decoded_events[0], DecodedEvent::from(&event1));
     }
 
     #[test]
@@ -5,10 +5,21 @@
 //! the official Stellar RPC client.
 //!
 use crate::error::SorobanHelperError;
+use crate::event::EventFilter;
 use crate::SorobanTransactionResponse;
+use jsonrpsee_core::client::ClientT;
+use jsonrpsee_core::params::ObjectParams;
+use std::future::Future;
+use std::str::FromStr;
 use stellar_rpc_client::Client;
-use stellar_rpc_client::SimulateTransactionResponse;
-use stellar_xdr::curr::{AccountEntry, TransactionEnvelope};
+use stellar_rpc_client::{
+    EventStart, EventType, GetEventsResponse, GetHealthResponse, GetLatestLedgerResponse,
+    GetLedgerEntriesResponse, SendTransactionResponse, SimulateTransactionResponse,
+};
+use stellar_xdr::curr::{
+    AccountEntry, Hash, LedgerKey, Limits, ReadXdr, TransactionEnvelope, TransactionResult,
+    WriteXdr,
+};
 
 /// Interface for RPC operations with Soroban servers.
 ///
@@ -22,10 +33,32 @@ pub trait RpcClient: Send + Sync {
         &self,
         tx_envelope: &TransactionEnvelope,
     ) -> Result<SimulateTransactionResponse, SorobanHelperError>;
-    async fn send_transaction_polling(
+    /// Submits a transaction to the network without waiting for the result.
+    ///
+    /// Returns the transaction hash, or `SorobanHelperError::TransactionFailedWithCode`
+    /// if the transaction was rejected, e.g. with `txBadSeq`.
+    async fn send_transaction(
         &self,
         tx_envelope: &TransactionEnvelope,
+    ) -> Result<Hash, SorobanHelperError>;
+    /// Polls a submitted transaction until it is included in a ledger.
+    ///
+    /// Returns `SorobanHelperError::TransactionFailedWithCode` if the transaction failed.
+    async fn get_transaction_polling(
+        &self,
+        hash: &Hash,
     ) -> Result<SorobanTransactionResponse, SorobanHelperError>;
+    async fn get_ledger_entries(
+        &self,
+        keys: &[LedgerKey],
+    ) -> Result<GetLedgerEntriesResponse, SorobanHelperError>;
+    async fn get_latest_ledger(&self) -> Result<GetLatestLedgerResponse, SorobanHelperError>;
+    async fn get_events(
+        &self,
+        start_ledger: u32,
+        filter: &EventFilter,
+    ) -> Result<GetEventsResponse, SorobanHelperError>;
+    async fn get_health(&self) -> Result<GetHealthResponse, SorobanHelperError>;
 }
 
 /// Implementation of the RPC client using the official Stellar RPC client.
@@ -59,6 +92,88 @@ impl ExternalRpcClient {
     }
 }
 
+/// Returns whether the request failed before being processed by the server.
+fn is_transport_error(err: &jsonrpsee_core::Error) -> bool {
+    matches!(
+        err,
+        jsonrpsee_core::Error::Transport(_)
+            | jsonrpsee_core::Error::RequestTimeout
+            | jsonrpsee_core::Error::RestartNeeded(_)
+    )
+}
+
+/// Converts an error of the Stellar RPC client.
+///
+/// Requests that did not reach the server fail with
+/// `SorobanHelperError::TransportFailed`, every other error with
+/// `SorobanHelperError::NetworkRequestFailed`.
+fn rpc_error(err: stellar_rpc_client::Error) -> SorobanHelperError {
+    match &err {
+        stellar_rpc_client::Error::JsonRpc(e) if is_transport_error(e) => {
+            SorobanHelperError::TransportFailed(format!("Error: {}", err))
+        }
+        _ => SorobanHelperError::NetworkRequestFailed(format!("Error: {}", err)),
+    }
+}
+
+/// Returns the hash of a sent transaction, or the error it was rejected with.
+///
+/// A transaction rejected by the server fails with
+/// `SorobanHelperError::TransactionFailedWithCode`, e.g. `txBadSeq`.
+fn sent_transaction_hash(response: SendTransactionResponse) -> Result<Hash, SorobanHelperError> {
+    match response.status.as_str() {
+        "PENDING" | "DUPLICATE" => Ok(Hash::from_str(&response.hash)?),
+        "ERROR" => {
+            let result_xdr = response.error_result_xdr.ok_or_else(|| {
+                SorobanHelperError::NetworkRequestFailed(
+                    "Error: missing result of the rejected transaction".to_string(),
+                )
+            })?;
+            let result = TransactionResult::from_xdr_base64(result_xdr, Limits::none())?;
+            Err(SorobanHelperError::from_transaction_result(&result.result))
+        }
+        "TRY_AGAIN_LATER" => Err(SorobanHelperError::TransportFailed(
+            "Error: the server asked to try again later".to_string(),
+        )),
+        status => Err(SorobanHelperError::NetworkRequestFailed(format!(
+            "Error: unexpected transaction status {}",
+            status
+        ))),
+    }
+}
+
+/// Runs an RPC request.
+///
+/// With the `logging` feature enabled, the request runs within an `rpc` tracing
+/// span recording the method name, the duration and the resulting status.
+#[cfg_attr(not(feature = "logging"), allow(unused_variables))]
+async fn traced<T>(
+    method: &'static str,
+    request: impl Future<Output = Result<T, SorobanHelperError>>,
+) -> Result<T, SorobanHelperError> {
+    #[cfg(feature = "logging")]
+    {
+        use tracing::Instrument;
+
+        let span = tracing::debug_span!(
+            "rpc",
+            method,
+            duration_ms = tracing::field::Empty,
+            status = tracing::field::Empty,
+        );
+        let start = std::time::Instant::now();
+        let result = request.instrument(span.clone()).await;
+
+        let status = if result.is_ok() { "ok" } else { "error" };
+        span.record("duration_ms", start.elapsed().as_millis() as u64);
+        span.record("status", status);
+        tracing::debug!(parent: &span, method, status, "RPC request completed");
+        result
+    }
+    #[cfg(not(feature = "logging"))]
+    request.await
+}
+
 #[async_trait::async_trait]
 impl RpcClient for ExternalRpcClient {
     /// Retrieves account information from the Stellar network.
@@ -71,10 +186,10 @@ impl RpcClient for ExternalRpcClient {
     ///
     /// The account entry information or an error if the account could not be retrieved
     async fn get_account(&self, account_id: &str) -> Result<AccountEntry, SorobanHelperError> {
-        self.client
-            .get_account(account_id)
-            .await
-            .map_err(|e| SorobanHelperError::NetworkRequestFailed(format!("Error: {}", e)))
+        traced("get_account", async {
+            self.client.get_account(account_id).await.map_err(rpc_error)
+        })
+        .await
     }
 
     /// Simulates a transaction without submitting it to the network.
@@ -90,13 +205,16 @@ impl RpcClient for ExternalRpcClient {
         &self,
         tx_envelope: &TransactionEnvelope,
     ) -> Result<SimulateTransactionResponse, SorobanHelperError> {
-        self.client
-            .simulate_transaction_envelope(tx_envelope, None)
-            .await
-            .map_err(|e| SorobanHelperError::NetworkRequestFailed(format!("Error: {}", e)))
+        traced("simulate_transaction_envelope", async {
+            self.client
+                .simulate_transaction_envelope(tx_envelope, None)
+                .await
+                .map_err(rpc_error)
+        })
+        .await
     }
 
-    /// Submits a transaction to the network and polls until completion.
+    /// Submits a transaction to the network without waiting for the result.
     ///
     /// # Parameters
     ///
@@ -104,22 +222,152 @@ impl RpcClient for ExternalRpcClient {
     ///
     /// # Returns
     ///
-    /// The transaction response or an error if the transaction failed
-    async fn send_transaction_polling(
+    /// The transaction hash, or an error if the transaction was rejected. A
+    /// transaction already known by the server is not rejected.
+    async fn send_transaction(
         &self,
         tx_envelope: &TransactionEnvelope,
+    ) -> Result<Hash, SorobanHelperError> {
+        traced("send_transaction", async {
+            let mut params = ObjectParams::new();
+            params
+                .insert("transaction", tx_envelope.to_xdr_base64(Limits::none())?)
+                .map_err(|e| SorobanHelperError::XdrEncodingFailed(e.to_string()))?;
+
+            // The request is sent directly, as the Stellar RPC client only keeps a
+            // description of the result of rejected transactions.
+            let response: SendTransactionResponse = self
+                .client
+                .client()
+                .request("sendTransaction", params)
+                .await
+                .map_err(|e| rpc_error(e.into()))?;
+
+            sent_transaction_hash(response)
+        })
+        .await
+    }
+
+    /// Polls a submitted transaction until it is included in a ledger.
+    ///
+    /// # Parameters
+    ///
+    /// * `hash` - The hash of the submitted transaction
+    ///
+    /// # Returns
+    ///
+    /// The transaction response or an error if the transaction failed
+    async fn get_transaction_polling(
+        &self,
+        hash: &Hash,
     ) -> Result<SorobanTransactionResponse, SorobanHelperError> {
-        self.client
-            .send_transaction_polling(tx_envelope)
-            .await
-            .map(SorobanTransactionResponse::from)
-            .map_err(|e| SorobanHelperError::NetworkRequestFailed(format!("Error: {}", e)))
+        traced("get_transaction_polling", async {
+            match self.client.get_transaction_polling(hash, None).await {
+                Ok(response) => Ok(SorobanTransactionResponse::from(response)),
+                // The Stellar RPC client only keeps a description of the result,
+                // so the failed transaction is fetched again
+                Err(stellar_rpc_client::Error::TransactionSubmissionFailed(_)) => {
+                    let response = self.client.get_transaction(hash).await.map_err(rpc_error)?;
+                    Err(match response.result {
+                        Some(result) => SorobanHelperError::from_transaction_result(&result.result),
+                        None => SorobanHelperError::TransactionFailed(format!(
+                            "No result available for transaction {}",
+                            hash
+                        )),
+                    })
+                }
+                Err(e) => Err(rpc_error(e)),
+            }
+        })
+        .await
+    }
+
+    /// Retrieves ledger entries (e.g. contract storage) for the given keys.
+    ///
+    /// # Parameters
+    ///
+    /// * `keys` - The ledger keys to look up
+    ///
+    /// # Returns
+    ///
+    /// The ledger entries found for the given keys or an error if the request failed
+    async fn get_ledger_entries(
+        &self,
+        keys: &[LedgerKey],
+    ) -> Result<GetLedgerEntriesResponse, SorobanHelperError> {
+        traced("get_ledger_entries", async {
+            self.client
+                .get_ledger_entries(keys)
+                .await
+                .map_err(rpc_error)
+        })
+        .await
+    }
+
+    /// Retrieves the latest ledger known by the RPC server.
+    ///
+    /// # Returns
+    ///
+    /// The latest ledger sequence and protocol version or an error if the request failed
+    async fn get_latest_ledger(&self) -> Result<GetLatestLedgerResponse, SorobanHelperError> {
+        traced("get_latest_ledger", async {
+            self.client.get_latest_ledger().await.map_err(rpc_error)
+        })
+        .await
+    }
+
+    /// Retrieves contract events emitted from the given ledger onwards.
+    ///
+    /// # Parameters
+    ///
+    /// * `start_ledger` - The first ledger to include in the search
+    /// * `filter` - The contracts and topics to match
+    ///
+    /// # Returns
+    ///
+    /// The matching events or an error if the request failed
+    async fn get_events(
+        &self,
+        start_ledger: u32,
+        filter: &EventFilter,
+    ) -> Result<GetEventsResponse, SorobanHelperError> {
+        let contract_ids: Vec<String> = filter
+            .contract_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        traced("get_events", async {
+            self.client
+                .get_events(
+                    EventStart::Ledger(start_ledger),
+                    Some(EventType::Contract),
+                    &contract_ids,
+                    &filter.topics,
+                    filter.limit,
+                )
+                .await
+                .map_err(rpc_error)
+        })
+        .await
+    }
+
+    /// Retrieves the health status of the RPC server.
+    ///
+    /// # Returns
+    ///
+    /// The node status and the range of ledgers it retains or an error if the request failed
+    async fn get_health(&self) -> Result<GetHealthResponse, SorobanHelperError> {
+        traced("get_health", async {
+            self.client.get_health().await.map_err(rpc_error)
+        })
+        .await
     }
 }
 
 #[cfg(test)]
 pub mod test {
     use crate::mock::{mock_signer1, mock_transaction_envelope};
+    use stellar_xdr::curr::{TransactionResultExt, TransactionResultResult};
 
     use super::*;
 
@@ -134,6 +382,59 @@ pub mod test {
         assert_eq!(client.client.base_url(), "http://test.com:80/");
     }
 
+    #[test]
+    fn test_rpc_error() {
+        let error = rpc_error(stellar_rpc_client::Error::JsonRpc(
+            jsonrpsee_core::Error::RequestTimeout,
+        ));
+        assert!(matches!(error, SorobanHelperError::TransportFailed(_)));
+
+        let error = rpc_error(stellar_rpc_client::Error::TransactionSubmissionTimeout);
+        assert!(matches!(error, SorobanHelperError::NetworkRequestFailed(_)));
+    }
+
+    #[test]
+    fn test_sent_transaction_hash() {
+        let response = |status: &str, error_result_xdr: Option<String>| SendTransactionResponse {
+            hash: hex::encode([1u8; 32]),
+            status: status.to_string(),
+            error_result_xdr,
+            latest_ledger: 1,
+            latest_ledger_close_time: 0,
+        };
+
+        assert_eq!(
+            sent_transaction_hash(response("PENDING", None)).unwrap(),
+            Hash([1; 32])
+        );
+        assert_eq!(
+            sent_transaction_hash(response("DUPLICATE", None)).unwrap(),
+            Hash([1; 32])
+        );
+        assert!(matches!(
+            sent_transaction_hash(response("TRY_AGAIN_LATER", None)),
+            Err(SorobanHelperError::TransportFailed(_))
+        ));
+
+        let bad_seq = TransactionResult {
+            fee_charged: 100,
+            result: TransactionResultResult::TxBadSeq,
+            ext: TransactionResultExt::V0,
+        };
+        let error = sent_transaction_hash(response(
+            "ERROR",
+            Some(bad_seq.to_xdr_base64(Limits::none()).unwrap()),
+        ))
+        .unwrap_err();
+        assert_eq!(
+            error,
+            SorobanHelperError::TransactionFailedWithCode {
+                code: "txBadSeq".to_string(),
+                detail: String::new(),
+            }
+        );
+    }
+
     #[tokio::test]
     async fn test_get_account_error() {
         let client = ExternalRpcClient::new("https://test.com").unwrap();
@@ -142,7 +443,7 @@ pub mod test {
         assert!(res.is_err());
         assert!(matches!(
             res.err().unwrap(),
-            SorobanHelperError::NetworkRequestFailed(_)
+            SorobanHelperError::NetworkRequestFailed(_) | SorobanHelperError::TransportFailed(_)
         ));
     }
 
@@ -164,15 +465,84 @@ pub mod test {
     }
 
     #[tokio::test]
-    async fn test_send_transaction_polling_error() {
+    async fn test_send_transaction_error() {
         let client = ExternalRpcClient::new("https://soroban-testnet.stellar.org").unwrap();
         let account_id = mock_signer1().account_id();
         let transaction_envelope = mock_transaction_envelope(account_id);
-        let res = client.send_transaction_polling(&transaction_envelope).await;
+        let res = client.send_transaction(&transaction_envelope).await;
         assert!(res.is_err());
         assert!(matches!(
             res.err().unwrap(),
             SorobanHelperError::NetworkRequestFailed(_)
+                | SorobanHelperError::TransportFailed(_)
+                | SorobanHelperError::TransactionFailedWithCode { .. }
         ));
     }
+
+    #[cfg(feature = "logging")]
+    #[test]
+    fn test_get_account_span() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// Records the name and `method` field of every span created
+        #[derive(Clone, Default)]
+        struct CapturingSubscriber {
+            spans: Arc<Mutex<Vec<(String, String)>>>,
+        }
+
+        struct MethodVisitor(String);
+
+        impl Visit for MethodVisitor {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                if field.name() == "method" {
+                    self.0 = value.to_string();
+                }
+            }
+
+            fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+        }
+
+        impl Subscriber for CapturingSubscriber {
+            fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut visitor = MethodVisitor(String::new());
+                span.record(&mut visitor);
+                let mut spans = self.spans.lock().unwrap();
+                spans.push((span.metadata().name().to_string(), visitor.0));
+                Id::from_u64(spans.len() as u64)
+            }
+
+            fn record(&self, _span: &Id, _values: &Record<'_>) {}
+            fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+            fn event(&self, _event: &Event<'_>) {}
+            fn enter(&self, _span: &Id) {}
+            fn exit(&self, _span: &Id) {}
+        }
+
+        let subscriber = CapturingSubscriber::default();
+        let spans = subscriber.spans.clone();
+        tracing::subscriber::with_default(subscriber, || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                let client = ExternalRpcClient::new("https://test.com").unwrap();
+                let account_id =
+                    "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+                assert!(client.get_account(account_id).await.is_err());
+            });
+        });
+
+        assert!(spans
+            .lock()
+            .unwrap()
+            .contains(&("rpc".to_string(), "get_account".to_string())));
+    }
 }
@@ -1,17 +1,71 @@
+//! # Soroban Value Conversions
+//!
+//! This module provides conversions between native Rust values and `ScVal`, along
+//! with helpers to build symbols and contract enum variants.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use soroban_rs::{scval, xdr::ScVal};
+//!
+//! // `Status::Active`
+//! let active = scval::unit_variant("Active").unwrap();
+//! // `DataKey::Counter(1)`
+//! let counter = scval::data_variant("Counter", vec![ScVal::U32(1)]).unwrap();
+//! ```
+use std::collections::BTreeMap;
+use std::fmt;
 use std::time::Duration;
 
 use crate::SorobanHelperError;
 use stellar_xdr::curr::{
-    AccountId, BytesM, Duration as XDRDuration, ScAddress, ScBytes, ScString, ScVal, ScVec,
-    StringM, VecM,
+    AccountId, BytesM, Duration as XDRDuration, Int128Parts, Int256Parts, ScAddress, ScBytes,
+    ScError, ScMap, ScMapEntry, ScString, ScSymbol, ScVal, ScVec, StringM, UInt128Parts,
+    UInt256Parts, VecM,
 };
 
+/// Maximum length of a Soroban symbol.
+pub const MAX_SYMBOL_LENGTH: usize = 32;
+
 /// A trait for converting native rust values into a `ScVal`.
 pub trait IntoScVal {
     fn try_into_val(&self) -> Result<ScVal, SorobanHelperError>;
     fn into_val(self) -> ScVal;
 }
 
+/// Marker for the types that can be elements of a `Vec` converted into an `ScVal::Vec`.
+///
+/// `u8` is the only `IntoScVal` type left out, so that `Vec<u8>` can be
+/// converted into `ScVal::Bytes` instead. Implement it along with `IntoScVal`
+/// to convert vectors of your own types.
+pub trait VecElement: IntoScVal {}
+
+macro_rules! impl_vec_element {
+    ($($ty:ty),+) => {
+        $(impl VecElement for $ty {})+
+    };
+}
+
+impl_vec_element!(
+    AccountId, ScAddress, u32, u64, u128, i32, i64, i128, u16, i8, i16, bool, String, [u8; 32],
+    Duration, ScVal, SymbolVal, U256Bytes, I256Bytes
+);
+impl VecElement for Vec<u8> {}
+impl<T: VecElement> VecElement for Vec<T> {}
+impl<K: IntoScVal + Ord, V: IntoScVal> VecElement for BTreeMap<K, V> {}
+impl<T: IntoScVal> VecElement for Option<T> {}
+
+/// A trait for decoding a `ScVal`, e.g. a contract return value, into a native rust value.
+pub trait FromScVal: Sized {
+    /// Decodes `val` into `Self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::ConversionError` if `val` is not the `ScVal`
+    /// variant matching `Self`
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError>;
+}
+
 /// Converts a Stellar `AccountId` into an `ScVal::Address` containing an account.
 impl IntoScVal for AccountId {
     fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
@@ -23,6 +77,17 @@ impl IntoScVal for AccountId {
     }
 }
 
+/// Converts a contract or account address into an `ScVal::Address`.
+impl IntoScVal for ScAddress {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(ScVal::Address(self.clone()))
+    }
+
+    fn into_val(self) -> ScVal {
+        ScVal::Address(self)
+    }
+}
+
 /// Converts a 32-bit unsigned integer into an `ScVal::U32`.
 impl IntoScVal for u32 {
     fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
@@ -67,6 +132,81 @@ impl IntoScVal for i64 {
     }
 }
 
+/// Converts a 128-bit unsigned integer into an `ScVal::U128`, e.g. a token amount.
+impl IntoScVal for u128 {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(self.into_val())
+    }
+
+    fn into_val(self) -> ScVal {
+        ScVal::U128(UInt128Parts {
+            hi: (self >> 64) as u64,
+            lo: self as u64,
+        })
+    }
+}
+
+/// Converts a 128-bit signed integer into an `ScVal::I128`, e.g. a token amount.
+impl IntoScVal for i128 {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(self.into_val())
+    }
+
+    fn into_val(self) -> ScVal {
+        ScVal::I128(Int128Parts {
+            hi: (self >> 64) as i64,
+            lo: self as u64,
+        })
+    }
+}
+
+/// Converts a 8-bit unsigned integer into an `ScVal::U32`.
+///
+/// Soroban has no 8-bit value type, so the integer is intentionally widened
+/// to the smallest type holding it. The conversion is lossless.
+impl IntoScVal for u8 {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(ScVal::U32(u32::from(*self)))
+    }
+
+    fn into_val(self) -> ScVal {
+        ScVal::U32(u32::from(self))
+    }
+}
+
+/// Converts a 16-bit unsigned integer into an `ScVal::U32`, widening it losslessly.
+impl IntoScVal for u16 {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(ScVal::U32(u32::from(*self)))
+    }
+
+    fn into_val(self) -> ScVal {
+        ScVal::U32(u32::from(self))
+    }
+}
+
+/// Converts a 8-bit signed integer into an `ScVal::I32`, widening it losslessly.
+impl IntoScVal for i8 {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(ScVal::I32(i32::from(*self)))
+    }
+
+    fn into_val(self) -> ScVal {
+        ScVal::I32(i32::from(self))
+    }
+}
+
+/// Converts a 16-bit signed integer into an `ScVal::I32`, widening it losslessly.
+impl IntoScVal for i16 {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(ScVal::I32(i32::from(*self)))
+    }
+
+    fn into_val(self) -> ScVal {
+        ScVal::I32(i32::from(self))
+    }
+}
+
 /// Converts a boolean value into an `ScVal::Bool`.
 impl IntoScVal for bool {
     fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
@@ -123,21 +263,663 @@ impl IntoScVal for Duration {
     }
 }
 
-/// Converts a vector of `ScVal` objects into an `ScVal::Vec`.
-impl IntoScVal for Vec<ScVal> {
+/// Passes an `ScVal` through unchanged, so already converted values can be
+/// mixed with native ones, e.g. in a `Vec<ScVal>` or a tuple.
+impl IntoScVal for ScVal {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(self.clone())
+    }
+
+    fn into_val(self) -> ScVal {
+        self
+    }
+}
+
+/// Converts a byte vector into an `ScVal::Bytes` of the same length.
+impl IntoScVal for Vec<u8> {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        let bytes_m = BytesM::<{ u32::MAX }>::try_from(self).map_err(|_| {
+            SorobanHelperError::XdrEncodingFailed("Failed to convert Bytes to BytesM".to_string())
+        })?;
+        Ok(ScVal::Bytes(ScBytes::from(bytes_m)))
+    }
+
+    fn into_val(self) -> ScVal {
+        let bytes_m =
+            BytesM::<{ u32::MAX }>::try_from(self).expect("Failed to convert Bytes to BytesM");
+        ScVal::Bytes(ScBytes::from(bytes_m))
+    }
+}
+
+/// Converts a vector of values into an `ScVal::Vec`, converting each element.
+impl<T: VecElement> IntoScVal for Vec<T> {
     fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
-        let vec_m = VecM::try_from(self).map_err(|_| {
+        let items = self
+            .iter()
+            .map(IntoScVal::try_into_val)
+            .collect::<Result<Vec<ScVal>, _>>()?;
+        let vec_m = VecM::try_from(items).map_err(|_| {
             SorobanHelperError::XdrEncodingFailed("Failed to convert Vec to VecM".to_string())
         })?;
         Ok(ScVal::Vec(Some(ScVec::from(vec_m))))
     }
 
     fn into_val(self) -> ScVal {
-        let vec_m = VecM::try_from(self).expect("Failed to convert Vec to VecM");
+        let items: Vec<ScVal> = self.into_iter().map(IntoScVal::into_val).collect();
+        let vec_m = VecM::try_from(items).expect("Failed to convert Vec to VecM");
         ScVal::Vec(Some(ScVec::from(vec_m)))
     }
 }
 
+/// Converts a map into an `ScVal::Map`.
+///
+/// Soroban requires map entries to be sorted by key, so entries are ordered by
+/// their encoded keys rather than by `K`.
+impl<K: IntoScVal + Ord, V: IntoScVal> IntoScVal for BTreeMap<K, V> {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        let mut entries = self
+            .iter()
+            .map(|(key, val)| {
+                Ok(ScMapEntry {
+                    key: key.try_into_val()?,
+                    val: val.try_into_val()?,
+                })
+            })
+            .collect::<Result<Vec<ScMapEntry>, SorobanHelperError>>()?;
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+        if let Some(pair) = entries.windows(2).find(|pair| pair[0].key == pair[1].key) {
+            return Err(SorobanHelperError::InvalidArgument(format!(
+                "Duplicate map key {:?}",
+                pair[0].key
+            )));
+        }
+
+        let vec_m = VecM::try_from(entries).map_err(|_| {
+            SorobanHelperError::XdrEncodingFailed("Failed to convert Map to VecM".to_string())
+        })?;
+        Ok(ScVal::Map(Some(ScMap(vec_m))))
+    }
+
+    fn into_val(self) -> ScVal {
+        self.try_into_val()
+            .expect("Failed to convert BTreeMap to ScMap")
+    }
+}
+
+/// A Soroban symbol, e.g. an enum tag or a storage key.
+///
+/// Plain strings are converted into `ScVal::String`, this newtype is converted
+/// into an `ScVal::Symbol` instead.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SymbolVal(pub String);
+
+/// Converts a symbol into an `ScVal::Symbol`, validating it with `symbol`.
+impl IntoScVal for SymbolVal {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(ScVal::Symbol(symbol(&self.0)?))
+    }
+
+    fn into_val(self) -> ScVal {
+        self.try_into_val()
+            .expect("Failed to convert String to ScSymbol")
+    }
+}
+
+/// A 256-bit unsigned integer given as 32 big-endian bytes, i.e. the most
+/// significant byte first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U256Bytes(pub [u8; 32]);
+
+/// A 256-bit signed (two's complement) integer given as 32 big-endian bytes,
+/// i.e. the most significant byte, holding the sign bit, first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct I256Bytes(pub [u8; 32]);
+
+/// Splits 32 big-endian bytes into four big-endian 64-bit words, most significant first.
+fn u64_words(bytes: &[u8; 32]) -> [u64; 4] {
+    let mut words = [0u64; 4];
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(8)) {
+        *word = u64::from_be_bytes(chunk.try_into().expect("chunk of 8 bytes"));
+    }
+    words
+}
+
+/// Joins four 64-bit words, most significant first, into 32 big-endian bytes.
+fn be_bytes(words: [u64; 4]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (chunk, word) in bytes.chunks_exact_mut(8).zip(words) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    bytes
+}
+
+/// Converts a big-endian 256-bit unsigned integer into an `ScVal::U256`.
+impl IntoScVal for U256Bytes {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(self.into_val())
+    }
+
+    fn into_val(self) -> ScVal {
+        let [hi_hi, hi_lo, lo_hi, lo_lo] = u64_words(&self.0);
+        ScVal::U256(UInt256Parts {
+            hi_hi,
+            hi_lo,
+            lo_hi,
+            lo_lo,
+        })
+    }
+}
+
+/// Converts a big-endian 256-bit signed integer into an `ScVal::I256`.
+impl IntoScVal for I256Bytes {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        Ok(self.into_val())
+    }
+
+    fn into_val(self) -> ScVal {
+        let [hi_hi, hi_lo, lo_hi, lo_lo] = u64_words(&self.0);
+        ScVal::I256(Int256Parts {
+            hi_hi: hi_hi as i64,
+            hi_lo,
+            lo_hi,
+            lo_lo,
+        })
+    }
+}
+
+/// Converts an optional value into its `ScVal`, or `ScVal::Void` when `None`.
+///
+/// This is how the Soroban SDK encodes optional contract arguments.
+impl<T: IntoScVal> IntoScVal for Option<T> {
+    fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+        match self {
+            Some(v) => v.try_into_val(),
+            None => Ok(ScVal::Void),
+        }
+    }
+
+    fn into_val(self) -> ScVal {
+        match self {
+            Some(v) => v.into_val(),
+            None => ScVal::Void,
+        }
+    }
+}
+
+/// Implements `IntoScVal` for a tuple, encoded as an `ScVal::Vec` holding the
+/// elements in positional order, like Soroban tuple structs.
+macro_rules! impl_into_scval_for_tuple {
+    ($($name:ident : $idx:tt),+) => {
+        impl<$($name: IntoScVal),+> IntoScVal for ($($name,)+) {
+            fn try_into_val(&self) -> Result<ScVal, SorobanHelperError> {
+                vec![$(self.$idx.try_into_val()?),+].try_into_val()
+            }
+
+            fn into_val(self) -> ScVal {
+                vec![$(self.$idx.into_val()),+].into_val()
+            }
+        }
+
+        impl<$($name: IntoScVal),+> VecElement for ($($name,)+) {}
+    };
+}
+
+impl_into_scval_for_tuple!(A: 0, B: 1);
+impl_into_scval_for_tuple!(A: 0, B: 1, C: 2);
+impl_into_scval_for_tuple!(A: 0, B: 1, C: 2, D: 3);
+
+/// Builds the error returned when a `ScVal` doesn't hold the requested type.
+fn unexpected_val(expected: &str, val: &ScVal) -> SorobanHelperError {
+    SorobanHelperError::ConversionError(format!(
+        "Expected ScVal::{}, got ScVal::{}",
+        expected,
+        val.name()
+    ))
+}
+
+/// Decodes an `ScVal::U32` into a 32-bit unsigned integer.
+impl FromScVal for u32 {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::U32(v) => Ok(*v),
+            _ => Err(unexpected_val("U32", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::U64` into a 64-bit unsigned integer.
+impl FromScVal for u64 {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::U64(v) => Ok(*v),
+            _ => Err(unexpected_val("U64", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::I32` into a 32-bit signed integer.
+impl FromScVal for i32 {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::I32(v) => Ok(*v),
+            _ => Err(unexpected_val("I32", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::I64` into a 64-bit signed integer.
+impl FromScVal for i64 {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::I64(v) => Ok(*v),
+            _ => Err(unexpected_val("I64", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::U128` into a 128-bit unsigned integer.
+impl FromScVal for u128 {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::U128(parts) => Ok(((parts.hi as u128) << 64) | parts.lo as u128),
+            _ => Err(unexpected_val("U128", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::I128` into a 128-bit signed integer.
+impl FromScVal for i128 {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::I128(parts) => Ok(((parts.hi as i128) << 64) | parts.lo as i128),
+            _ => Err(unexpected_val("I128", val)),
+        }
+    }
+}
+
+/// Implements `FromScVal` for an integer narrower than 32 bits, decoded from the
+/// `ScVal` variant it is widened to by `IntoScVal`.
+///
+/// `u8` is left out, so that `Vec<u8>` can be decoded from `ScVal::Bytes` instead.
+macro_rules! impl_from_scval_for_small_integer {
+    ($($ty:ty: $variant:ident),+) => {
+        $(
+            impl FromScVal for $ty {
+                fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+                    match val {
+                        ScVal::$variant(v) => <$ty>::try_from(*v).map_err(|_| {
+                            SorobanHelperError::ConversionError(format!(
+                                "ScVal::{} value {} doesn't fit in a {}",
+                                stringify!($variant),
+                                v,
+                                stringify!($ty)
+                            ))
+                        }),
+                        _ => Err(unexpected_val(stringify!($variant), val)),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_from_scval_for_small_integer!(u16: U32, i8: I32, i16: I32);
+
+/// Decodes an `ScVal::Bool` into a boolean value.
+impl FromScVal for bool {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::Bool(v) => Ok(*v),
+            _ => Err(unexpected_val("Bool", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::String` into a Rust `String`.
+impl FromScVal for String {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::String(v) => v.to_utf8_string().map_err(|e| {
+                SorobanHelperError::ConversionError(format!("Invalid UTF-8 string: {}", e))
+            }),
+            _ => Err(unexpected_val("String", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::Address` into an account or contract address.
+impl FromScVal for ScAddress {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::Address(address) => Ok(address.clone()),
+            _ => Err(unexpected_val("Address", val)),
+        }
+    }
+}
+
+/// Returns the `ScVal` as is, for values without a native counterpart.
+impl FromScVal for ScVal {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        Ok(val.clone())
+    }
+}
+
+/// Decodes an `ScVal::Symbol` into a symbol.
+impl FromScVal for SymbolVal {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::Symbol(v) => Ok(SymbolVal(v.to_utf8_string_lossy())),
+            _ => Err(unexpected_val("Symbol", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::Bytes` into a byte vector.
+impl FromScVal for Vec<u8> {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::Bytes(v) => Ok(v.to_vec()),
+            _ => Err(unexpected_val("Bytes", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::Vec` into a vector, decoding each element.
+impl<T: FromScVal> FromScVal for Vec<T> {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::Vec(Some(v)) => v.iter().map(T::try_from_val).collect(),
+            _ => Err(unexpected_val("Vec", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::Map` into a map, decoding each key and value.
+impl<K: FromScVal + Ord, V: FromScVal> FromScVal for BTreeMap<K, V> {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::Map(Some(map)) => map
+                .iter()
+                .map(|entry| Ok((K::try_from_val(&entry.key)?, V::try_from_val(&entry.val)?)))
+                .collect(),
+            _ => Err(unexpected_val("Map", val)),
+        }
+    }
+}
+
+/// Decodes `ScVal::Void` into `None` and any other value into `Some`.
+impl<T: FromScVal> FromScVal for Option<T> {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::Void => Ok(None),
+            val => T::try_from_val(val).map(Some),
+        }
+    }
+}
+
+/// Implements `FromScVal` for a tuple, decoded from an `ScVal::Vec` holding
+/// exactly one element per tuple field.
+macro_rules! impl_from_scval_for_tuple {
+    ($len:literal; $($name:ident : $idx:tt),+) => {
+        impl<$($name: FromScVal),+> FromScVal for ($($name,)+) {
+            fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+                match val {
+                    ScVal::Vec(Some(v)) if v.len() == $len => {
+                        Ok(($($name::try_from_val(&v[$idx])?,)+))
+                    }
+                    _ => Err(unexpected_val(concat!("Vec of ", $len, " elements"), val)),
+                }
+            }
+        }
+    };
+}
+
+impl_from_scval_for_tuple!(2; A: 0, B: 1);
+impl_from_scval_for_tuple!(3; A: 0, B: 1, C: 2);
+impl_from_scval_for_tuple!(4; A: 0, B: 1, C: 2, D: 3);
+
+/// Decodes an `ScVal::U256` into its big-endian bytes.
+impl FromScVal for U256Bytes {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::U256(parts) => Ok(U256Bytes(be_bytes([
+                parts.hi_hi,
+                parts.hi_lo,
+                parts.lo_hi,
+                parts.lo_lo,
+            ]))),
+            _ => Err(unexpected_val("U256", val)),
+        }
+    }
+}
+
+/// Decodes an `ScVal::I256` into its big-endian bytes.
+impl FromScVal for I256Bytes {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        match val {
+            ScVal::I256(parts) => Ok(I256Bytes(be_bytes([
+                parts.hi_hi as u64,
+                parts.hi_lo,
+                parts.lo_hi,
+                parts.lo_lo,
+            ]))),
+            _ => Err(unexpected_val("I256", val)),
+        }
+    }
+}
+
+/// Creates a validated `ScSymbol`.
+///
+/// Soroban symbols are at most 32 characters long and only contain the
+/// characters `a-z`, `A-Z`, `0-9` and `_`.
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::InvalidArgument` if `name` is not a valid symbol
+pub fn symbol(name: &str) -> Result<ScSymbol, SorobanHelperError> {
+    if name.len() > MAX_SYMBOL_LENGTH {
+        return Err(SorobanHelperError::InvalidArgument(format!(
+            "Invalid symbol {}: longer than {} characters",
+            name, MAX_SYMBOL_LENGTH
+        )));
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '_'))
+    {
+        return Err(SorobanHelperError::InvalidArgument(format!(
+            "Invalid symbol {}: invalid character '{}'",
+            name, c
+        )));
+    }
+    let symbol = name.try_into().map_err(|e| {
+        SorobanHelperError::InvalidArgument(format!("Invalid symbol {}: {}", name, e))
+    })?;
+    Ok(ScSymbol(symbol))
+}
+
+/// Encodes a unit variant of a contract enum, e.g. `Status::Active`.
+///
+/// Unit variants are encoded as a vector holding only the variant name.
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::InvalidArgument` if `name` is not a valid symbol
+pub fn unit_variant(name: &str) -> Result<ScVal, SorobanHelperError> {
+    data_variant(name, vec![])
+}
+
+/// Encodes a variant with data of a contract enum, e.g. `DataKey::Balance(address)`.
+///
+/// Data variants are encoded as a vector holding the variant name followed by its payload.
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::InvalidArgument` if `name` is not a valid symbol
+pub fn data_variant(name: &str, payload: Vec<ScVal>) -> Result<ScVal, SorobanHelperError> {
+    let mut items = vec![ScVal::Symbol(symbol(name)?)];
+    items.extend(payload);
+    items.try_into_val()
+}
+
+/// Encodes a contract struct from its fields, e.g. `Allowance { amount, expiration_ledger }`.
+///
+/// `#[contracttype]` structs are encoded as a map keyed by the field names,
+/// sorted as Soroban requires.
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::InvalidArgument` if a field name is not a valid
+/// symbol or appears twice
+pub fn encode_struct_fields(fields: Vec<(&str, ScVal)>) -> Result<ScVal, SorobanHelperError> {
+    let mut entries = fields
+        .into_iter()
+        .map(|(name, val)| {
+            Ok(ScMapEntry {
+                key: ScVal::Symbol(symbol(name)?),
+                val,
+            })
+        })
+        .collect::<Result<Vec<ScMapEntry>, SorobanHelperError>>()?;
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+    if let Some(pair) = entries.windows(2).find(|pair| pair[0].key == pair[1].key) {
+        return Err(SorobanHelperError::InvalidArgument(format!(
+            "Duplicate struct field {:?}",
+            pair[0].key
+        )));
+    }
+
+    let vec_m = VecM::try_from(entries).map_err(|_| {
+        SorobanHelperError::XdrEncodingFailed("Failed to convert struct to ScMap".to_string())
+    })?;
+    Ok(ScVal::Map(Some(ScMap(vec_m))))
+}
+
+/// Decodes a variant of a contract enum, see `unit_variant` and `data_variant`.
+///
+/// # Returns
+///
+/// The variant name and its payload, empty for unit variants
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::ConversionError` if `val` is not a vector
+/// starting with the variant name
+pub fn decode_variant(val: &ScVal) -> Result<(String, Vec<ScVal>), SorobanHelperError> {
+    match val {
+        ScVal::Vec(Some(items)) => match items.split_first() {
+            Some((ScVal::Symbol(name), payload)) => {
+                Ok((name.to_utf8_string_lossy(), payload.to_vec()))
+            }
+            _ => Err(SorobanHelperError::ConversionError(
+                "Expected an enum variant name".to_string(),
+            )),
+        },
+        _ => Err(unexpected_val("Vec", val)),
+    }
+}
+
+/// Reads a field of a contract struct returned as a value, e.g. by a getter function.
+///
+/// `#[contracttype]` structs are encoded as a map keyed by the field names.
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::ConversionError` if `val` is not a map or
+/// doesn't hold `field`
+pub fn decode_struct_field(val: &ScVal, field: &str) -> Result<ScVal, SorobanHelperError> {
+    let map = match val {
+        ScVal::Map(Some(map)) => map,
+        _ => return Err(unexpected_val("Map", val)),
+    };
+    map.iter()
+        .find(
+            |entry| matches!(&entry.key, ScVal::Symbol(key) if key.as_slice() == field.as_bytes()),
+        )
+        .map(|entry| entry.val.clone())
+        .ok_or_else(|| {
+            SorobanHelperError::ConversionError(format!("Struct field {} not found", field))
+        })
+}
+
+/// Reads several fields of a contract struct, see `decode_struct_field`.
+///
+/// # Returns
+///
+/// The values of the fields, in the order of `fields`
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::ConversionError` if `val` is not a map or
+/// doesn't hold one of the fields
+pub fn decode_struct_fields(
+    val: &ScVal,
+    fields: &[&str],
+) -> Result<Vec<ScVal>, SorobanHelperError> {
+    fields
+        .iter()
+        .map(|field| decode_struct_field(val, field))
+        .collect()
+}
+
+/// An error returned by a contract function, e.g. the `E` of a function returning `Result<T, E>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractError {
+    /// An error defined by the contract, e.g. a `#[contracterror]` enum variant
+    Contract(u32),
+    /// An error raised by the host environment
+    Host(ScError),
+}
+
+impl From<ScError> for ContractError {
+    fn from(error: ScError) -> Self {
+        match error {
+            ScError::Contract(code) => Self::Contract(code),
+            error => Self::Host(error),
+        }
+    }
+}
+
+impl fmt::Display for ContractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Contract(code) => write!(f, "Contract error #{}", code),
+            Self::Host(error) => write!(f, "Host error {:?}", error),
+        }
+    }
+}
+
+/// Decodes the return value of a contract function returning `Result<T, E>`.
+///
+/// Successful results are encoded as the `T` value itself, while errors are
+/// encoded as an `ScVal::Error`.
+///
+/// # Returns
+///
+/// `Ok(value)` for a successful result, or `Err(error)` for an error returned by the contract
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::ConversionError` if `val` is neither an error
+/// nor a `T` value
+pub fn decode_result<T: FromScVal>(
+    val: &ScVal,
+) -> Result<Result<T, ContractError>, SorobanHelperError> {
+    match val {
+        ScVal::Error(error) => Ok(Err(error.clone().into())),
+        val => T::try_from_val(val).map(Ok),
+    }
+}
+
+/// Decodes the return value of a contract function returning `Result<T, E>`, see `decode_result`.
+impl<T: FromScVal> FromScVal for Result<T, ContractError> {
+    fn try_from_val(val: &ScVal) -> Result<Self, SorobanHelperError> {
+        decode_result(val)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +955,394 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_128_bit_integers_into_scval() {
+        assert_eq!(
+            (u128::MAX - 1).into_val(),
+            ScVal::U128(UInt128Parts {
+                hi: u64::MAX,
+                lo: u64::MAX - 1,
+            })
+        );
+        assert_eq!(
+            (-2i128).try_into_val().unwrap(),
+            ScVal::I128(Int128Parts {
+                hi: -1,
+                lo: u64::MAX - 1,
+            })
+        );
+        assert_eq!(
+            (1i128 << 64).into_val(),
+            ScVal::I128(Int128Parts { hi: 1, lo: 0 })
+        );
+    }
+
+    #[test]
+    fn test_address_into_scval() {
+        let address = ScAddress::Contract(stellar_xdr::curr::ContractId(stellar_xdr::curr::Hash(
+            [1; 32],
+        )));
+        assert_eq!(
+            address.try_into_val().unwrap(),
+            ScVal::Address(address.clone())
+        );
+    }
+
+    #[test]
+    fn test_option_into_scval() {
+        assert_eq!(Some(42u32).try_into_val().unwrap(), ScVal::U32(42));
+        assert_eq!(Some(42u32).into_val(), ScVal::U32(42));
+        assert_eq!(None::<u32>.try_into_val().unwrap(), ScVal::Void);
+        assert_eq!(None::<u32>.into_val(), ScVal::Void);
+    }
+
+    #[test]
+    fn test_tuple_into_scval() {
+        let expected = ScVal::Vec(Some(
+            vec![ScVal::U32(1), ScVal::Bool(true)].try_into().unwrap(),
+        ));
+        assert_eq!((1u32, true).into_val(), expected);
+        assert_eq!((1u32, true).try_into_val().unwrap(), expected);
+
+        match (1u32, -2i64, false, 3u64).into_val() {
+            ScVal::Vec(Some(items)) => assert_eq!(
+                items.to_vec(),
+                vec![
+                    ScVal::U32(1),
+                    ScVal::I64(-2),
+                    ScVal::Bool(false),
+                    ScVal::U64(3)
+                ]
+            ),
+            other => panic!("Expected ScVal::Vec, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_generic_vec_into_scval() {
+        let expected = ScVal::Vec(Some(
+            vec![ScVal::U32(1), ScVal::U32(2), ScVal::U32(3)]
+                .try_into()
+                .unwrap(),
+        ));
+        assert_eq!(vec![1u32, 2, 3].try_into_val().unwrap(), expected);
+        assert_eq!(vec![1u32, 2, 3].into_val(), expected);
+
+        let empty = ScVal::Vec(Some(VecM::default().into()));
+        assert_eq!(Vec::<u32>::new().try_into_val().unwrap(), empty);
+        assert_eq!(Vec::<u32>::new().into_val(), empty);
+    }
+
+    #[test]
+    fn test_btree_map_into_scval() {
+        let mut map = BTreeMap::new();
+        map.insert("b".to_string(), 2u32);
+        map.insert("a".to_string(), 1u32);
+
+        match map.try_into_val().unwrap() {
+            ScVal::Map(Some(ScMap(entries))) => {
+                assert_eq!(entries.len(), 2);
+                assert_eq!(entries[0].key, "a".to_string().into_val());
+                assert_eq!(entries[0].val, ScVal::U32(1));
+                assert_eq!(entries[1].key, "b".to_string().into_val());
+                assert_eq!(entries[1].val, ScVal::U32(2));
+            }
+            other => panic!("Expected ScVal::Map, got {:?}", other),
+        }
+
+        // distinct keys with the same encoding
+        let mut map = BTreeMap::new();
+        map.insert(Some(ScVal::Void), 1u32);
+        map.insert(None, 2u32);
+        assert!(matches!(
+            map.try_into_val(),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_u256_round_trip() {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+
+        for bytes in [[0u8; 32], one, [0xff; 32]] {
+            let val = U256Bytes(bytes).into_val();
+            assert_eq!(U256Bytes::try_from_val(&val).unwrap(), U256Bytes(bytes));
+        }
+
+        assert_eq!(
+            U256Bytes(one).into_val(),
+            ScVal::U256(UInt256Parts {
+                hi_hi: 0,
+                hi_lo: 0,
+                lo_hi: 0,
+                lo_lo: 1
+            })
+        );
+        assert_eq!(
+            U256Bytes([0xff; 32]).into_val(),
+            ScVal::U256(UInt256Parts {
+                hi_hi: u64::MAX,
+                hi_lo: u64::MAX,
+                lo_hi: u64::MAX,
+                lo_lo: u64::MAX
+            })
+        );
+    }
+
+    #[test]
+    fn test_i256_round_trip() {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let mut max = [0xff; 32];
+        max[0] = 0x7f;
+
+        for bytes in [[0u8; 32], one, max, [0xff; 32]] {
+            let val = I256Bytes(bytes).try_into_val().unwrap();
+            assert_eq!(I256Bytes::try_from_val(&val).unwrap(), I256Bytes(bytes));
+        }
+
+        assert_eq!(
+            I256Bytes(max).into_val(),
+            ScVal::I256(Int256Parts {
+                hi_hi: i64::MAX,
+                hi_lo: u64::MAX,
+                lo_hi: u64::MAX,
+                lo_lo: u64::MAX
+            })
+        );
+        // -1
+        assert_eq!(
+            I256Bytes([0xff; 32]).into_val(),
+            ScVal::I256(Int256Parts {
+                hi_hi: -1,
+                hi_lo: u64::MAX,
+                lo_hi: u64::MAX,
+                lo_lo: u64::MAX
+            })
+        );
+        assert!(matches!(
+            I256Bytes::try_from_val(&U256Bytes(one).into_val()),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_symbol_into_scval() {
+        let val = SymbolVal("transfer".to_string()).try_into_val().unwrap();
+        assert_eq!(val, ScVal::Symbol(ScSymbol("transfer".try_into().unwrap())));
+
+        assert!(matches!(
+            SymbolVal("a".repeat(33)).try_into_val(),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            SymbolVal("not a symbol".to_string()).try_into_val(),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_byte_vec_into_scval() {
+        for bytes in [vec![], vec![0xab; 64], vec![7u8]] {
+            let expected = ScVal::Bytes(ScBytes(bytes.clone().try_into().unwrap()));
+            assert_eq!(bytes.try_into_val().unwrap(), expected);
+            assert_eq!(bytes.into_val(), expected);
+        }
+
+        // vectors of byte vectors are still vectors
+        match vec![vec![1u8, 2]].into_val() {
+            ScVal::Vec(Some(items)) => {
+                assert_eq!(items.len(), 1);
+                assert!(matches!(items[0], ScVal::Bytes(_)));
+            }
+            other => panic!("Expected ScVal::Vec, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_scval() {
+        assert_eq!(u32::try_from_val(&ScVal::U32(42)).unwrap(), 42);
+        assert_eq!(u64::try_from_val(&ScVal::U64(42)).unwrap(), 42);
+        assert_eq!(i32::try_from_val(&ScVal::I32(-42)).unwrap(), -42);
+        assert_eq!(i64::try_from_val(&ScVal::I64(-42)).unwrap(), -42);
+        assert!(bool::try_from_val(&ScVal::Bool(true)).unwrap());
+        assert_eq!(
+            String::try_from_val(&"hello".to_string().into_val()).unwrap(),
+            "hello"
+        );
+        let vec = vec![ScVal::U32(1), ScVal::Bool(false)];
+        assert_eq!(
+            Vec::<ScVal>::try_from_val(&vec.clone().into_val()).unwrap(),
+            vec
+        );
+        assert_eq!(
+            ScVal::try_from_val(&ScVal::Symbol(symbol("ok").unwrap())).unwrap(),
+            ScVal::Symbol(symbol("ok").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_collections_from_scval() {
+        let vec = vec![1u32, 2, 3];
+        assert_eq!(
+            Vec::<u32>::try_from_val(&vec.clone().into_val()).unwrap(),
+            vec
+        );
+
+        let bytes = vec![1u8, 2, 3];
+        assert_eq!(
+            Vec::<u8>::try_from_val(&bytes.clone().into_val()).unwrap(),
+            bytes
+        );
+
+        let map = BTreeMap::from([(1u32, true), (2, false)]);
+        assert_eq!(
+            BTreeMap::<u32, bool>::try_from_val(&map.clone().into_val()).unwrap(),
+            map
+        );
+
+        assert_eq!(Option::<u32>::try_from_val(&ScVal::Void).unwrap(), None);
+        assert_eq!(
+            Option::<u32>::try_from_val(&ScVal::U32(1)).unwrap(),
+            Some(1)
+        );
+
+        let tuple = (1u32, "a".to_string(), true);
+        assert_eq!(
+            <(u32, String, bool)>::try_from_val(&tuple.clone().into_val()).unwrap(),
+            tuple
+        );
+        assert!(matches!(
+            <(u32, bool)>::try_from_val(&tuple.into_val()),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+
+        let sym = SymbolVal("transfer".to_string());
+        assert_eq!(
+            SymbolVal::try_from_val(&sym.clone().into_val()).unwrap(),
+            sym
+        );
+    }
+
+    #[test]
+    fn test_encode_struct_fields() {
+        let val = encode_struct_fields(vec![("owner", ScVal::U32(1)), ("amount", ScVal::U32(2))])
+            .unwrap();
+        match &val {
+            ScVal::Map(Some(map)) => {
+                // entries are sorted by field name
+                assert_eq!(map[0].key, ScVal::Symbol(symbol("amount").unwrap()));
+                assert_eq!(map[1].key, ScVal::Symbol(symbol("owner").unwrap()));
+            }
+            other => panic!("Expected ScVal::Map, got {:?}", other),
+        }
+        assert_eq!(
+            decode_struct_fields(&val, &["owner", "amount"]).unwrap(),
+            vec![ScVal::U32(1), ScVal::U32(2)]
+        );
+
+        assert!(matches!(
+            encode_struct_fields(vec![("a", ScVal::Void), ("a", ScVal::Void)]),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_variant() {
+        assert_eq!(
+            decode_variant(&unit_variant("Active").unwrap()).unwrap(),
+            ("Active".to_string(), vec![])
+        );
+        assert_eq!(
+            decode_variant(&data_variant("Counter", vec![ScVal::U32(1)]).unwrap()).unwrap(),
+            ("Counter".to_string(), vec![ScVal::U32(1)])
+        );
+        assert!(matches!(
+            decode_variant(&ScVal::U32(1)),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+        assert!(matches!(
+            decode_variant(&vec![ScVal::U32(1)].into_val()),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_128_bit_integers_from_scval() {
+        for v in [0u128, 42, (1 << 64) + 5, u128::MAX] {
+            assert_eq!(u128::try_from_val(&v.into_val()).unwrap(), v);
+        }
+        for v in [0i128, -42, i128::MIN, i128::MAX, -(1 << 64)] {
+            assert_eq!(i128::try_from_val(&v.into_val()).unwrap(), v);
+        }
+        assert!(matches!(
+            u128::try_from_val(&ScVal::I128(Int128Parts { hi: 0, lo: 1 })),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_address_from_scval() {
+        let address = ScAddress::Contract(stellar_xdr::curr::ContractId(stellar_xdr::curr::Hash(
+            [7; 32],
+        )));
+        assert_eq!(
+            ScAddress::try_from_val(&ScVal::Address(address.clone())).unwrap(),
+            address
+        );
+        assert!(matches!(
+            ScAddress::try_from_val(&ScVal::Void),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_scval_mismatch() {
+        let err = u32::try_from_val(&ScVal::I32(42)).unwrap_err();
+        assert_eq!(
+            err,
+            SorobanHelperError::ConversionError("Expected ScVal::U32, got ScVal::I32".to_string())
+        );
+        assert!(matches!(
+            String::try_from_val(&ScVal::Void),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+        assert!(matches!(
+            Vec::<ScVal>::try_from_val(&ScVal::Vec(None)),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_small_integers_into_scval() {
+        assert_eq!(200u8.into_val(), ScVal::U32(200));
+        assert_eq!(u16::MAX.into_val(), ScVal::U32(65535));
+        assert_eq!((-5i16).into_val(), ScVal::I32(-5));
+        assert_eq!(i8::MIN.into_val(), ScVal::I32(-128));
+
+        assert_eq!(200u8.try_into_val().unwrap(), ScVal::U32(200));
+        assert_eq!((-5i16).try_into_val().unwrap(), ScVal::I32(-5));
+    }
+
+    #[test]
+    fn test_small_integers_from_scval() {
+        assert_eq!(u16::try_from_val(&u16::MAX.into_val()).unwrap(), u16::MAX);
+        assert_eq!(i16::try_from_val(&(-5i16).into_val()).unwrap(), -5);
+        assert_eq!(i8::try_from_val(&i8::MIN.into_val()).unwrap(), i8::MIN);
+
+        assert_eq!(
+            u16::try_from_val(&ScVal::U32(65536)).unwrap_err(),
+            SorobanHelperError::ConversionError(
+                "ScVal::U32 value 65536 doesn't fit in a u16".to_string()
+            )
+        );
+        assert!(matches!(
+            i8::try_from_val(&ScVal::U32(1)),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
     #[test]
     fn test_u32_into_scval() {
         let value: u32 = 42;
@@ -414,4 +1584,119 @@ mod tests {
         let result: Result<VecM<ScVal, { u32::MAX }>, _> = VecM::try_from(&small_vec);
         assert!(result.is_ok(), "Small vector should convert successfully");
     }
+
+    #[test]
+    fn test_symbol() {
+        assert_eq!(
+            symbol("transfer_1").unwrap(),
+            ScSymbol("transfer_1".try_into().unwrap())
+        );
+        assert!(symbol(&"a".repeat(MAX_SYMBOL_LENGTH)).is_ok());
+        assert!(matches!(
+            symbol(&"a".repeat(MAX_SYMBOL_LENGTH + 1)),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            symbol("not-valid"),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            symbol("spaces are invalid"),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_unit_variant() {
+        let scval = unit_variant("Active").unwrap();
+        match scval {
+            ScVal::Vec(Some(sc_vec)) => {
+                assert_eq!(sc_vec.len(), 1);
+                assert_eq!(sc_vec[0], ScVal::Symbol(symbol("Active").unwrap()));
+            }
+            _ => panic!("Expected ScVal::Vec, got {:?}", scval),
+        }
+
+        assert!(unit_variant("In-Active").is_err());
+    }
+
+    #[test]
+    fn test_data_variant() {
+        let scval = data_variant("Balance", vec![ScVal::U32(1), ScVal::Bool(true)]).unwrap();
+        match scval {
+            ScVal::Vec(Some(sc_vec)) => {
+                assert_eq!(sc_vec.len(), 3);
+                assert_eq!(sc_vec[0], ScVal::Symbol(symbol("Balance").unwrap()));
+                assert_eq!(sc_vec[1], ScVal::U32(1));
+                assert_eq!(sc_vec[2], ScVal::Bool(true));
+            }
+            _ => panic!("Expected ScVal::Vec, got {:?}", scval),
+        }
+
+        assert!(data_variant(&"B".repeat(33), vec![ScVal::U32(1)]).is_err());
+    }
+
+    #[test]
+    fn test_decode_struct_fields() {
+        let owner = ScVal::Address(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256([1; 32])),
+        )));
+        let val = ScVal::Map(Some(
+            vec![
+                ScMapEntry {
+                    key: ScVal::Symbol(symbol("amount").unwrap()),
+                    val: ScVal::U32(100),
+                },
+                ScMapEntry {
+                    key: ScVal::Symbol(symbol("owner").unwrap()),
+                    val: owner.clone(),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        ));
+
+        assert_eq!(
+            decode_struct_field(&val, "amount").unwrap(),
+            ScVal::U32(100)
+        );
+        assert_eq!(
+            decode_struct_fields(&val, &["owner", "amount"]).unwrap(),
+            vec![owner, ScVal::U32(100)]
+        );
+        assert!(matches!(
+            decode_struct_field(&val, "missing"),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+        assert!(matches!(
+            decode_struct_fields(&ScVal::U32(1), &["amount"]),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_result() {
+        assert_eq!(decode_result::<u32>(&ScVal::U32(7)).unwrap(), Ok(7));
+        assert_eq!(
+            decode_result::<u32>(&ScVal::Error(ScError::Contract(3))).unwrap(),
+            Err(ContractError::Contract(3))
+        );
+
+        let host_error = ScError::Budget(stellar_xdr::curr::ScErrorCode::ExceededLimit);
+        assert_eq!(
+            decode_result::<u32>(&ScVal::Error(host_error.clone())).unwrap(),
+            Err(ContractError::Host(host_error))
+        );
+
+        assert!(matches!(
+            decode_result::<u32>(&ScVal::Bool(true)),
+            Err(SorobanHelperError::ConversionError(_))
+        ));
+
+        assert_eq!(
+            Result::<u32, ContractError>::try_from_val(&ScVal::Error(ScError::Contract(3)))
+                .unwrap(),
+            Err(ContractError::Contract(3))
+        );
+    }
 }
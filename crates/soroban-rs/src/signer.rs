@@ -27,16 +27,104 @@
 //!     let signature = signer.sign_transaction(&tx, &env.network_id()).unwrap();
 //! }
 //! ```
-use crate::error::SorobanHelperError;
-use ed25519_dalek::{ed25519::signature::SignerMut, SigningKey};
-use sha2::{Digest, Sha256};
-use stellar_strkey::ed25519::PublicKey;
+use crate::{crypto, error::SorobanHelperError};
+use ed25519_dalek::{ed25519::signature::SignerMut, Signature, SigningKey};
+use std::sync::Arc;
+use stellar_strkey::ed25519::{PrivateKey, PublicKey};
 use stellar_xdr::curr::{
-    AccountId, DecoratedSignature, Hash, Limits, PublicKey as XDRPublicKey, Signature,
-    SignatureHint, Transaction, TransactionSignaturePayload,
-    TransactionSignaturePayloadTaggedTransaction, WriteXdr,
+    AccountId, DecoratedSignature, FeeBumpTransaction, Hash, PublicKey as XDRPublicKey,
+    Signature as XdrSignature, SignatureHint, Transaction,
+    TransactionSignaturePayloadTaggedTransaction,
 };
 
+/// A key able to sign transactions on behalf of an account.
+///
+/// Implemented by the in-process `Signer`, and meant to be implemented for
+/// keys held elsewhere, such as hardware wallets, HSMs or remote signing
+/// services, so they can be used by an `Account`.
+pub trait TransactionSigner: Send + Sync {
+    /// Returns the account ID of the signing key.
+    fn account_id(&self) -> AccountId;
+
+    /// Signs a 32-byte hash, e.g. the hash of a transaction signature payload.
+    ///
+    /// # Parameters
+    ///
+    /// * `hash` - The hash to sign
+    ///
+    /// # Returns
+    ///
+    /// The ed25519 signature of the hash, decorated with the hint of the signing key
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::SigningFailed` if the hash can't be signed
+    fn sign_hash(&self, hash: &[u8; 32]) -> Result<DecoratedSignature, SorobanHelperError>;
+}
+
+impl<T: TransactionSigner + ?Sized> TransactionSigner for Arc<T> {
+    fn account_id(&self) -> AccountId {
+        (**self).account_id()
+    }
+
+    fn sign_hash(&self, hash: &[u8; 32]) -> Result<DecoratedSignature, SorobanHelperError> {
+        (**self).sign_hash(hash)
+    }
+}
+
+impl TransactionSigner for Signer {
+    fn account_id(&self) -> AccountId {
+        self.account_id.clone()
+    }
+
+    fn sign_hash(&self, hash: &[u8; 32]) -> Result<DecoratedSignature, SorobanHelperError> {
+        let hint = SignatureHint(
+            self.signing_key.verifying_key().to_bytes()[28..]
+                .try_into()
+                .map_err(|_| {
+                    SorobanHelperError::SigningFailed("Failed to create signature hint".to_string())
+                })?,
+        );
+
+        let signature = XdrSignature(
+            self.signing_key
+                .clone()
+                .sign(hash)
+                .to_bytes()
+                .to_vec()
+                .try_into()
+                .map_err(|_| {
+                    SorobanHelperError::SigningFailed(
+                        "Failed to convert signature to XDR".to_string(),
+                    )
+                })?,
+        );
+
+        Ok(DecoratedSignature { hint, signature })
+    }
+}
+
+/// Signs the signature payload of a transaction with any transaction signer.
+///
+/// # Parameters
+///
+/// * `signer` - The signer to use
+/// * `tagged_transaction` - The transaction (or fee bump transaction) to sign
+/// * `network_id` - The network ID hash
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::XdrEncodingFailed` if the payload cannot be
+/// encoded, or the error of the signer
+pub(crate) fn sign_tagged_transaction(
+    signer: &dyn TransactionSigner,
+    tagged_transaction: TransactionSignaturePayloadTaggedTransaction,
+    network_id: &Hash,
+) -> Result<DecoratedSignature, SorobanHelperError> {
+    let tx_hash = crypto::signature_payload_hash(tagged_transaction, network_id)?;
+    signer.sign_hash(&tx_hash)
+}
+
 impl From<&[u8; 32]> for Signer {
     fn from(bytes: &[u8; 32]) -> Self {
         Signer::new(SigningKey::from_bytes(bytes))
@@ -79,6 +167,65 @@ impl Signer {
         }
     }
 
+    /// Creates a new signer from a Stellar secret seed.
+    ///
+    /// # Parameters
+    ///
+    /// * `seed` - The secret seed, encoded as a strkey (`S...`)
+    ///
+    /// # Returns
+    ///
+    /// A new Signer instance
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::SigningFailed` if the seed is not a valid secret seed
+    pub fn from_secret_seed(seed: &str) -> Result<Self, SorobanHelperError> {
+        // The seed itself is left out of the error to avoid leaking it in logs
+        let private_key = PrivateKey::from_string(seed.trim())
+            .map_err(|_| SorobanHelperError::SigningFailed("Invalid secret seed".to_string()))?;
+        Ok(Self::new(SigningKey::from_bytes(&private_key.0)))
+    }
+
+    /// Creates a new signer from a BIP-39 mnemonic phrase, as used by wallets.
+    ///
+    /// The key is derived following SEP-0005, using the `m/44'/148'/index'` path.
+    /// The phrase is not checked against the BIP-39 wordlist, so a mistyped word
+    /// results in a different account rather than an error.
+    ///
+    /// # Parameters
+    ///
+    /// * `phrase` - The mnemonic phrase, with words separated by whitespace
+    /// * `passphrase` - The optional BIP-39 passphrase, empty if none
+    /// * `index` - The index of the account to derive, usually 0
+    ///
+    /// # Returns
+    ///
+    /// A new Signer instance
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::SigningFailed` if the phrase does not have
+    /// 12, 15, 18, 21 or 24 words, or if `index` is greater than `2^31 - 1`
+    pub fn from_mnemonic(
+        phrase: &str,
+        passphrase: &str,
+        index: u32,
+    ) -> Result<Self, SorobanHelperError> {
+        let words: Vec<&str> = phrase.split_whitespace().collect();
+        if ![12, 15, 18, 21, 24].contains(&words.len()) {
+            return Err(SorobanHelperError::SigningFailed(format!(
+                "Invalid mnemonic: expected 12, 15, 18, 21 or 24 words, got {}",
+                words.len()
+            )));
+        }
+
+        let seed = crypto::mnemonic_to_seed(&words.join(" "), passphrase);
+        let key = crypto::derive_ed25519_key(&seed, &[44, 148, index])
+            .map_err(|e| SorobanHelperError::SigningFailed(e.to_string()))?;
+        Ok(Self::new(SigningKey::from_bytes(&key)))
+    }
+
     /// Returns the public key associated with this signer.
     ///
     /// # Returns
@@ -118,49 +265,84 @@ impl Signer {
         tx: &Transaction,
         network_id: &Hash,
     ) -> Result<DecoratedSignature, SorobanHelperError> {
-        let signature_payload = TransactionSignaturePayload {
-            network_id: network_id.clone(),
-            tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
-        };
-
-        let tx_hash: [u8; 32] = Sha256::digest(
-            signature_payload
-                .to_xdr(Limits::none())
-                .map_err(|e| SorobanHelperError::XdrEncodingFailed(e.to_string()))?,
+        sign_tagged_transaction(
+            self,
+            TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+            network_id,
         )
-        .into();
-
-        let hint = SignatureHint(
-            self.signing_key.verifying_key().to_bytes()[28..]
-                .try_into()
-                .map_err(|_| {
-                    SorobanHelperError::SigningFailed("Failed to create signature hint".to_string())
-                })?,
-        );
+    }
 
-        let signature = Signature(
-            self.signing_key
-                .clone()
-                .sign(&tx_hash)
-                .to_bytes()
-                .to_vec()
-                .try_into()
-                .map_err(|_| {
-                    SorobanHelperError::SigningFailed(
-                        "Failed to convert signature to XDR".to_string(),
-                    )
-                })?,
-        );
+    /// Signs a fee bump transaction with this signer's private key.
+    ///
+    /// # Parameters
+    ///
+    /// * `tx` - The fee bump transaction to sign
+    /// * `network_id` - The network ID hash
+    ///
+    /// # Returns
+    ///
+    /// A decorated signature that can be attached to the fee bump transaction
+    ///
+    /// # Errors
+    ///
+    /// Same as `sign_transaction`
+    pub fn sign_fee_bump(
+        &self,
+        tx: &FeeBumpTransaction,
+        network_id: &Hash,
+    ) -> Result<DecoratedSignature, SorobanHelperError> {
+        sign_tagged_transaction(
+            self,
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(tx.clone()),
+            network_id,
+        )
+    }
 
-        Ok(DecoratedSignature { hint, signature })
+    /// Signs an arbitrary payload with this signer's private key.
+    ///
+    /// Used for payloads other than transactions, such as off-chain messages
+    /// proving the ownership of an account. The signature can be checked with
+    /// `verify_payload`.
+    ///
+    /// # Parameters
+    ///
+    /// * `payload` - The bytes to sign
+    ///
+    /// # Returns
+    ///
+    /// The Ed25519 signature of the payload
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::SigningFailed` if the payload can't be signed
+    pub fn sign_payload(&self, payload: &[u8]) -> Result<Signature, SorobanHelperError> {
+        self.signing_key
+            .clone()
+            .try_sign(payload)
+            .map_err(|e| SorobanHelperError::SigningFailed(e.to_string()))
     }
 }
 
+/// Verifies the signature of an arbitrary payload, e.g. one produced by `Signer::sign_payload`.
+///
+/// # Parameters
+///
+/// * `public_key` - The public key of the expected signer
+/// * `payload` - The signed bytes
+/// * `signature` - The Ed25519 signature to verify
+///
+/// # Returns
+///
+/// Whether the signature is valid for the payload and public key
+pub fn verify_payload(public_key: &PublicKey, payload: &[u8], signature: &Signature) -> bool {
+    crypto::verify_signature(public_key, payload, &signature.to_bytes())
+}
+
 #[cfg(test)]
 mod tests {
     use stellar_xdr::curr::BytesM;
 
-    use crate::mock::mock_transaction;
+    use crate::mock::{mock_signer1, mock_signer3, mock_transaction};
 
     use super::*;
 
@@ -173,6 +355,101 @@ mod tests {
         assert_eq!(signer.public_key(), public_key);
     }
 
+    #[test]
+    fn test_from_secret_seed() {
+        let seed = "SD3C2X7WPTUYX4YHL2G34PX75JZ35QJDFKM6SXDLYHWIPOWPIQUXFVLE";
+        let signer = Signer::from_secret_seed(seed).unwrap();
+        assert_eq!(signer.public_key(), mock_signer1().public_key());
+
+        for seed in [
+            "",
+            "not a seed",
+            // a public key is not a secret seed
+            "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7",
+            // bad checksum
+            "SD3C2X7WPTUYX4YHL2G34PX75JZ35QJDFKM6SXDLYHWIPOWPIQUXFVLA",
+        ] {
+            assert!(
+                matches!(
+                    Signer::from_secret_seed(seed),
+                    Err(SorobanHelperError::SigningFailed(_))
+                ),
+                "{} should be rejected",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_mnemonic() {
+        // SEP-0005 test case 1
+        let phrase = "illness spike retreat truth genius clock brain pass fit cave bargain toe";
+        let cases = [
+            (
+                0,
+                "GDRXE2BQUC3AZNPVFSCEZ76NJ3WWL25FYFK6RGZGIEKWE4SOOHSUJUJ6",
+                "SBGWSG6BTNCKCOB3DIFBGCVMUPQFYPA2G4O34RMTB343OYPXU5DJDVMN",
+            ),
+            (
+                1,
+                "GBAW5XGWORWVFE2XTJYDTLDHXTY2Q2MO73HYCGB3XMFMQ562Q2W2GJQX",
+                "SCEPFFWGAG5P2VX5DHIYK3XEMZYLTYWIPWYEKXFHSK25RVMIUNJ7CTIS",
+            ),
+        ];
+        for (index, public_key, secret_seed) in cases {
+            let signer = Signer::from_mnemonic(phrase, "", index).unwrap();
+            assert_eq!(signer.public_key().to_string(), public_key);
+            assert_eq!(
+                signer.public_key(),
+                Signer::from_secret_seed(secret_seed).unwrap().public_key()
+            );
+        }
+
+        // extra whitespace is ignored
+        let signer = Signer::from_mnemonic(&format!("  {}\n", phrase), "", 0).unwrap();
+        assert_eq!(
+            signer.public_key().to_string(),
+            "GDRXE2BQUC3AZNPVFSCEZ76NJ3WWL25FYFK6RGZGIEKWE4SOOHSUJUJ6"
+        );
+
+        // the passphrase changes the derived keys
+        let signer = Signer::from_mnemonic(phrase, "secret", 0).unwrap();
+        assert_ne!(
+            signer.public_key().to_string(),
+            "GDRXE2BQUC3AZNPVFSCEZ76NJ3WWL25FYFK6RGZGIEKWE4SOOHSUJUJ6"
+        );
+
+        assert!(matches!(
+            Signer::from_mnemonic("illness spike retreat", "", 0),
+            Err(SorobanHelperError::SigningFailed(_))
+        ));
+        assert!(matches!(
+            Signer::from_mnemonic(phrase, "", u32::MAX),
+            Err(SorobanHelperError::SigningFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_sign_payload() {
+        let signer = mock_signer1();
+        let message = b"I own this account";
+        let signature = signer.sign_payload(message).unwrap();
+        assert!(verify_payload(&signer.public_key(), message, &signature));
+
+        // tampered payload
+        assert!(!verify_payload(
+            &signer.public_key(),
+            b"I own this account!",
+            &signature
+        ));
+        // other signer
+        assert!(!verify_payload(
+            &mock_signer3().public_key(),
+            message,
+            &signature
+        ));
+    }
+
     #[test]
     fn test_account_id() {
         let signing_key = SigningKey::from_bytes(&[42; 32]);
@@ -209,6 +486,6 @@ mod tests {
             .expect("slice with incorrect length");
 
         assert_eq!(decorated_signature.hint, SignatureHint(hint));
-        assert_eq!(decorated_signature.signature, Signature(signature_bytes));
+        assert_eq!(decorated_signature.signature, XdrSignature(signature_bytes));
     }
 }
@@ -0,0 +1,180 @@
+//! # Soroban Contract Storage
+//!
+//! This module provides helpers to build the keys used to read a contract's
+//! storage entries directly from the ledger, without invoking a getter function.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use soroban_rs::{xdr::ContractDataDurability, Contract, ContractDataKey};
+//!
+//! async fn example(contract: &Contract) {
+//!     let key = ContractDataKey::symbol("ADMIN").unwrap();
+//!     let admin = contract.read(key, ContractDataDurability::Persistent).await.unwrap();
+//! }
+//! ```
+use stellar_xdr::curr::{
+    ContractDataDurability, ContractId as XdrContractId, Hash, LedgerKey, LedgerKeyContractData,
+    ScAddress, ScVal,
+};
+
+use crate::{scval, ContractId, SorobanHelperError};
+
+/// Key of a contract storage entry.
+///
+/// Wraps the `ScVal` a contract uses as a storage key, providing constructors
+/// for the encodings most commonly used by Soroban contracts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractDataKey(ScVal);
+
+impl ContractDataKey {
+    /// Creates a key for a value stored under a symbol, e.g. `symbol_short!("ADMIN")`.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The symbol used as storage key
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::InvalidArgument` if `name` is not a valid symbol
+    pub fn symbol(name: &str) -> Result<Self, SorobanHelperError> {
+        Ok(Self(ScVal::Symbol(scval::symbol(name)?)))
+    }
+
+    /// Creates the key of the contract instance entry.
+    ///
+    /// The instance entry holds the contract executable and its instance storage.
+    pub fn instance() -> Self {
+        Self(ScVal::LedgerKeyContractInstance)
+    }
+
+    /// Creates a key for a value stored under a `#[contracttype]` enum variant
+    /// with data, e.g. `DataKey::Balance(address)`.
+    ///
+    /// Such keys are encoded as a vector holding the variant name followed by its fields.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The enum variant name
+    /// * `fields` - The values carried by the variant
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::InvalidArgument` if `name` is not a valid symbol
+    pub fn variant(name: &str, fields: Vec<ScVal>) -> Result<Self, SorobanHelperError> {
+        Ok(Self(scval::data_variant(name, fields)?))
+    }
+
+    /// Returns the `ScVal` used as key.
+    pub fn as_val(&self) -> &ScVal {
+        &self.0
+    }
+
+    /// Builds the ledger key identifying this entry in the given contract's storage.
+    ///
+    /// # Parameters
+    ///
+    /// * `contract_id` - The contract owning the storage entry
+    /// * `durability` - The storage durability (persistent or temporary)
+    pub fn to_ledger_key(
+        &self,
+        contract_id: &ContractId,
+        durability: ContractDataDurability,
+    ) -> LedgerKey {
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(XdrContractId(Hash(contract_id.0))),
+            key: self.0.clone(),
+            durability,
+        })
+    }
+}
+
+/// Builds the ledger key identifying a contract storage entry.
+///
+/// Shorthand for `ContractDataKey::from(key).to_ledger_key(contract, durability)`,
+/// meant to be used with `Env::get_ledger_entries`.
+///
+/// # Parameters
+///
+/// * `contract` - The contract owning the storage entry
+/// * `key` - The `ScVal` the contract uses as storage key
+/// * `durability` - The storage durability (persistent or temporary)
+pub fn contract_data_key(
+    contract: &ContractId,
+    key: ScVal,
+    durability: ContractDataDurability,
+) -> LedgerKey {
+    ContractDataKey::from(key).to_ledger_key(contract, durability)
+}
+
+/// Value of a contract storage entry, along with its time to live.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractDataResult {
+    /// The stored value
+    pub value: ScVal,
+    /// The last ledger the entry is live at, if reported by the RPC server
+    pub live_until_ledger: Option<u32>,
+}
+
+impl From<ScVal> for ContractDataKey {
+    fn from(val: ScVal) -> Self {
+        Self(val)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use stellar_xdr::curr::ScSymbol;
+
+    #[test]
+    fn test_symbol_key() {
+        let key = ContractDataKey::symbol("ADMIN").unwrap();
+        assert_eq!(
+            key.as_val(),
+            &ScVal::Symbol(ScSymbol("ADMIN".try_into().unwrap()))
+        );
+
+        let too_long = "a".repeat(33);
+        assert!(matches!(
+            ContractDataKey::symbol(&too_long),
+            Err(SorobanHelperError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_instance_key() {
+        let key = ContractDataKey::instance();
+        assert_eq!(key.as_val(), &ScVal::LedgerKeyContractInstance);
+
+        let contract_id = ContractId([7; 32]);
+        let ledger_key = key.to_ledger_key(&contract_id, ContractDataDurability::Persistent);
+        match ledger_key {
+            LedgerKey::ContractData(data) => {
+                assert_eq!(
+                    data.contract,
+                    ScAddress::Contract(XdrContractId(Hash([7; 32])))
+                );
+                assert_eq!(data.key, ScVal::LedgerKeyContractInstance);
+                assert_eq!(data.durability, ContractDataDurability::Persistent);
+            }
+            _ => panic!("Expected LedgerKey::ContractData, got {:?}", ledger_key),
+        }
+    }
+
+    #[test]
+    fn test_variant_key() {
+        let key = ContractDataKey::variant("Balance", vec![ScVal::U32(1)]).unwrap();
+        match key.as_val() {
+            ScVal::Vec(Some(items)) => {
+                assert_eq!(items.len(), 2);
+                assert_eq!(
+                    items[0],
+                    ScVal::Symbol(ScSymbol("Balance".try_into().unwrap()))
+                );
+                assert_eq!(items[1], ScVal::U32(1));
+            }
+            other => panic!("Expected ScVal::Vec, got {:?}", other),
+        }
+    }
+}
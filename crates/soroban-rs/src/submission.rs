@@ -0,0 +1,256 @@
+//! # Soroban Submission Guard
+//!
+//! This module provides a safety net against submitting the same signed
+//! transaction envelope more than once, which is easy to do by accident in
+//! retry logic.
+//!
+//! Every `Env` holds a `SubmissionGuard` shared by all its clones. Submitting an
+//! envelope that was already successfully submitted returns the cached result
+//! instead of hitting the network again, and submitting an envelope whose
+//! submission is still in flight fails with `SorobanHelperError::DuplicateSubmission`.
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use stellar_xdr::curr::{Limits, TransactionEnvelope, WriteXdr};
+
+use crate::{SorobanHelperError, SorobanTransactionResponse};
+
+/// Default number of successful submissions kept in the cache.
+pub const DEFAULT_SUBMISSION_CACHE_SIZE: usize = 1024;
+
+/// State of a submitted envelope.
+enum Submission {
+    /// The envelope was submitted and the result is not known yet.
+    InFlight,
+    /// The envelope was successfully submitted.
+    Completed(Box<SorobanTransactionResponse>),
+}
+
+#[derive(Default)]
+struct Submissions {
+    entries: HashMap<[u8; 32], Submission>,
+    // Hashes of the completed entries, oldest first
+    completed: VecDeque<[u8; 32]>,
+}
+
+/// Tracks the envelopes submitted within an `Env` session.
+pub struct SubmissionGuard {
+    submissions: Mutex<Submissions>,
+    capacity: usize,
+}
+
+/// Outcome of registering an envelope with `SubmissionGuard::begin`.
+pub enum BeginSubmission<'a> {
+    /// The envelope was already successfully submitted, with its cached response.
+    Submitted(Box<SorobanTransactionResponse>),
+    /// The envelope can be submitted. The ticket records the outcome.
+    Ready(SubmissionTicket<'a>),
+}
+
+/// An in-flight submission registered with `SubmissionGuard::begin`.
+///
+/// Dropping the ticket without calling `finish`, e.g. because the submitting
+/// future was cancelled, releases the envelope so it can be submitted again.
+pub struct SubmissionTicket<'a> {
+    guard: &'a SubmissionGuard,
+    hash: [u8; 32],
+    finished: bool,
+}
+
+impl Default for SubmissionGuard {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_SUBMISSION_CACHE_SIZE)
+    }
+}
+
+impl SubmissionGuard {
+    /// Creates an empty submission guard caching up to
+    /// `DEFAULT_SUBMISSION_CACHE_SIZE` successful submissions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty submission guard caching up to `capacity` successful
+    /// submissions. Once full, the oldest submission is evicted.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            submissions: Mutex::new(Submissions::default()),
+            capacity,
+        }
+    }
+
+    /// Computes the key identifying an envelope, the SHA-256 hash of its XDR encoding.
+    pub fn envelope_hash(
+        tx_envelope: &TransactionEnvelope,
+    ) -> Result<[u8; 32], SorobanHelperError> {
+        let xdr = tx_envelope.to_xdr(Limits::none())?;
+        Ok(Sha256::digest(xdr).into())
+    }
+
+    /// Registers an envelope about to be submitted.
+    ///
+    /// # Returns
+    ///
+    /// `BeginSubmission::Ready` with the ticket tracking the submission, or
+    /// `BeginSubmission::Submitted` with the cached response if the envelope was already
+    /// successfully submitted
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::DuplicateSubmission` if a submission of the
+    /// same envelope is still in flight
+    pub fn begin(&self, hash: [u8; 32]) -> Result<BeginSubmission<'_>, SorobanHelperError> {
+        let mut submissions = self.submissions.lock().unwrap();
+        match submissions.entries.get(&hash) {
+            Some(Submission::Completed(response)) => {
+                Ok(BeginSubmission::Submitted(response.clone()))
+            }
+            Some(Submission::InFlight) => {
+                Err(SorobanHelperError::DuplicateSubmission(hex::encode(hash)))
+            }
+            None => {
+                submissions.entries.insert(hash, Submission::InFlight);
+                Ok(BeginSubmission::Ready(SubmissionTicket {
+                    guard: self,
+                    hash,
+                    finished: false,
+                }))
+            }
+        }
+    }
+
+    /// Returns whether the envelope was already successfully submitted.
+    pub fn is_submitted(&self, tx_envelope: &TransactionEnvelope) -> bool {
+        Self::envelope_hash(tx_envelope)
+            .map(|hash| {
+                matches!(
+                    self.submissions.lock().unwrap().entries.get(&hash),
+                    Some(Submission::Completed(_))
+                )
+            })
+            .unwrap_or(false)
+    }
+
+    fn complete(&self, hash: [u8; 32], response: &SorobanTransactionResponse) {
+        let mut submissions = self.submissions.lock().unwrap();
+        if self.capacity == 0 {
+            submissions.entries.remove(&hash);
+            return;
+        }
+        while submissions.completed.len() >= self.capacity {
+            if let Some(oldest) = submissions.completed.pop_front() {
+                submissions.entries.remove(&oldest);
+            }
+        }
+        submissions
+            .entries
+            .insert(hash, Submission::Completed(Box::new(response.clone())));
+        submissions.completed.push_back(hash);
+    }
+
+    fn release(&self, hash: [u8; 32]) {
+        let mut submissions = self.submissions.lock().unwrap();
+        if matches!(submissions.entries.get(&hash), Some(Submission::InFlight)) {
+            submissions.entries.remove(&hash);
+        }
+    }
+}
+
+impl SubmissionTicket<'_> {
+    /// Records the outcome of the submission.
+    ///
+    /// Successful results are cached. Failed submissions are forgotten so the
+    /// envelope can be submitted again.
+    pub fn finish(mut self, result: &Result<SorobanTransactionResponse, SorobanHelperError>) {
+        self.finished = true;
+        match result {
+            Ok(response) => self.guard.complete(self.hash, response),
+            Err(_) => self.guard.release(self.hash),
+        }
+    }
+}
+
+impl Drop for SubmissionTicket<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.guard.release(self.hash);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{mock_signer1, mock_transaction_envelope, mock_transaction_response};
+    use stellar_xdr::curr::SequenceNumber;
+
+    fn begin_ticket(guard: &SubmissionGuard, hash: [u8; 32]) -> SubmissionTicket<'_> {
+        match guard.begin(hash).unwrap() {
+            BeginSubmission::Ready(ticket) => ticket,
+            BeginSubmission::Submitted(_) => panic!("envelope was already submitted"),
+        }
+    }
+
+    fn envelope_with_sequence(sequence: i64) -> TransactionEnvelope {
+        let mut envelope = mock_transaction_envelope(mock_signer1().account_id());
+        if let TransactionEnvelope::Tx(ref mut v1) = envelope {
+            v1.tx.seq_num = SequenceNumber(sequence);
+        }
+        envelope
+    }
+
+    #[test]
+    fn test_submission_guard() {
+        let guard = SubmissionGuard::new();
+        let envelope = mock_transaction_envelope(mock_signer1().account_id());
+        let hash = SubmissionGuard::envelope_hash(&envelope).unwrap();
+
+        let ticket = begin_ticket(&guard, hash);
+        assert!(matches!(
+            guard.begin(hash),
+            Err(SorobanHelperError::DuplicateSubmission(_))
+        ));
+
+        // a failed submission can be retried
+        ticket.finish(&Err(SorobanHelperError::NetworkRequestFailed(
+            "error".to_string(),
+        )));
+        assert!(!guard.is_submitted(&envelope));
+
+        let ticket = begin_ticket(&guard, hash);
+        ticket.finish(&Ok(mock_transaction_response()));
+        assert!(guard.is_submitted(&envelope));
+        assert!(matches!(
+            guard.begin(hash),
+            Ok(BeginSubmission::Submitted(_))
+        ));
+    }
+
+    #[test]
+    fn test_dropped_ticket_releases_envelope() {
+        let guard = SubmissionGuard::new();
+        let envelope = mock_transaction_envelope(mock_signer1().account_id());
+        let hash = SubmissionGuard::envelope_hash(&envelope).unwrap();
+
+        drop(begin_ticket(&guard, hash));
+
+        assert!(!guard.is_submitted(&envelope));
+        begin_ticket(&guard, hash).finish(&Ok(mock_transaction_response()));
+        assert!(guard.is_submitted(&envelope));
+    }
+
+    #[test]
+    fn test_completed_cache_is_capped() {
+        let guard = SubmissionGuard::with_capacity(2);
+        let envelopes: Vec<_> = (1..=3).map(envelope_with_sequence).collect();
+
+        for envelope in &envelopes {
+            let hash = SubmissionGuard::envelope_hash(envelope).unwrap();
+            begin_ticket(&guard, hash).finish(&Ok(mock_transaction_response()));
+        }
+
+        assert!(!guard.is_submitted(&envelopes[0]));
+        assert!(guard.is_submitted(&envelopes[1]));
+        assert!(guard.is_submitted(&envelopes[2]));
+    }
+}
@@ -32,14 +32,24 @@
 //!     env.send_transaction(&tx_envelope).await.unwrap();
 //! }
 //! ```
-use crate::{error::SorobanHelperError, Account, Env};
+use crate::{
+    error::SorobanHelperError, Account, AccountSequence, AuthorizationBuilder, Env,
+    TransactionSigner,
+};
+use std::sync::Arc;
+use stellar_rpc_client::SimulateTransactionResponse;
 use stellar_xdr::curr::{
-    Memo, Operation, Preconditions, SequenceNumber, SorobanCredentials, Transaction, TransactionExt,
+    Duration, Hash, Memo, Operation, OperationBody, Preconditions, PreconditionsV2, ScAddress,
+    SequenceNumber, SorobanCredentials, SorobanTransactionData, Transaction, TransactionExt, VecM,
 };
 
 /// Default transaction fee in stroops (0.00001 XLM)
 pub const DEFAULT_TRANSACTION_FEES: u32 = 100;
 
+/// Number of ledgers, after the simulation ledger, address authorizations
+/// signed while building stay valid for (about 8 minutes)
+pub const AUTH_VALIDITY_LEDGERS: u32 = 100;
+
 /// Builder for creating and configuring Stellar transactions.
 ///
 /// TransactionBuilder provides an API for building Stellar transactions
@@ -60,6 +70,16 @@ pub struct TransactionBuilder {
     pub preconditions: Preconditions,
     /// Environment for network interaction
     pub env: Env,
+    /// Percentage added on top of the simulated resource fee
+    pub resource_fee_margin: u32,
+    /// Floor for the fee computed by `simulate_and_build`
+    pub min_fee: Option<u32>,
+    /// Additional signers used to sign address authorizations requested by simulation
+    pub auth_signers: Vec<Arc<dyn TransactionSigner>>,
+    /// Soroban resources attached to the transaction before simulation
+    pub soroban_data: Option<SorobanTransactionData>,
+    /// Sequence number of the transaction, fetched from the network when not set
+    pub sequence: Option<AccountSequence>,
 }
 
 impl TransactionBuilder {
@@ -87,6 +107,11 @@ impl TransactionBuilder {
             memo: Memo::None,
             preconditions: Preconditions::None,
             env: env.clone(),
+            resource_fee_margin: 0,
+            min_fee: None,
+            auth_signers: Vec::new(),
+            soroban_data: None,
+            sequence: None,
         }
     }
 
@@ -120,6 +145,34 @@ impl TransactionBuilder {
         self
     }
 
+    /// Adds several operations to the transaction, in order.
+    ///
+    /// # Parameters
+    ///
+    /// * `operations` - The operations to add
+    ///
+    /// # Returns
+    ///
+    /// The updated TransactionBuilder
+    pub fn add_operations(mut self, operations: impl IntoIterator<Item = Operation>) -> Self {
+        self.operations.extend(operations);
+        self
+    }
+
+    /// Sets the operations of the transaction, replacing any previously added.
+    ///
+    /// # Parameters
+    ///
+    /// * `operations` - The operations of the transaction
+    ///
+    /// # Returns
+    ///
+    /// The updated TransactionBuilder
+    pub fn set_operations(mut self, operations: Vec<Operation>) -> Self {
+        self.operations = operations;
+        self
+    }
+
     /// Sets the memo for the transaction.
     ///
     /// Memos can be used to attach additional information to a transaction.
@@ -156,10 +209,177 @@ impl TransactionBuilder {
         self
     }
 
+    /// Requires a minimum number of ledgers between the source account's
+    /// sequence number change and the ledger this transaction is applied in.
+    ///
+    /// The preconditions are turned into `Preconditions::V2`, keeping any time bounds already set.
+    ///
+    /// # Parameters
+    ///
+    /// * `gap` - The minimum number of ledgers
+    ///
+    /// # Returns
+    ///
+    /// The updated TransactionBuilder
+    pub fn min_seq_ledger_gap(mut self, gap: u32) -> Self {
+        self.preconditions_v2().min_seq_ledger_gap = gap;
+        self
+    }
+
+    /// Requires the source account's sequence number to be at least `min_seq_num`
+    /// for the transaction to be valid.
+    ///
+    /// The preconditions are turned into `Preconditions::V2`, keeping any time bounds already set.
+    ///
+    /// # Parameters
+    ///
+    /// * `min_seq_num` - The minimum source account sequence number
+    ///
+    /// # Returns
+    ///
+    /// The updated TransactionBuilder
+    pub fn min_seq_num(mut self, min_seq_num: SequenceNumber) -> Self {
+        self.preconditions_v2().min_seq_num = Some(min_seq_num);
+        self
+    }
+
+    /// Converts the current preconditions into `Preconditions::V2` and returns them.
+    fn preconditions_v2(&mut self) -> &mut PreconditionsV2 {
+        if !matches!(self.preconditions, Preconditions::V2(_)) {
+            let time_bounds = match &self.preconditions {
+                Preconditions::Time(time_bounds) => Some(time_bounds.clone()),
+                _ => None,
+            };
+            self.preconditions = Preconditions::V2(PreconditionsV2 {
+                time_bounds,
+                ledger_bounds: None,
+                min_seq_num: None,
+                min_seq_age: Duration(0),
+                min_seq_ledger_gap: 0,
+                extra_signers: VecM::default(),
+            });
+        }
+        match &mut self.preconditions {
+            Preconditions::V2(v2) => v2,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Sets the transaction fee in stroops.
+    ///
+    /// When building with `simulate_and_build`, this fee acts as a floor: the
+    /// final fee is the higher of this value and the fee computed from simulation,
+    /// so an explicitly set fee is never discarded in favor of a lower estimate.
+    ///
+    /// # Parameters
+    ///
+    /// * `fee` - The transaction fee in stroops
+    ///
+    /// # Returns
+    ///
+    /// The updated TransactionBuilder
+    pub fn set_fee(mut self, fee: u32) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    /// Sets a minimum for the fee computed by `simulate_and_build`.
+    ///
+    /// Useful to bump fees on a congested network without giving up the
+    /// simulated estimate: the final fee is the higher of the two. Unlike
+    /// `set_fee`, this doesn't change the fee of transactions built with `build`.
+    ///
+    /// # Parameters
+    ///
+    /// * `min` - The minimum transaction fee in stroops
+    ///
+    /// # Returns
+    ///
+    /// The updated TransactionBuilder
+    pub fn with_min_fee(mut self, min: u32) -> Self {
+        self.min_fee = Some(min);
+        self
+    }
+
+    /// Sets a safety margin applied to the simulated resource fee.
+    ///
+    /// Simulation's `min_resource_fee` is a lower bound, and under contention the
+    /// actual fee can be higher. The margin inflates the resource fee computed by
+    /// `simulate_and_build` by the given percentage (e.g. `20` adds 20%).
+    ///
+    /// # Parameters
+    ///
+    /// * `percent` - The percentage to add to the simulated resource fee
+    ///
+    /// # Returns
+    ///
+    /// The updated TransactionBuilder
+    pub fn resource_fee_margin(mut self, percent: u32) -> Self {
+        self.resource_fee_margin = percent;
+        self
+    }
+
+    /// Adds a signer for address authorizations requested by simulation.
+    ///
+    /// When simulating, contract calls to `require_auth` on an address other
+    /// than the source account need an authorization entry signed by that
+    /// address. Entries for the source account signers are signed automatically;
+    /// this adds signers for other addresses.
+    ///
+    /// # Parameters
+    ///
+    /// * `signer` - The signer of the address authorizing the invocation
+    ///
+    /// # Returns
+    ///
+    /// The updated TransactionBuilder
+    pub fn add_auth_signer(mut self, signer: impl TransactionSigner + 'static) -> Self {
+        self.auth_signers.push(Arc::new(signer));
+        self
+    }
+
+    /// Sets the Soroban resources of the transaction.
+    ///
+    /// Most operations don't need this, as simulation computes the resources.
+    /// Operations acting on an explicit footprint, like `ExtendFootprintTtl`,
+    /// need the footprint to be set before simulating.
+    ///
+    /// # Parameters
+    ///
+    /// * `soroban_data` - The resources, including the footprint
+    ///
+    /// # Returns
+    ///
+    /// The updated TransactionBuilder
+    pub fn set_soroban_data(mut self, soroban_data: SorobanTransactionData) -> Self {
+        self.soroban_data = Some(soroban_data);
+        self
+    }
+
+    /// Sets the sequence number of the transaction.
+    ///
+    /// By default, building fetches the source account's sequence number from
+    /// the network. Setting it avoids the round trip when the caller tracks the
+    /// sequence locally, e.g. when submitting several transactions in a row.
+    ///
+    /// # Parameters
+    ///
+    /// * `sequence` - The sequence number of the transaction (current account sequence + 1)
+    ///
+    /// # Returns
+    ///
+    /// The updated TransactionBuilder
+    pub fn set_sequence(mut self, sequence: AccountSequence) -> Self {
+        self.sequence = Some(sequence);
+        self
+    }
+
     /// Builds a transaction without simulation.
     ///
-    /// This method retrieves the source account's current sequence number
-    /// and constructs a transaction with the configured parameters.
+    /// This method retrieves the source account's next sequence number,
+    /// unless set with `set_sequence`, and constructs a transaction with the
+    /// configured parameters. Building a transaction doesn't increment the
+    /// account's cached sequence number, see `Account::use_sequence_cache`.
     ///
     /// # Returns
     ///
@@ -175,25 +395,30 @@ impl TransactionBuilder {
             SorobanHelperError::XdrEncodingFailed(format!("Failed to convert operations: {}", e))
         })?;
 
-        let seq_num = self
-            .source_account
-            .get_sequence(&self.env)
-            .await
-            .map_err(|e| {
-                SorobanHelperError::XdrEncodingFailed(format!(
-                    "Failed to get sequence number: {}",
-                    e
-                ))
-            })?;
+        let seq_num = match self.sequence {
+            Some(sequence) => sequence,
+            None => self
+                .source_account
+                .peek_sequence(&self.env)
+                .await
+                .map_err(|e| {
+                    SorobanHelperError::XdrEncodingFailed(format!(
+                        "Failed to get sequence number: {}",
+                        e
+                    ))
+                })?,
+        };
 
         Ok(Transaction {
             fee: self.fee,
-            seq_num: SequenceNumber::from(seq_num.increment().value()),
+            seq_num: SequenceNumber::from(seq_num.value()),
             source_account: self.source_account.account_id().into(),
             cond: self.preconditions,
             memo: self.memo,
             operations,
-            ext: TransactionExt::V0,
+            ext: self
+                .soroban_data
+                .map_or(TransactionExt::V0, TransactionExt::V1),
         })
     }
 
@@ -202,7 +427,12 @@ impl TransactionBuilder {
     /// This method:
     /// 1. Builds a transaction with default fees
     /// 2. Simulates the transaction to determine required resources
-    /// 3. Updates the transaction with the correct fees and resource data
+    /// 3. Attaches the authorization entries requested by the simulation, signing
+    ///    address authorizations (see `add_auth_signer`) and simulating again
+    /// 4. Updates the transaction with the correct fees and resource data
+    ///
+    /// The final fee is the maximum between the computed fee, the fee set
+    /// on the builder (see `set_fee`) and the minimum fee (see `with_min_fee`).
     ///
     /// This is the recommended way to build Soroban transactions, as it ensures
     /// they have sufficient fees and resources for execution.
@@ -228,55 +458,198 @@ impl TransactionBuilder {
         env: &Env,
         source_account: &Account,
     ) -> Result<Transaction, SorobanHelperError> {
-        let tx = self.build().await?;
-        let tx_envelope = source_account.sign_transaction_unsafe(&tx, &env.network_id())?;
-        let simulation = env.simulate_transaction(&tx_envelope).await?;
+        self.simulate_and_build_impl(env, source_account, false)
+            .await
+    }
+
+    /// Same as `simulate_and_build`, but fails with
+    /// `SorobanHelperError::TransactionSimulationFailed` when the simulation
+    /// reports an error instead of only warning about it.
+    pub(crate) async fn simulate_and_build_strict(
+        self,
+        env: &Env,
+        source_account: &Account,
+    ) -> Result<Transaction, SorobanHelperError> {
+        self.simulate_and_build_impl(env, source_account, true)
+            .await
+    }
+
+    async fn simulate_and_build_impl(
+        self,
+        env: &Env,
+        source_account: &Account,
+        fail_on_simulation_error: bool,
+    ) -> Result<Transaction, SorobanHelperError> {
+        let resource_fee_margin = self.resource_fee_margin;
+        let min_fee = self.min_fee.unwrap_or_default();
+        let auth_signers = self.auth_signers.clone();
+        let mut tx = self.build().await?;
+        let mut simulation =
+            Self::simulate(env, source_account, &tx, fail_on_simulation_error).await?;
 
-        let updated_fee = DEFAULT_TRANSACTION_FEES.max(
+        let signed_auth = Self::attach_auth_entries(
+            &mut tx,
+            &simulation,
+            source_account,
+            &auth_signers,
+            &env.network_id(),
+        )?;
+        if signed_auth {
+            // Verifying the signed entries consumes resources the recording simulation didn't account for
+            simulation = Self::simulate(env, source_account, &tx, fail_on_simulation_error).await?;
+        }
+
+        let resource_fee =
+            Self::apply_resource_fee_margin(simulation.min_resource_fee, resource_fee_margin)?;
+
+        let computed_fee = DEFAULT_TRANSACTION_FEES.max(
             u32::try_from(
-                (tx.operations.len() as u64 * DEFAULT_TRANSACTION_FEES as u64)
-                    + simulation.min_resource_fee,
+                (tx.operations.len() as u64 * DEFAULT_TRANSACTION_FEES as u64) + resource_fee,
             )
             .map_err(|_| {
                 SorobanHelperError::InvalidArgument("Transaction fee too high".to_string())
             })?,
         );
+        // a fee explicitly set by the caller is used as a floor
+        tx.fee = computed_fee.max(tx.fee).max(min_fee);
+
+        if let Ok(mut tx_data) = simulation.transaction_data().map_err(|e| {
+            SorobanHelperError::TransactionFailed(format!("Failed to get transaction data: {}", e))
+        }) {
+            tx_data.resource_fee = i64::try_from(resource_fee).map_err(|_| {
+                SorobanHelperError::InvalidArgument("Resource fee too high".to_string())
+            })?;
+            tx.ext = TransactionExt::V1(tx_data);
+        }
 
-        if simulation.error.is_some() {
+        Ok(tx)
+    }
+
+    /// Simulates a transaction, warning about or failing on simulation errors.
+    async fn simulate(
+        env: &Env,
+        source_account: &Account,
+        tx: &Transaction,
+        fail_on_simulation_error: bool,
+    ) -> Result<SimulateTransactionResponse, SorobanHelperError> {
+        let tx_envelope = source_account.sign_transaction_unsafe(tx, &env.network_id())?;
+        let simulation = env.simulate_transaction(&tx_envelope).await?;
+
+        if let Some(error) = &simulation.error {
+            if fail_on_simulation_error {
+                return Err(SorobanHelperError::TransactionSimulationFailed(
+                    error.to_string(),
+                ));
+            }
             println!(
                 "[WARN] Transaction simulation failed with error: {:?}",
                 simulation.error
             );
         }
 
+        Ok(simulation)
+    }
+
+    /// Attaches the authorization entries requested by the simulation to the
+    /// invoke host function operations that don't carry any.
+    ///
+    /// Source account entries are attached as they are, since the transaction
+    /// signature covers them. Address entries are signed by the matching signer
+    /// among the source account signers and the builder's auth signers.
+    ///
+    /// # Returns
+    ///
+    /// Whether any address entry was signed
+    ///
+    /// # Errors
+    ///
+    /// Returns `SorobanHelperError::Unauthorized` if an address entry requires a
+    /// signer that isn't available
+    fn attach_auth_entries(
+        tx: &mut Transaction,
+        simulation: &SimulateTransactionResponse,
+        source_account: &Account,
+        auth_signers: &[Arc<dyn TransactionSigner>],
+        network_id: &Hash,
+    ) -> Result<bool, SorobanHelperError> {
         let sim_results = simulation.results().unwrap_or_default();
-        for result in &sim_results {
-            for auth in &result.auth {
-                if matches!(auth.credentials, SorobanCredentials::Address(_)) {
-                    return Err(SorobanHelperError::NotSupported(
-                        "Address authorization not yet supported".to_string(),
+        let valid_until_ledger = simulation
+            .latest_ledger
+            .saturating_add(AUTH_VALIDITY_LEDGERS);
+
+        let mut operations = tx.operations.to_vec();
+        let mut signed = false;
+        let invoke_ops = operations.iter_mut().filter_map(|op| match &mut op.body {
+            OperationBody::InvokeHostFunction(invoke) => Some(invoke),
+            _ => None,
+        });
+
+        for (invoke, result) in invoke_ops.zip(sim_results.iter()) {
+            if !invoke.auth.is_empty() {
+                // Address authorizations already signed and attached by the caller are accepted
+                let missing = result.auth.iter().any(|auth| {
+                    matches!(auth.credentials, SorobanCredentials::Address(_))
+                        && !invoke.auth.contains(auth)
+                });
+                if missing {
+                    return Err(SorobanHelperError::Unauthorized(
+                        "Simulation requires address authorizations that weren't provided"
+                            .to_string(),
                     ));
                 }
+                continue;
             }
-        }
-
-        let mut tx = Transaction {
-            fee: updated_fee,
-            seq_num: tx.seq_num,
-            source_account: tx.source_account,
-            cond: tx.cond,
-            memo: tx.memo,
-            operations: tx.operations,
-            ext: tx.ext,
-        };
 
-        if let Ok(tx_data) = simulation.transaction_data().map_err(|e| {
-            SorobanHelperError::TransactionFailed(format!("Failed to get transaction data: {}", e))
-        }) {
-            tx.ext = TransactionExt::V1(tx_data);
+            let mut entries = Vec::with_capacity(result.auth.len());
+            for entry in &result.auth {
+                match &entry.credentials {
+                    SorobanCredentials::SourceAccount => entries.push(entry.clone()),
+                    SorobanCredentials::Address(credentials) => {
+                        let signer = source_account
+                            .signers()
+                            .iter()
+                            .chain(auth_signers)
+                            .find(|signer| {
+                                ScAddress::Account(signer.account_id()) == credentials.address
+                            })
+                            .ok_or_else(|| {
+                                SorobanHelperError::Unauthorized(format!(
+                                    "Missing signer for address authorization of {:?}",
+                                    credentials.address
+                                ))
+                            })?;
+                        entries.extend(
+                            AuthorizationBuilder::new(entry.root_invocation.clone())
+                                .nonce(credentials.nonce)
+                                .valid_until_ledger(valid_until_ledger)
+                                .build(signer.as_ref(), network_id)?,
+                        );
+                        signed = true;
+                    }
+                }
+            }
+            invoke.auth = entries.try_into().map_err(|e| {
+                SorobanHelperError::XdrEncodingFailed(format!(
+                    "Failed to encode auth entries: {}",
+                    e
+                ))
+            })?;
         }
 
-        Ok(tx)
+        tx.operations = operations.try_into().map_err(|e| {
+            SorobanHelperError::XdrEncodingFailed(format!("Failed to convert operations: {}", e))
+        })?;
+        Ok(signed)
+    }
+
+    /// Inflates a resource fee by the given percentage, rounding up.
+    fn apply_resource_fee_margin(
+        resource_fee: u64,
+        margin_percent: u32,
+    ) -> Result<u64, SorobanHelperError> {
+        let inflated = (resource_fee as u128 * (100 + margin_percent as u128)).div_ceil(100);
+        u64::try_from(inflated)
+            .map_err(|_| SorobanHelperError::InvalidArgument("Resource fee too high".to_string()))
     }
 }
 
@@ -284,13 +657,18 @@ impl TransactionBuilder {
 mod test {
     use crate::{
         mock::{
-            mock_account_entry, mock_contract_id, mock_env, mock_signer1, mock_simulate_tx_response,
+            mock_account_entry, mock_contract_id, mock_env, mock_signer1, mock_signer2,
+            mock_simulate_tx_response, mock_simulate_tx_response_with_auth,
         },
         operation::Operations,
-        transaction::DEFAULT_TRANSACTION_FEES,
-        Account, TransactionBuilder,
+        transaction::{AUTH_VALIDITY_LEDGERS, DEFAULT_TRANSACTION_FEES},
+        verify_authorization_entry, Account, AccountSequence, AuthorizationBuilder,
+        SorobanHelperError, TransactionBuilder,
+    };
+    use stellar_xdr::curr::{
+        Memo, OperationBody, Preconditions, ScAddress, SequenceNumber, SorobanAddressCredentials,
+        SorobanAuthorizationEntry, SorobanCredentials, TimeBounds, TimePoint, Transaction,
     };
-    use stellar_xdr::curr::{Memo, Preconditions, TimeBounds, TimePoint};
 
     #[tokio::test]
     async fn test_build_transaction() {
@@ -311,6 +689,24 @@ mod test {
         assert!(transaction.fee == DEFAULT_TRANSACTION_FEES);
     }
 
+    #[tokio::test]
+    async fn test_build_with_sequence() {
+        let account = Account::single(mock_signer1());
+        // fetching the sequence number fails, so it must not be needed
+        let get_account_result = Err(SorobanHelperError::NetworkRequestFailed(
+            "account not available".to_string(),
+        ));
+
+        let env = mock_env(Some(get_account_result), None, None);
+        let transaction = TransactionBuilder::new(&account, &env)
+            .set_sequence(AccountSequence::new(42))
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(transaction.seq_num, SequenceNumber(42));
+    }
+
     #[tokio::test]
     async fn test_simulate_and_build() {
         let simulation_fee = 42;
@@ -331,6 +727,221 @@ mod test {
         assert!(tx.operations[0].body == operation.body);
     }
 
+    #[tokio::test]
+    async fn test_simulate_and_build_with_resource_fee_margin() {
+        let simulation_fee = 1000;
+
+        let account = Account::single(mock_signer1());
+        let get_account_result = Ok(mock_account_entry(&account.account_id().0.to_string()));
+        let simulate_tx_result = Ok(mock_simulate_tx_response(Some(simulation_fee)));
+
+        let env = mock_env(Some(get_account_result), Some(simulate_tx_result), None);
+        let contract_id = mock_contract_id(account.clone(), &env);
+        let operation = Operations::invoke_contract(&contract_id, "test", vec![]).unwrap();
+        let tx_builder = TransactionBuilder::new(&account, &env)
+            .add_operation(operation)
+            .resource_fee_margin(20);
+        assert_eq!(tx_builder.resource_fee_margin, 20);
+
+        let tx = tx_builder.simulate_and_build(&env, &account).await.unwrap();
+
+        // DEFAULT_TRANSACTION_FEE + SIMULATION_FEE * 1.2
+        assert_eq!(tx.fee, 1300);
+    }
+
+    #[tokio::test]
+    async fn test_simulate_and_build_keeps_higher_set_fee() {
+        let simulation_fee = 42;
+
+        let account = Account::single(mock_signer1());
+        let get_account_result = Ok(mock_account_entry(&account.account_id().0.to_string()));
+        let simulate_tx_result = Ok(mock_simulate_tx_response(Some(simulation_fee)));
+
+        let env = mock_env(Some(get_account_result), Some(simulate_tx_result), None);
+        let contract_id = mock_contract_id(account.clone(), &env);
+        let operation = Operations::invoke_contract(&contract_id, "test", vec![]).unwrap();
+
+        // set fee higher than the computed one (142)
+        let tx = TransactionBuilder::new(&account, &env)
+            .add_operation(operation.clone())
+            .set_fee(10_000)
+            .simulate_and_build(&env, &account)
+            .await
+            .unwrap();
+        assert_eq!(tx.fee, 10_000);
+
+        // set fee lower than the computed one
+        let tx = TransactionBuilder::new(&account, &env)
+            .add_operation(operation)
+            .set_fee(50)
+            .simulate_and_build(&env, &account)
+            .await
+            .unwrap();
+        assert_eq!(tx.fee, 142);
+    }
+
+    #[tokio::test]
+    async fn test_simulate_and_build_with_min_fee() {
+        let simulation_fee = 42;
+
+        let account = Account::single(mock_signer1());
+        let get_account_result = Ok(mock_account_entry(&account.account_id().0.to_string()));
+        let simulate_tx_result = Ok(mock_simulate_tx_response(Some(simulation_fee)));
+
+        let env = mock_env(Some(get_account_result), Some(simulate_tx_result), None);
+        let contract_id = mock_contract_id(account.clone(), &env);
+        let operation = Operations::invoke_contract(&contract_id, "test", vec![]).unwrap();
+
+        // min fee higher than the computed one (142)
+        let tx_builder = TransactionBuilder::new(&account, &env)
+            .add_operation(operation.clone())
+            .with_min_fee(5_000);
+        assert_eq!(tx_builder.min_fee, Some(5_000));
+        let tx = tx_builder.simulate_and_build(&env, &account).await.unwrap();
+        assert_eq!(tx.fee, 5_000);
+
+        // min fee lower than the computed one
+        let tx = TransactionBuilder::new(&account, &env)
+            .add_operation(operation.clone())
+            .with_min_fee(120)
+            .simulate_and_build(&env, &account)
+            .await
+            .unwrap();
+        assert_eq!(tx.fee, 142);
+
+        // the min fee only applies to simulated builds
+        let tx = TransactionBuilder::new(&account, &env)
+            .add_operation(operation)
+            .with_min_fee(5_000)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(tx.fee, DEFAULT_TRANSACTION_FEES);
+    }
+
+    fn invocation_auth_entry(credentials: SorobanCredentials) -> SorobanAuthorizationEntry {
+        let mut entry = AuthorizationBuilder::for_contract_call(
+            &stellar_strkey::Contract([1; 32]),
+            "test",
+            vec![],
+        )
+        .unwrap()
+        .nonce(7)
+        .valid_until_ledger(1)
+        .build(&mock_signer1(), &[0; 32].into())
+        .unwrap()
+        .remove(0);
+        entry.credentials = credentials;
+        entry
+    }
+
+    fn op_auth(tx: &Transaction) -> Vec<SorobanAuthorizationEntry> {
+        match &tx.operations[0].body {
+            OperationBody::InvokeHostFunction(op) => op.auth.to_vec(),
+            _ => panic!("Expected InvokeHostFunction operation"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_simulate_and_build_source_account_auth() {
+        let account = Account::single(mock_signer1());
+        let entry = invocation_auth_entry(SorobanCredentials::SourceAccount);
+        let simulation = mock_simulate_tx_response_with_auth(vec![entry.clone()]);
+        let env = mock_env(None, Some(Ok(simulation)), None);
+        let operation =
+            Operations::invoke_contract(&mock_contract_id(account.clone(), &env), "test", vec![])
+                .unwrap();
+
+        let tx = TransactionBuilder::new(&account, &env)
+            .add_operation(operation)
+            .simulate_and_build(&env, &account)
+            .await
+            .unwrap();
+
+        // attached as is, the transaction signature covers it
+        assert_eq!(op_auth(&tx), vec![entry]);
+    }
+
+    #[tokio::test]
+    async fn test_simulate_and_build_address_auth() {
+        let account = Account::single(mock_signer1());
+        let other = mock_signer2();
+        let entry = invocation_auth_entry(SorobanCredentials::Address(SorobanAddressCredentials {
+            address: ScAddress::Account(other.account_id()),
+            nonce: 7,
+            signature_expiration_ledger: 0,
+            signature: stellar_xdr::curr::ScVal::Void,
+        }));
+        let mut simulation = mock_simulate_tx_response_with_auth(vec![entry]);
+        simulation.latest_ledger = 500;
+        let env = mock_env(None, Some(Ok(simulation)), None);
+        let operation =
+            Operations::invoke_contract(&mock_contract_id(account.clone(), &env), "test", vec![])
+                .unwrap();
+
+        let tx = TransactionBuilder::new(&account, &env)
+            .add_operation(operation)
+            .add_auth_signer(other.clone())
+            .simulate_and_build(&env, &account)
+            .await
+            .unwrap();
+
+        let auth = op_auth(&tx);
+        assert_eq!(auth.len(), 1);
+        match &auth[0].credentials {
+            SorobanCredentials::Address(credentials) => {
+                assert_eq!(credentials.address, ScAddress::Account(other.account_id()));
+                assert_eq!(credentials.nonce, 7);
+                assert_eq!(
+                    credentials.signature_expiration_ledger,
+                    500 + AUTH_VALIDITY_LEDGERS
+                );
+            }
+            _ => panic!("Expected address credentials"),
+        }
+        assert!(verify_authorization_entry(&auth[0], &env.network_id()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_and_build_address_auth_missing_signer() {
+        let account = Account::single(mock_signer1());
+        let entry = invocation_auth_entry(SorobanCredentials::Address(SorobanAddressCredentials {
+            address: ScAddress::Account(mock_signer2().account_id()),
+            nonce: 7,
+            signature_expiration_ledger: 0,
+            signature: stellar_xdr::curr::ScVal::Void,
+        }));
+        let simulation = mock_simulate_tx_response_with_auth(vec![entry]);
+        let env = mock_env(None, Some(Ok(simulation)), None);
+        let operation =
+            Operations::invoke_contract(&mock_contract_id(account.clone(), &env), "test", vec![])
+                .unwrap();
+
+        let res = TransactionBuilder::new(&account, &env)
+            .add_operation(operation)
+            .simulate_and_build(&env, &account)
+            .await;
+        assert!(matches!(res, Err(SorobanHelperError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_apply_resource_fee_margin() {
+        assert_eq!(
+            TransactionBuilder::apply_resource_fee_margin(100, 0).unwrap(),
+            100
+        );
+        assert_eq!(
+            TransactionBuilder::apply_resource_fee_margin(100, 20).unwrap(),
+            120
+        );
+        // rounds up so the margin is never lost to truncation
+        assert_eq!(
+            TransactionBuilder::apply_resource_fee_margin(101, 10).unwrap(),
+            112
+        );
+        assert!(TransactionBuilder::apply_resource_fee_margin(u64::MAX, 100).is_err());
+    }
+
     #[tokio::test]
     async fn test_set_env() {
         let account = Account::single(mock_signer1());
@@ -394,6 +1005,31 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_min_seq_preconditions() {
+        let account = Account::single(mock_signer1());
+        let env = mock_env(None, None, None);
+        let time_bounds = TimeBounds {
+            min_time: TimePoint(100),
+            max_time: TimePoint(200),
+        };
+
+        let tx_builder = TransactionBuilder::new(&account, &env)
+            .set_preconditions(Preconditions::Time(time_bounds.clone()))
+            .min_seq_ledger_gap(10)
+            .min_seq_num(SequenceNumber(42));
+
+        match tx_builder.preconditions {
+            Preconditions::V2(v2) => {
+                assert_eq!(v2.time_bounds, Some(time_bounds));
+                assert_eq!(v2.min_seq_ledger_gap, 10);
+                assert_eq!(v2.min_seq_num, Some(SequenceNumber(42)));
+                assert_eq!(v2.ledger_bounds, None);
+            }
+            other => panic!("Expected Preconditions::V2, got {:?}", other),
+        }
+    }
+
     #[tokio::test]
     async fn test_add_operation() {
         let account = Account::single(mock_signer1());
@@ -415,4 +1051,27 @@ mod test {
         assert_eq!(builder_with_two_ops.operations[0].body, operation1.body);
         assert_eq!(builder_with_two_ops.operations[1].body, operation2.body);
     }
+
+    #[tokio::test]
+    async fn test_add_operations() {
+        let account = Account::single(mock_signer1());
+        let env = mock_env(None, None, None);
+        let contract_id = mock_contract_id(account.clone(), &env);
+
+        let operations: Vec<_> = ["function1", "function2", "function3"]
+            .iter()
+            .map(|name| Operations::invoke_contract(&contract_id, name, vec![]).unwrap())
+            .collect();
+
+        let tx_builder = TransactionBuilder::new(&account, &env).add_operations(operations.clone());
+        assert_eq!(tx_builder.operations.len(), 3);
+        for (added, expected) in tx_builder.operations.iter().zip(&operations) {
+            assert_eq!(added.body, expected.body);
+        }
+
+        // set_operations replaces the list
+        let tx_builder = tx_builder.set_operations(vec![operations[2].clone()]);
+        assert_eq!(tx_builder.operations.len(), 1);
+        assert_eq!(tx_builder.operations[0].body, operations[2].body);
+    }
 }
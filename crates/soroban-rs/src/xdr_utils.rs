@@ -0,0 +1,82 @@
+//! # XDR Export and Import
+//!
+//! Transaction envelopes are exchanged as base64 encoded XDR, e.g. to inspect
+//! them in Stellar Laboratory or to hand them to another signer. This module
+//! provides the conversions between envelopes and their base64 XDR encoding.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use soroban_rs::xdr_utils;
+//! use stellar_xdr::curr::{TransactionEnvelope, TransactionV1Envelope};
+//!
+//! let envelope = TransactionEnvelope::Tx(TransactionV1Envelope::default());
+//! let encoded = xdr_utils::envelope_to_base64(&envelope).unwrap();
+//! assert_eq!(xdr_utils::envelope_from_base64(&encoded).unwrap(), envelope);
+//! ```
+use stellar_xdr::curr::{Limits, ReadXdr, TransactionEnvelope, WriteXdr};
+
+use crate::SorobanHelperError;
+
+/// Encodes a transaction envelope as base64 XDR.
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::XdrEncodingFailed` if the envelope can't be encoded
+pub fn envelope_to_base64(tx_envelope: &TransactionEnvelope) -> Result<String, SorobanHelperError> {
+    tx_envelope.to_xdr_base64(Limits::none()).map_err(|e| {
+        SorobanHelperError::XdrEncodingFailed(format!("Failed to encode envelope: {}", e))
+    })
+}
+
+/// Decodes a transaction envelope from base64 XDR.
+///
+/// # Errors
+///
+/// Returns `SorobanHelperError::XdrEncodingFailed` if `encoded` is not a
+/// base64 XDR transaction envelope
+pub fn envelope_from_base64(encoded: &str) -> Result<TransactionEnvelope, SorobanHelperError> {
+    TransactionEnvelope::from_xdr_base64(encoded.trim(), Limits::none()).map_err(|e| {
+        SorobanHelperError::XdrEncodingFailed(format!("Failed to decode envelope: {}", e))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{
+        create_mock_set_options_tx_envelope, mock_signer1, mock_transaction_envelope,
+    };
+    use crate::Account;
+    use stellar_xdr::curr::Hash;
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let mut account = Account::single(mock_signer1());
+        let envelope = account
+            .sign_transaction_envelope(
+                &mock_transaction_envelope(mock_signer1().account_id()),
+                &Hash([1; 32]),
+            )
+            .unwrap();
+
+        for envelope in [envelope, create_mock_set_options_tx_envelope()] {
+            let encoded = envelope_to_base64(&envelope).unwrap();
+            assert_eq!(envelope_from_base64(&encoded).unwrap(), envelope);
+        }
+    }
+
+    #[test]
+    fn test_envelope_from_base64_invalid() {
+        for invalid in ["", "not base64!", "AAAA"] {
+            assert!(
+                matches!(
+                    envelope_from_base64(invalid),
+                    Err(SorobanHelperError::XdrEncodingFailed(_))
+                ),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Integration tests running against a live or local network.
+//!
+//! They are only compiled with the `integration` feature, and skipped unless
+//! `SOROBAN_RPC_URL` is set:
+//!
+//! ```sh
+//! SOROBAN_RPC_URL=http://localhost:8000/rpc \
+//! SOROBAN_NETWORK_PASSPHRASE="Standalone Network ; February 2017" \
+//!     cargo test -p soroban-rs --features integration --test integration
+//! ```
+//!
+//! The network passphrase defaults to the testnet one. Accounts are funded with
+//! friendbot, at `SOROBAN_FRIENDBOT_URL` or the URL advertised by the RPC server.
+#![cfg(feature = "integration")]
+
+use ed25519_dalek::SigningKey;
+use soroban_rs::{
+    stellar_rpc_client, xdr::ScAddress, xdr::ScVal, Account, Contract, Env, EnvConfigs, IntoScVal,
+    Signer,
+};
+use std::env;
+
+const TESTNET_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+/// Connection to the network under test
+struct Network {
+    env: Env,
+    rpc_url: String,
+}
+
+/// Returns the network under test, or `None` if `SOROBAN_RPC_URL` isn't set
+fn network() -> Option<Network> {
+    let Ok(rpc_url) = env::var("SOROBAN_RPC_URL") else {
+        eprintln!("SOROBAN_RPC_URL not set, skipping integration test");
+        return None;
+    };
+    let network_passphrase =
+        env::var("SOROBAN_NETWORK_PASSPHRASE").unwrap_or_else(|_| TESTNET_PASSPHRASE.to_string());
+
+    let env = Env::new(EnvConfigs::new(rpc_url.clone(), network_passphrase))
+        .expect("Invalid network configuration");
+    Some(Network { env, rpc_url })
+}
+
+/// Creates a new account with a random key and funds it with friendbot
+async fn funded_account(network: &Network) -> Account {
+    let signer = Signer::new(SigningKey::from_bytes(&rand::random()));
+
+    let friendbot_url = match env::var("SOROBAN_FRIENDBOT_URL") {
+        Ok(url) => url,
+        Err(_) => stellar_rpc_client::Client::new(&network.rpc_url)
+            .expect("Invalid RPC URL")
+            .friendbot_url()
+            .await
+            .expect("The RPC server doesn't advertise a friendbot"),
+    };
+    let uri: hyper::Uri = format!("{}?addr={}", friendbot_url, signer.account_id())
+        .parse()
+        .expect("Invalid friendbot URL");
+
+    let https = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_native_roots()
+        .https_or_http()
+        .enable_http1()
+        .build();
+    let client: hyper::Client<_, hyper::Body> = hyper::Client::builder().build(https);
+    let response = client.get(uri).await.expect("Friendbot request failed");
+    assert!(
+        response.status().is_success(),
+        "Friendbot funding failed with status {}",
+        response.status()
+    );
+
+    Account::single(signer)
+}
+
+#[tokio::test]
+async fn test_deploy_and_invoke() {
+    let Some(network) = network() else {
+        return;
+    };
+    let mut account = funded_account(&network).await;
+
+    let contract = Contract::new("fixtures/soroban-helpers-example.wasm", None).unwrap();
+    let mut deployed = contract
+        .deploy(&network.env, &mut account, Some(vec![42_u32.into_val()]))
+        .await
+        .unwrap();
+    assert!(deployed.contract_id().is_some());
+
+    let address = ScVal::Address(ScAddress::Account(account.account_id()));
+    let response = deployed
+        .invoke("send", vec![address.clone(), address])
+        .await
+        .unwrap();
+    assert!(matches!(
+        response.get_return_value().unwrap(),
+        ScVal::Vec(Some(_))
+    ));
+}
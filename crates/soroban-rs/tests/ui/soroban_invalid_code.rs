@@ -0,0 +1,15 @@
+use soroban_rs::macros::soroban;
+
+soroban!(
+    r#"
+    pub struct Token;
+
+    impl Token {
+        pub fn transfer(env: &Env, amount u32) -> u32 {
+            amount
+        }
+    }
+"#
+);
+
+fn main() {}
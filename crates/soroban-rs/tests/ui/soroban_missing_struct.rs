@@ -0,0 +1,13 @@
+use soroban_rs::macros::soroban;
+
+soroban!(
+    r#"
+    impl Token {
+        pub fn transfer(env: &Env, amount: u32) -> u32 {
+            amount
+        }
+    }
+"#
+);
+
+fn main() {}
@@ -5,8 +5,8 @@
 //! ## Features
 //!
 //! - `#[test]` attribute macro: Simplifies writing tests for Soroban contracts by:
-//!   - Automatically creating a test environment
-//!   - Generating test addresses as needed
+//!   - Automatically creating a test environment, optionally configuring its ledger
+//!   - Generating test addresses, byte arrays and symbols as needed
 //!   - Reducing boilerplate in test code
 //!
 //! ## Example
@@ -22,39 +22,127 @@
 //!     // `user` is automatically generated with env
 //! }
 //! ```
+//!
+//! ## Environment configuration
+//!
+//! The ledger of the created environment can be configured through the attribute arguments:
+//!
+//! ```rust,ignore
+//! #[test(ledger_timestamp = 12345, protocol = 23, sequence_number = 100)]
+//! fn my_test(env: Env) {
+//!     assert_eq!(env.ledger().timestamp(), 12345);
+//! }
+//! ```
+//!
+//! A contract can be registered with `register`, passing `constructor_args` to its
+//! constructor, and its client is injected into the arguments of type `<Contract>Client`:
+//!
+//! ```rust,ignore
+//! #[test(register = Token, constructor_args = (19_u32,))]
+//! fn my_client_test(env: Env, client: TokenClient, user: Address) {}
+//! ```
+//!
+//! Generated addresses can be made reproducible across test runs and environments with a
+//! `seed`, which only affects the `Address` arguments:
+//!
+//! ```rust,ignore
+//! #[test(seed = 42)]
+//! fn my_seeded_test(env: Env, user: Address) {}
+//! ```
+//!
+//! Authorization checks can be mocked with the `mock_all_auths` flag, which calls
+//! `env.mock_all_auths()` right after the environment is created:
+//!
+//! ```rust,ignore
+//! #[test(mock_all_auths)]
+//! fn my_authorized_test(env: Env, admin: Address) {}
+//! ```
+//!
+//! ## Supported argument types
+//!
+//! | Type | Generated with |
+//! |------|----------------|
+//! | `Address`, `MuxedAddress` | `<T>::generate(&env)` |
+//! | `BytesN<N>` | `BytesN::from_array(&env, ..)`, filled with the argument position |
+//! | `Symbol` | `Symbol::new(&env, "<argument name>")` |
+//!
+//! Any other type can be generated with a `#[generate_with = <expr>]` attribute on the
+//! argument. The expression can refer to the environment as `env`:
+//!
+//! ```rust,ignore
+//! #[test]
+//! fn my_test(env: Env, #[generate_with = String::from_str(&env, "memo")] memo: String) {}
+//! ```
 
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{parse_macro_input, FnArg};
+use syn::{parse_macro_input, Expr, FnArg, Ident, Pat, PatType, Type};
 
 /// A procedural macro for simplifying Soroban contract tests.
 ///
 /// This macro transforms a function into a proper Soroban test by:
 ///
 /// 1. Creating a test environment automatically
-/// 2. Generating address arguments automatically
+/// 2. Generating the remaining arguments automatically
 /// 3. Wrapping the test in a proper `#[test]` attribute
 ///
+/// # Attribute Arguments
+///
+/// * `ledger_timestamp = <u64>` - Sets the ledger timestamp of the environment
+/// * `protocol = <u32>` - Sets the ledger protocol version of the environment
+/// * `sequence_number = <u32>` - Sets the ledger sequence number of the environment
+/// * `mock_all_auths` - Calls `env.mock_all_auths()` before generating the other arguments
+/// * `register = <Contract>` - Registers the contract in the environment, and injects its client
+///   into the arguments of type `<Contract>Client`
+/// * `constructor_args = <tuple>` - Arguments passed to the constructor of the registered
+///   contract, `()` by default
+/// * `seed = <u64>` - Derives the `Address` arguments deterministically from the seed and
+///   their order, instead of using `Address::generate(&env)`. Only the generated test
+///   addresses are affected, not the rest of the environment
+///
+/// Without arguments the environment is used as created by `Default::default()`.
+///
+/// # Returns
+///
+/// The test keeps the return type of the annotated function, so tests returning
+/// `Result<(), E>` fail when they return an `Err`, and can use the `?` operator.
+///
 /// # Parameters
 ///
-/// * The first parameter must be an environment type (`Env`) which will be instantiated using `Default::default()`
+/// * The first parameter must be an environment type (`Env`) which will be instantiated using `Default::default()`,
+///   or a reference to it (`&Env`) which will receive a reference to the created environment
 /// * Any additional parameters will be auto-generated based on their type:
-///   - For `Address` types: generated using `Address::generate(&env)`
-///   - For other Soroban data types: must support a similar `generate(&env)` pattern
+///   - For `BytesN<N>` types: a value filled with the argument position, so that
+///     every `BytesN` argument is distinct
+///   - For `Symbol` types: a symbol named after the argument
+///   - For the client of the contract given in `register`: created with `<T>::new(&env, &id)`
+///   - For arguments annotated with `#[generate_with = <expr>]`: the given expression,
+///     which can refer to the environment as `env`
+///   - For other types, like `Address`: generated using `<T>::generate(&env)`
 ///   - All generated values are properly passed to your test function
 ///
 /// # Example
 ///
 /// ```rust,no_run
 /// #[test]
-/// fn transfer_test(env: Env, sender: Address, receiver: Address) {
+/// fn transfer_test(env: Env, sender: Address, receiver: Address, salt: BytesN<32>) {
 ///     // Test logic here
 ///     // env will be created with Env::default()
 ///     // sender and receiver will be created with Address::generate(&env)
+///     // salt will be created with BytesN::from_array(&env, &[3; 32])
 /// }
 /// ```
 #[proc_macro_attribute]
-pub fn test(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn test(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut env_config = EnvConfig::default();
+    let config_parser = syn::meta::parser(|meta| env_config.parse(meta));
+    parse_macro_input!(attr with config_parser);
+    let client_name = match env_config.client_name() {
+        Ok(name) => name,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let mut address_counter = 0;
     let item_fn = parse_macro_input!(input as syn::ItemFn);
     let attrs = &item_fn.attrs;
     let sig = &item_fn.sig;
@@ -72,9 +160,10 @@ pub fn test(_attr: TokenStream, input: TokenStream) -> TokenStream {
                     "unexpected receiver argument in test signature",
                 ));
             };
+            let generate_with = generate_with(arg)?;
             let arg_binding = &arg.pat;
             let arg_ty = &arg.ty;
-            Ok((arg_binding, arg_ty))
+            Ok((arg_binding, arg_ty, generate_with))
         })
         .collect::<Result<Vec<_>, _>>()
     {
@@ -82,39 +171,286 @@ pub fn test(_attr: TokenStream, input: TokenStream) -> TokenStream {
         Err(err) => return err.to_compile_error().into(),
     };
 
-    let arg_defs = arg_binding_and_ty.iter().map(|(arg_binding, arg_ty)| {
+    let arg_defs = arg_binding_and_ty.iter().map(|(arg_binding, arg_ty, _)| {
         quote! {
           #arg_binding: #arg_ty
         }
     });
 
     // extracts the first Env argument and initializes with ::default()
-    let first_ty = arg_binding_and_ty
-        .first()
-        .map(|(_binding, ty)| ty)
-        .expect("at least one argument required");
-    let env_init = quote! { let env = <#first_ty>::default(); };
-
-    // extracts the following arguments (Addresses) and generates them passing the env as parameter.
-    let arg_inits = arg_binding_and_ty
-        .iter()
-        .enumerate()
-        .map(|(i, (_arg_binding, arg_ty))| {
-            if i == 0 {
-                quote! { env.clone() }
-            } else {
-                quote! { <#arg_ty>::generate(&env) }
-            }
-        });
+    let Some((_binding, first_ty, _)) = arg_binding_and_ty.first() else {
+        return syn::Error::new_spanned(
+            sig,
+            "test functions require at least an `Env` parameter, e.g. `fn my_test(env: Env)`",
+        )
+        .to_compile_error()
+        .into();
+    };
+    // a reference first argument, e.g. `env: &Env`, receives a reference to the created env.
+    let (env_ty, env_arg) = match first_ty.as_ref() {
+        Type::Reference(reference) => (reference.elem.as_ref(), quote! { &env }),
+        ty => (ty, quote! { env.clone() }),
+    };
+    let env_setup = env_config.setup();
+    let env_init = quote! {
+        let env = <#env_ty>::default();
+        #env_setup
+    };
+
+    // extracts the following arguments and generates them passing the env as parameter.
+    let arg_inits =
+        arg_binding_and_ty
+            .iter()
+            .enumerate()
+            .map(|(i, (arg_binding, arg_ty, generate_with))| {
+                if i == 0 {
+                    env_arg.clone()
+                } else if let Some(expr) = generate_with {
+                    quote! { #expr }
+                } else if is_client(arg_ty, client_name.as_ref()) {
+                    quote! { <#arg_ty>::new(&env, &contract_id) }
+                } else if let (Some(seed), true) = (env_config.seed, is_address(arg_ty)) {
+                    address_counter += 1;
+                    seeded_address(seed, address_counter, arg_ty)
+                } else {
+                    generate(i, arg_binding, arg_ty)
+                }
+            });
 
     quote! {
         #( #attrs )*
         #[test]
         fn #fn_name() #fn_return_type {
             #env_init
-            let test = | #( #arg_defs ),* | #fn_block;
+            let test = | #( #arg_defs ),* | #fn_return_type #fn_block;
             test( #( #arg_inits ),* )
         }
     }
     .into()
 }
+
+/// Ledger settings applied to the environment after its creation.
+#[derive(Default)]
+struct EnvConfig {
+    ledger_timestamp: Option<Expr>,
+    protocol: Option<Expr>,
+    sequence_number: Option<Expr>,
+    mock_all_auths: bool,
+    register: Option<Expr>,
+    constructor_args: Option<Expr>,
+    seed: Option<u64>,
+}
+
+impl EnvConfig {
+    /// Parses a single `key = value` attribute argument.
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("mock_all_auths") {
+            if self.mock_all_auths {
+                return Err(meta.error("duplicate argument"));
+            }
+            self.mock_all_auths = true;
+            return Ok(());
+        }
+        if meta.path.is_ident("seed") {
+            if self.seed.is_some() {
+                return Err(meta.error("duplicate argument"));
+            }
+            let seed: syn::LitInt = meta.value()?.parse()?;
+            self.seed = Some(seed.base10_parse()?);
+            return Ok(());
+        }
+        let field = if meta.path.is_ident("ledger_timestamp") {
+            &mut self.ledger_timestamp
+        } else if meta.path.is_ident("protocol") {
+            &mut self.protocol
+        } else if meta.path.is_ident("sequence_number") {
+            &mut self.sequence_number
+        } else if meta.path.is_ident("register") {
+            &mut self.register
+        } else if meta.path.is_ident("constructor_args") {
+            &mut self.constructor_args
+        } else {
+            return Err(meta.error(
+                "unsupported argument, expected one of `ledger_timestamp`, `protocol`, \
+                 `sequence_number`, `mock_all_auths`, `register`, `constructor_args` or `seed`",
+            ));
+        };
+        if field.is_some() {
+            return Err(meta.error("duplicate argument"));
+        }
+        *field = Some(meta.value()?.parse()?);
+        Ok(())
+    }
+
+    /// Returns the name of the client of the registered contract, e.g. `TokenClient`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registered contract isn't a path, or if constructor
+    /// arguments are given without a contract to register.
+    fn client_name(&self) -> syn::Result<Option<Ident>> {
+        let Some(register) = &self.register else {
+            if let Some(args) = &self.constructor_args {
+                return Err(syn::Error::new_spanned(
+                    args,
+                    "`constructor_args` requires a contract to `register`",
+                ));
+            }
+            return Ok(None);
+        };
+        let Expr::Path(path) = register else {
+            return Err(syn::Error::new_spanned(
+                register,
+                "expected the contract type, e.g. `register = Token`",
+            ));
+        };
+        let contract = path
+            .path
+            .segments
+            .last()
+            .map(|segment| &segment.ident)
+            .ok_or_else(|| syn::Error::new_spanned(path, "expected the contract type"))?;
+        Ok(Some(Ident::new(
+            &format!("{}Client", contract),
+            contract.span(),
+        )))
+    }
+
+    /// Builds the statements configuring `env`, empty when nothing is configured.
+    fn setup(&self) -> TokenStream2 {
+        let setters = [
+            (&self.ledger_timestamp, quote!(set_timestamp)),
+            (&self.protocol, quote!(set_protocol_version)),
+            (&self.sequence_number, quote!(set_sequence_number)),
+        ];
+        let calls = setters
+            .iter()
+            .filter_map(|(value, setter)| {
+                value
+                    .as_ref()
+                    .map(|value| quote! { env.ledger().#setter(#value); })
+            })
+            .collect::<Vec<_>>();
+        let mock_all_auths = self
+            .mock_all_auths
+            .then(|| quote! { env.mock_all_auths(); });
+        let ledger = (!calls.is_empty()).then(|| {
+            quote! {
+                use ::soroban_sdk::testutils::Ledger as _;
+                #( #calls )*
+            }
+        });
+        let register = self.register.as_ref().map(|contract| {
+            let args = self
+                .constructor_args
+                .as_ref()
+                .map_or_else(|| quote!(()), |args| quote!(#args));
+            quote! { let contract_id = env.register(#contract, #args); }
+        });
+        quote! {
+            #mock_all_auths
+            #ledger
+            #register
+        }
+    }
+}
+
+/// Extracts the expression of a `#[generate_with = <expr>]` attribute, if present.
+fn generate_with(arg: &PatType) -> syn::Result<Option<&Expr>> {
+    let mut exprs = arg
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("generate_with"));
+    let Some(attr) = exprs.next() else {
+        return Ok(None);
+    };
+    if let Some(duplicate) = exprs.next() {
+        return Err(syn::Error::new_spanned(
+            duplicate,
+            "duplicate `generate_with` attribute",
+        ));
+    }
+    match &attr.meta {
+        syn::Meta::NameValue(meta) => Ok(Some(&meta.value)),
+        meta => Err(syn::Error::new_spanned(
+            meta,
+            "expected `#[generate_with = <expr>]`",
+        )),
+    }
+}
+
+/// Returns whether `ty` is the client of the registered contract.
+fn is_client(ty: &Type, client_name: Option<&Ident>) -> bool {
+    let Some(client_name) = client_name else {
+        return false;
+    };
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == *client_name),
+        _ => false,
+    }
+}
+
+/// Returns whether `ty` is an `Address`.
+fn is_address(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Address"),
+        _ => false,
+    }
+}
+
+/// Builds the expression creating the `counter`-th `Address` argument from `seed`.
+///
+/// The address bytes are derived at expansion time with SplitMix64, so the same seed
+/// always yields the same addresses.
+fn seeded_address(seed: u64, counter: u64, ty: &Type) -> TokenStream2 {
+    let mut state = seed ^ counter.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    let bytes = (0..4)
+        .flat_map(|_| {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            (z ^ (z >> 31)).to_be_bytes()
+        })
+        .collect::<Vec<u8>>();
+    quote! {
+        <#ty as ::soroban_sdk::TryFromVal<::soroban_sdk::Env, ::soroban_sdk::xdr::ScAddress>>::try_from_val(
+            &env,
+            &::soroban_sdk::xdr::ScAddress::Contract(::soroban_sdk::xdr::ContractId(
+                ::soroban_sdk::xdr::Hash([#( #bytes ),*]),
+            )),
+        )
+        .expect("seeded address")
+    }
+}
+
+/// Builds the expression generating the argument at `position` from its type.
+fn generate(position: usize, binding: &Pat, ty: &Type) -> TokenStream2 {
+    let ident = match &ty {
+        Type::Path(path) => path.path.segments.last().map(|segment| &segment.ident),
+        _ => None,
+    };
+    match ident {
+        Some(ident) if ident == "BytesN" => {
+            let byte = position as u8;
+            quote! { <#ty>::from_array(&env, &::core::array::from_fn(|_| #byte)) }
+        }
+        Some(ident) if ident == "Symbol" => {
+            // symbols are limited to 32 characters, longer argument names are truncated.
+            let name = match binding {
+                Pat::Ident(pat) => pat.ident.to_string().chars().take(32).collect(),
+                _ => format!("arg{}", position),
+            };
+            quote! { <#ty>::new(&env, #name) }
+        }
+        _ => quote! { <#ty>::generate(&env) },
+    }
+}
@@ -0,0 +1,14 @@
+//! Tests for the compile errors reported by the `#[test]` attribute macro.
+//!
+//! The expected errors are stored next to each test case in `tests/ui`. To update
+//! them after changing an error message, run:
+//!
+//! ```sh
+//! TRYBUILD=overwrite cargo test -p soroban-test-helpers --test macros
+//! ```
+
+#[test]
+fn test_macro_compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,4 @@
+#[soroban_test_helpers::test]
+fn test_without_env() {}
+
+fn main() {}